@@ -16,13 +16,30 @@ impl CommandRunnable for AppsCommand {
         clap::Command::new(self.name())
             .version("1.0")
             .about("list applications which can be run through Git-Tool")
-            .long_about("Gets the list of applications that you have added to your configuration file. These applications can be run through the `open` and `scratch` commands.")
+            .long_about("Gets the list of applications that you have added to your configuration file, showing whether each of them is available on your machine. These applications can be run through the `open` and `scratch` commands.")
     }
 
     #[tracing::instrument(name = "gt apps", err, skip(self, core, _matches))]
     async fn run(&self, core: &Core, _matches: &ArgMatches) -> Result<i32, engine::Error> {
+        let width = core
+            .config()
+            .get_apps()
+            .map(|app| app.get_name().len())
+            .max()
+            .unwrap_or_default();
+
         for app in core.config().get_apps() {
-            writeln!(core.output(), "{}", app.get_name()).to_human_error()?;
+            if app.is_available() {
+                writeln!(core.output(), "{:width$}  available", app.get_name())
+            } else {
+                writeln!(
+                    core.output(),
+                    "{:width$}  missing ('{}' is not on your PATH)",
+                    app.get_name(),
+                    app.get_command()
+                )
+            }
+            .to_human_error()?;
         }
 
         Ok(0)
@@ -38,6 +55,7 @@ impl CommandRunnable for AppsCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::Config;
 
     #[tokio::test]
     async fn run() {
@@ -57,4 +75,28 @@ mod tests {
             "the output should contain the default app"
         );
     }
+
+    #[tokio::test]
+    async fn run_missing_app() {
+        let args = ArgMatches::default();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(
+                Config::from_str(
+                    "directory: /dev\napps:\n  - name: git\n    command: git\n  - name: missing\n    command: git-tool-definitely-not-a-real-command\n",
+                )
+                .unwrap(),
+            )
+            .with_console(console.clone())
+            .build();
+
+        let cmd = AppsCommand {};
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(
+            console.to_string(),
+            "git      available\nmissing  missing ('git-tool-definitely-not-a-real-command' is not on your PATH)\n"
+        );
+    }
 }
@@ -1,13 +1,19 @@
 use crate::engine::features;
 use crate::errors::HumanErrorResultExt;
+use crate::search;
+use futures::StreamExt;
+use itertools::Itertools;
 
 use super::async_trait;
 use super::*;
 use clap::Arg;
-use human_errors::OptionExt;
+use human_errors::{OptionExt, ResultExt};
 use online::registry::Registry;
 use tracing_batteries::prelude::*;
 
+/// The number of registry entries which are fetched at the same time when searching.
+const REGISTRY_SEARCH_CONCURRENCY: usize = 4;
+
 pub struct ConfigCommand;
 
 crate::command!(ConfigCommand);
@@ -24,16 +30,59 @@ impl CommandRunnable for ConfigCommand {
             .about("manage your Git-Tool configuration file")
             .long_about("This tool allows you to easily make changes to your Git-Tool config file.")
 
+            .subcommand(clap::Command::new("show")
+                .version("1.0")
+                .about("shows your current configuration")
+                .long_about("Shows the contents of your config file, or your effective configuration once includes, profiles and environment overrides have been applied.")
+                .arg(Arg::new("effective")
+                    .long("effective")
+                    .short('e')
+                    .help("show the effective value of each setting along with where it came from")
+                    .action(clap::ArgAction::SetTrue)))
+
+            .subcommand(clap::Command::new("validate")
+                .version("1.0")
+                .about("checks your config file for problems")
+                .long_about("Checks your config file (and any files it includes) for unknown keys, invalid service patterns, templates which cannot be rendered, duplicate entries, unknown feature flags and missing directories."))
+
+            .subcommand(clap::Command::new("lint")
+                .version("1.0")
+                .about("checks registry templates for problems")
+                .long_about("Checks registry templates for schema problems, unsupported platforms, URL and argument templates which cannot be rendered, service patterns which don't match their URL templates and unknown API kinds. Directories are searched for templates recursively.")
+                .arg(Arg::new("path")
+                    .index(1)
+                    .help("the template files, or directories containing templates, to check")
+                    .num_args(1..)
+                    .required(true)))
+
+            .subcommand(clap::Command::new("migrate")
+                .version("1.0")
+                .about("upgrades your config file to the latest format")
+                .long_about("Upgrades your config file (and any files it includes) to the latest version of the config format, keeping a backup of the original alongside it.")
+                .arg(Arg::new("check")
+                    .long("check")
+                    .help("only check whether your config file needs to be migrated, exiting with a non-zero status code if it does")
+                    .action(clap::ArgAction::SetTrue)))
+
             .subcommand(clap::Command::new("list")
                 .version("1.0")
                 .visible_alias("ls")
                 .about("list available config templates")
-                .long_about("Gets the list of config templates which are available through the Git-Tool registry."))
+                .long_about("Gets the list of config templates which are available through the registries in your config file and the Git-Tool registry."))
+
+            .subcommand(clap::Command::new("search")
+                .version("1.0")
+                .about("search for config templates in the registry")
+                .long_about("Searches the name, description and ID of the config templates which are available through your registries, showing which of them can be used on your platform and which you have already installed.")
+                .arg(Arg::new("query")
+                    .index(1)
+                    .help("the text you would like to search for")
+                    .required(true)))
 
             .subcommand(clap::Command::new("add")
                 .version("1.0")
                 .about("adds a configuration template to your current config file")
-                .long_about("Adds a configuration template from the Git-Tool online registry to your config file.")
+                .long_about("Adds a configuration template from one of the registries in your config file, or the Git-Tool online registry, to your config file.")
                 .arg(Arg::new("id")
                     .index(1)
                     .help("the id of the configuration template you want to add")
@@ -47,7 +96,38 @@ impl CommandRunnable for ConfigCommand {
                     .long("force")
                     .short('f')
                     .help("overwrites any existing entries with those from the template.")
-                    .action(clap::ArgAction::SetTrue)))
+                    .action(clap::ArgAction::SetTrue))
+                .arg(Arg::new("set")
+                    .long("set")
+                    .value_name("NAME=VALUE")
+                    .help("sets the value of one of the template's variables, instead of prompting for it")
+                    .action(clap::ArgAction::Append)))
+
+            .subcommand(clap::Command::new("outdated")
+                .version("1.0")
+                .about("lists the templates in your config file which have been updated")
+                .long_about("Checks the registry for newer versions of the templates you have added to your config file with `gt config add`."))
+
+            .subcommand(clap::Command::new("upgrade")
+                .version("1.0")
+                .about("upgrades the templates in your config file to their latest versions")
+                .long_about("Re-applies the latest version of the templates you have added to your config file with `gt config add`, showing the changes which are made to each app and service.")
+                .arg(Arg::new("id")
+                    .index(1)
+                    .help("the id of the template (or the app/service) you want to upgrade, all templates are upgraded if this is not provided")))
+
+            .subcommand(clap::Command::new("registry")
+                .version("1.0")
+                .about("manage the registries which templates are installed from")
+                .long_about("Lists the registries which Git-Tool searches for templates, or refreshes the copies of them which Git-Tool has cached.")
+                .subcommand(clap::Command::new("list")
+                    .version("1.0")
+                    .visible_alias("ls")
+                    .about("list the registries which are searched for templates"))
+                .subcommand(clap::Command::new("refresh")
+                    .version("1.0")
+                    .about("refreshes the cached copies of your registries")
+                    .long_about("Checks each of your registries for changes, updating the copies of them which Git-Tool has cached so that they can be used offline.")))
 
             .subcommand(clap::Command::new("alias")
                 .version("1.0")
@@ -93,6 +173,22 @@ impl CommandRunnable for ConfigCommand {
                     .short('w')
                     .help("configure the worktrees path instead of the repositories path")
                     .action(clap::ArgAction::SetTrue)))
+
+            .subcommand(clap::Command::new("profile")
+                .version("1.0")
+                .about("manage the profiles in your config file")
+                .long_about("List the profiles in your config file, or choose the profile which Git-Tool uses by default.")
+                .subcommand(clap::Command::new("list")
+                    .version("1.0")
+                    .visible_alias("ls")
+                    .about("list the profiles in your config file"))
+                .subcommand(clap::Command::new("use")
+                    .version("1.0")
+                    .about("sets the profile which is used by default")
+                    .arg(Arg::new("name")
+                        .index(1)
+                        .help("the name of the profile to use")
+                        .required(true))))
     }
 
     #[tracing::instrument(name = "gt config", err, skip(self, core, matches))]
@@ -100,8 +196,144 @@ impl CommandRunnable for ConfigCommand {
         let mut cfg = core.config().clone();
 
         let save_config = match matches.subcommand() {
+            Some(("show", args)) if args.get_flag("effective") => {
+                let mut output = core.output();
+                for value in core.config().get_effective_values() {
+                    writeln!(output, "{value}").to_human_error()?;
+                }
+
+                false
+            }
+            Some(("validate", _args)) => {
+                let diagnostics = engine::validate_config(core.config());
+
+                let mut output = core.output();
+                for diagnostic in diagnostics.iter() {
+                    writeln!(output, "{diagnostic}").to_human_error()?;
+                }
+
+                if diagnostics.is_empty() {
+                    writeln!(output, "Your config is valid.").to_human_error()?;
+                }
+
+                if diagnostics
+                    .iter()
+                    .any(|d| d.severity == engine::Severity::Error)
+                {
+                    return Ok(1);
+                }
+
+                false
+            }
+            Some(("lint", args)) => {
+                let mut files = Vec::new();
+                for path in args.get_many::<String>("path").into_iter().flatten() {
+                    find_templates(std::path::Path::new(path), &mut files)?;
+                }
+
+                if files.is_empty() {
+                    return Err(human_errors::user(
+                        "No registry templates were found at the paths you provided.",
+                        &[
+                            "Provide the path to a template file, or a directory containing `*.yaml` templates.",
+                        ],
+                    ));
+                }
+
+                let mut output = core.output();
+                let mut problems = 0;
+                let mut errors = false;
+                for file in files.iter() {
+                    let content = std::fs::read_to_string(file).wrap_user_err(
+                        format!("Could not read the template '{}'.", file.display()),
+                        &["Make sure that the file exists and that you have permission to read it."],
+                    )?;
+
+                    let diagnostics = engine::validate_template(Some(file), &content);
+                    for diagnostic in diagnostics.iter() {
+                        writeln!(output, "{diagnostic}").to_human_error()?;
+                    }
+
+                    problems += diagnostics.len();
+                    errors = errors
+                        || diagnostics
+                            .iter()
+                            .any(|d| d.severity == engine::Severity::Error);
+                }
+
+                if problems == 0 {
+                    writeln!(output, "All {} templates are valid.", files.len())
+                        .to_human_error()?;
+                }
+
+                if errors {
+                    return Ok(1);
+                }
+
+                false
+            }
+            Some(("migrate", args)) => {
+                let check = args.get_flag("check");
+                let files: Vec<std::path::PathBuf> = core
+                    .config()
+                    .get_config_files()
+                    .into_iter()
+                    .map(|f| f.to_path_buf())
+                    .collect();
+
+                if files.is_empty() {
+                    writeln!(core.output(), "You do not have a config file to migrate.")
+                        .to_human_error()?;
+                }
+
+                let mut outdated = false;
+                for file in files {
+                    let migration = engine::migration::migrate_file(&file)?;
+                    if !migration.is_required() {
+                        writeln!(
+                            core.output(),
+                            "{} is up to date (v{}).",
+                            file.display(),
+                            migration.to
+                        )
+                        .to_human_error()?;
+                        continue;
+                    }
+
+                    outdated = true;
+                    writeln!(
+                        core.output(),
+                        "{} needs to be migrated from v{} to v{}:",
+                        file.display(),
+                        migration.from,
+                        migration.to
+                    )
+                    .to_human_error()?;
+                    for change in migration.changes.iter() {
+                        writeln!(core.output(), " - {change}").to_human_error()?;
+                    }
+
+                    if !check && !core.dry_run() {
+                        let backup = migration.save(&file).await?;
+                        writeln!(
+                            core.output(),
+                            "Migrated {} to v{}, the original has been saved to {}.",
+                            file.display(),
+                            migration.to,
+                            backup.display()
+                        )
+                        .to_human_error()?;
+                    }
+                }
+
+                if check && outdated {
+                    return Ok(1);
+                }
+
+                false
+            }
             Some(("list", _args)) => {
-                let registry = online::GitHubRegistry;
+                let registry = online::CompositeRegistry::from_config(core.config());
 
                 let entries = registry.get_entries(core).await?;
                 let mut output = core.output();
@@ -111,32 +343,301 @@ impl CommandRunnable for ConfigCommand {
 
                 false
             }
+            Some(("search", args)) => {
+                let query = args
+                    .get_one::<String>("query")
+                    .map(|q| q.as_str())
+                    .unwrap_or_default();
+                let registry = online::CompositeRegistry::from_config(core.config());
+                let ids = registry.get_entries(core).await?;
+
+                // Entries are fetched a few at a time so that we don't flood the registry
+                // with requests when it holds a lot of templates.
+                let registry = &registry;
+                let entries: Vec<(String, online::registry::Entry)> =
+                    futures::stream::iter(ids.into_iter().map(|id| async move {
+                        let entry = registry.get_entry(core, &id).await;
+                        (id, entry)
+                    }))
+                    .buffered(REGISTRY_SEARCH_CONCURRENCY)
+                    .collect::<Vec<_>>()
+                    .await
+                    .into_iter()
+                    .filter_map(|(id, entry)| match entry {
+                        Ok(entry) => Some((id, entry)),
+                        Err(err) => {
+                            warn!("Could not fetch the registry entry {}: {}", id, err);
+                            None
+                        }
+                    })
+                    .collect();
+
+                let installed_ids: Vec<&str> = core
+                    .config()
+                    .get_installed_templates()
+                    .into_iter()
+                    .map(|(_, installed)| installed.id.as_str())
+                    .collect();
+
+                let matches = search::best_matches_by(query, entries.iter(), |(id, entry)| {
+                    format!("{id} {} {}", entry.name, entry.description)
+                });
+
+                if matches.is_empty() {
+                    writeln!(
+                        core.output(),
+                        "No config templates matched '{query}', use `gt config list` to see all of the available templates."
+                    )
+                    .to_human_error()?;
+                }
+
+                for (id, entry) in matches {
+                    let compatible: Vec<&online::registry::EntryConfig> = entry
+                        .configs
+                        .iter()
+                        .filter(|ec| ec.is_compatible())
+                        .collect();
+
+                    let installed = installed_ids.contains(&id.as_str())
+                        || compatible.iter().any(|ec| {
+                            ec.app
+                                .as_ref()
+                                .is_some_and(|app| core.config().get_app(&app.name).is_some())
+                                || ec
+                                    .service
+                                    .as_ref()
+                                    .is_some_and(|svc| core.config().get_service(&svc.name).is_ok())
+                        });
+
+                    let mut tags = Vec::new();
+                    if installed {
+                        tags.push("installed".to_string());
+                    }
+                    if compatible.is_empty() {
+                        tags.push(format!(
+                            "not available on {}, only {}",
+                            std::env::consts::OS,
+                            entry
+                                .configs
+                                .iter()
+                                .map(|ec| ec.platform.as_str())
+                                .join(", ")
+                        ));
+                    }
+
+                    if tags.is_empty() {
+                        writeln!(core.output(), "{id}: {}", entry.name)
+                    } else {
+                        writeln!(core.output(), "{id}: {} [{}]", entry.name, tags.join("; "))
+                    }
+                    .to_human_error()?;
+                    writeln!(core.output(), "  {}", entry.description).to_human_error()?;
+                }
+
+                false
+            }
             Some(("add", args)) => {
                 let id = args.get_one::<String>("id").ok_or_user_err(
                     "You have not provided an ID for the config template you wish to add.",
                     &["Please provide the ID of the config template when running this command (e.g. `git-tool config add apps/bash`)."],
                 )?;
 
-                let registry = online::GitHubRegistry;
+                let mut provided = std::collections::BTreeMap::new();
+                for value in args.get_many::<String>("set").into_iter().flatten() {
+                    let (name, value) = value.split_once('=').ok_or_user_err(
+                        format!("The variable '{value}' is not in the NAME=VALUE format."),
+                        &["Provide the values for the template's variables using `--set name=value`."],
+                    )?;
+                    provided.insert(name.to_string(), value.to_string());
+                }
+
+                let registry = online::CompositeRegistry::from_config(core.config());
                 let entry = registry.get_entry(core, id).await?;
 
                 writeln!(core.output(), "Applying {}", entry.name).to_human_error()?;
                 writeln!(core.output(), "> {}", entry.description).to_human_error()?;
 
+                let variables = entry.get_variables(core, &provided)?;
+
                 for ec in entry.configs {
                     if ec.is_compatible() {
+                        let ec = ec.with_variables(&variables);
                         let ec = if let Some(name) = args.get_one::<String>("as") {
                             ec.with_name(name)
                         } else {
                             ec
                         };
 
-                        cfg = cfg.apply_template(ec, args.get_flag("force"))?;
+                        cfg = cfg
+                            .apply_template(ec.clone(), args.get_flag("force"))?
+                            .with_installed_template(&ec, id, &entry.version, &variables);
                     }
                 }
 
                 true
             }
+            Some(("outdated", _args)) => {
+                let registry = online::CompositeRegistry::from_config(core.config());
+
+                let mut outdated = false;
+                for (key, installed) in core.config().get_installed_templates() {
+                    let entry = match registry.get_entry(core, &installed.id).await {
+                        Ok(entry) => entry,
+                        Err(err) => {
+                            writeln!(
+                                core.output(),
+                                "Could not check {key} for updates: {}",
+                                err.message()
+                            )
+                            .to_human_error()?;
+                            continue;
+                        }
+                    };
+
+                    if installed.is_outdated(&entry.version) {
+                        outdated = true;
+                        writeln!(
+                            core.output(),
+                            "{key} ({}): {} -> {}",
+                            installed.id,
+                            installed.version,
+                            entry.version
+                        )
+                        .to_human_error()?;
+                    }
+                }
+
+                if !outdated {
+                    writeln!(
+                        core.output(),
+                        "All of the templates in your config file are up to date."
+                    )
+                    .to_human_error()?;
+                }
+
+                false
+            }
+            Some(("upgrade", args)) => {
+                let filter = args.get_one::<String>("id");
+                let registry = online::CompositeRegistry::from_config(core.config());
+
+                let mut upgraded = false;
+                let mut skipped = false;
+                for (key, installed) in core.config().get_installed_templates() {
+                    if filter.is_some_and(|f| f != key && f != &installed.id) {
+                        continue;
+                    }
+
+                    let Some((kind, name)) = key.split_once('/') else {
+                        continue;
+                    };
+
+                    let entry = match registry.get_entry(core, &installed.id).await {
+                        Ok(entry) => entry,
+                        Err(err) => {
+                            writeln!(
+                                core.output(),
+                                "Could not check {key} for updates: {}",
+                                err.message()
+                            )
+                            .to_human_error()?;
+                            skipped = true;
+                            continue;
+                        }
+                    };
+
+                    if !installed.is_outdated(&entry.version) {
+                        continue;
+                    }
+
+                    // Re-applying the template would replace the whole entry, so we don't
+                    // upgrade apps and services which you have changed since installing them.
+                    if cfg.is_template_modified(key) {
+                        writeln!(
+                            core.output(),
+                            "Skipping {key} because it has been modified since it was installed. Use `gt config add {} --name {name} --force` to replace it with version {}.",
+                            installed.id,
+                            entry.version
+                        )
+                        .to_human_error()?;
+                        skipped = true;
+                        continue;
+                    }
+
+                    // Re-use the values you provided when the template was installed, only
+                    // prompting for variables which have been added since then.
+                    let provided = installed
+                        .variables
+                        .iter()
+                        .filter(|(name, _)| entry.variables.iter().any(|v| &v.name == *name))
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect();
+                    let variables = entry.get_variables(core, &provided)?;
+
+                    writeln!(
+                        core.output(),
+                        "Upgrading {key} from {} to {}",
+                        installed.version,
+                        entry.version
+                    )
+                    .to_human_error()?;
+
+                    for ec in entry.configs.into_iter().filter(|ec| ec.is_compatible()) {
+                        // Only the part of the template which was installed under this
+                        // name is upgraded, keeping the name you chose for it.
+                        let mut ec = ec.with_variables(&variables).with_name(name);
+                        if kind != "apps" {
+                            ec.app = None;
+                        }
+                        if kind != "services" {
+                            ec.service = None;
+                        }
+
+                        if ec.app.is_none() && ec.service.is_none() {
+                            continue;
+                        }
+
+                        let updated = cfg
+                            .apply_template(ec.clone(), true)?
+                            .with_installed_template(
+                                &ec,
+                                &installed.id,
+                                &entry.version,
+                                &variables,
+                            );
+
+                        let (current, latest) = match kind {
+                            "apps" => (
+                                serde_yaml::to_string(&cfg.get_app(name)),
+                                serde_yaml::to_string(&updated.get_app(name)),
+                            ),
+                            _ => (
+                                serde_yaml::to_string(&cfg.get_service(name).ok()),
+                                serde_yaml::to_string(&updated.get_service(name).ok()),
+                            ),
+                        };
+
+                        for line in
+                            diff_lines(&current.to_human_error()?, &latest.to_human_error()?)
+                        {
+                            writeln!(core.output(), "{line}").to_human_error()?;
+                        }
+
+                        cfg = updated;
+                        upgraded = true;
+                    }
+                }
+
+                if !upgraded && !skipped {
+                    writeln!(
+                        core.output(),
+                        "All of the templates in your config file are up to date."
+                    )
+                    .to_human_error()?;
+                }
+
+                upgraded && !core.dry_run()
+            }
             Some(("alias", args)) => match args.get_one::<String>("alias") {
                 Some(alias) => {
                     if args.get_flag("delete") {
@@ -273,6 +774,70 @@ impl CommandRunnable for ConfigCommand {
                     false
                 }
             },
+            Some(("registry", args)) => match args.subcommand() {
+                Some(("refresh", _)) => {
+                    let registry = online::CompositeRegistry::from_config(core.config());
+                    registry.refresh(core).await?;
+
+                    writeln!(core.output(), "Your registries have been refreshed.")
+                        .to_human_error()?;
+
+                    false
+                }
+                _ => {
+                    let mut output = core.output();
+                    for source in core.config().get_registries() {
+                        writeln!(output, "{source}").to_human_error()?;
+                    }
+                    writeln!(output, "{} (built-in)", GIT_TOOL_REGISTRY).to_human_error()?;
+
+                    false
+                }
+            },
+            Some(("profile", args)) => match args.subcommand() {
+                Some(("list", _)) => {
+                    let mut output = core.output();
+                    for name in core.config().get_profile_names() {
+                        writeln!(
+                            output,
+                            "{} {}{}",
+                            if core.config().get_active_profile_name() == Some(name) {
+                                "*"
+                            } else {
+                                " "
+                            },
+                            name,
+                            if core.config().get_default_profile_name() == Some(name) {
+                                " (default)"
+                            } else {
+                                ""
+                            }
+                        )
+                        .to_human_error()?;
+                    }
+
+                    false
+                }
+                Some(("use", args)) => {
+                    let name = args.get_one::<String>("name").ok_or_user_err(
+                        "You have not provided the name of the profile you wish to use.",
+                        &["Please provide the name of the profile when running this command (e.g. `git-tool config profile use work`)."],
+                    )?;
+
+                    cfg = cfg.with_default_profile(name)?;
+
+                    true
+                }
+                _ => {
+                    match core.config().get_active_profile_name() {
+                        Some(name) => writeln!(core.output(), "{name}"),
+                        None => writeln!(core.output(), "No profile is currently active."),
+                    }
+                    .to_human_error()?;
+
+                    false
+                }
+            },
             _ => {
                 writeln!(core.output(), "{}", core.config().to_string()?).to_human_error()?;
 
@@ -282,6 +847,20 @@ impl CommandRunnable for ConfigCommand {
 
         if save_config {
             match cfg.get_config_file() {
+                Some(path) if core.dry_run() => {
+                    writeln!(
+                        core.output(),
+                        "[dry-run] Update '{}' with the following changes:",
+                        path.display()
+                    )
+                    .to_human_error()?;
+                    for line in diff_lines(&core.config().to_string()?, &cfg.to_string()?)
+                        .into_iter()
+                        .filter(|line| !line.starts_with("  "))
+                    {
+                        writeln!(core.output(), "{line}").to_human_error()?;
+                    }
+                }
                 Some(path) => {
                     cfg.save(&path).await?;
                 }
@@ -300,9 +879,31 @@ impl CommandRunnable for ConfigCommand {
     )]
     async fn complete(&self, core: &Core, completer: &Completer, matches: &ArgMatches) {
         match matches.subcommand() {
+            Some(("show", args)) => {
+                if !args.get_flag("effective") {
+                    completer.offer("--effective");
+                }
+            }
+            Some(("lint", _)) => {}
+            Some(("migrate", args)) => {
+                if !args.get_flag("check") {
+                    completer.offer("--check");
+                }
+            }
             Some(("list", _)) => {}
+            Some(("outdated", _)) => {}
+            Some(("upgrade", args)) => {
+                if !args.contains_id("id") {
+                    completer.offer_many(
+                        core.config()
+                            .get_installed_templates()
+                            .into_iter()
+                            .map(|(key, _)| key),
+                    );
+                }
+            }
             Some(("add", _)) => {
-                let registry = online::GitHubRegistry;
+                let registry = online::CompositeRegistry::from_config(core.config());
                 if let Ok(entries) = registry.get_entries(core).await {
                     completer.offer_many(entries);
                 }
@@ -338,13 +939,109 @@ impl CommandRunnable for ConfigCommand {
                     completer.offer("--worktree");
                 }
             }
+            Some(("registry", args)) => match args.subcommand() {
+                Some(("list", _)) => {}
+                Some(("refresh", _)) => {}
+                _ => {
+                    completer.offer_many(vec!["list", "refresh"]);
+                }
+            },
+            Some(("profile", args)) => match args.subcommand() {
+                Some(("list", _)) => {}
+                Some(("use", _)) => {
+                    completer.offer_many(core.config().get_profile_names());
+                }
+                _ => {
+                    completer.offer_many(vec!["list", "use"]);
+                }
+            },
             _ => {
-                completer.offer_many(vec!["list", "add", "alias", "feature", "path"]);
+                completer.offer_many(vec![
+                    "show", "validate", "lint", "migrate", "list", "search", "add", "outdated",
+                    "upgrade", "registry", "alias", "feature", "path", "profile",
+                ]);
             }
         }
     }
 }
 
+/// Finds the registry templates at `path`, searching directories recursively for
+/// `*.yaml` files.
+fn find_templates(
+    path: &std::path::Path,
+    files: &mut Vec<std::path::PathBuf>,
+) -> Result<(), human_errors::Error> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries: Vec<std::path::PathBuf> = std::fs::read_dir(path)
+        .wrap_user_err(
+            format!("Could not read the directory '{}'.", path.display()),
+            &["Make sure that the directory exists and that you have permission to read it."],
+        )?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            find_templates(&entry, files)?;
+        } else if entry
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// The location of the built-in Git-Tool registry, which is searched after any
+/// registries in your config file.
+const GIT_TOOL_REGISTRY: &str = "https://github.com/SierraSoftworks/git-tool/tree/main/registry";
+
+/// Produces a line-by-line diff of two documents, prefixing removed lines with `-`,
+/// added lines with `+` and unchanged lines with a space.
+fn diff_lines(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // The length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+
+    lines.extend(old[i..].iter().map(|line| format!("- {line}")));
+    lines.extend(new[j..].iter().map(|line| format!("+ {line}")));
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -378,10 +1075,12 @@ mod tests {
     #[tokio::test]
     #[cfg_attr(feature = "pure-tests", ignore)]
     async fn run_list() {
+        let temp = tempfile::tempdir().unwrap();
         let cfg = Config::from_str("directory: /dev").unwrap();
         let console = crate::console::mock();
         let core = Core::builder()
             .with_config(cfg)
+            .with_registry_cache_directory(temp.path().join("cache"))
             .with_console(console.clone())
             .build();
 
@@ -403,10 +1102,12 @@ mod tests {
     #[tokio::test]
     #[cfg_attr(feature = "pure-tests", ignore)]
     async fn run_add_no_file() {
+        let temp = tempfile::tempdir().unwrap();
         let cfg = Config::from_str("directory: /dev").unwrap();
         let console = crate::console::mock();
         let core = Core::builder()
             .with_config(cfg)
+            .with_registry_cache_directory(temp.path().join("cache"))
             .with_console(console.clone())
             .build();
 
@@ -424,12 +1125,30 @@ mod tests {
     }
 
     #[tokio::test]
-    #[cfg_attr(feature = "pure-tests", ignore)]
-    async fn run_add_with_file() {
+    async fn run_add_from_registry() {
         let temp = tempfile::tempdir().unwrap();
+        let registry = temp.path().join("registry");
+        std::fs::create_dir_all(registry.join("apps")).unwrap();
+        std::fs::write(
+            registry.join("apps").join("internal-ide.yaml"),
+            r#"
+name: Internal IDE
+description: The IDE used within our company.
+configs:
+  - platform: any
+    app:
+      name: ide
+      command: ide
+"#,
+        )
+        .unwrap();
+
         tokio::fs::write(
             temp.path().join("config.yml"),
-            Config::default().to_string().unwrap(),
+            format!(
+                "directory: /dev\nregistries:\n  - path: {}\n",
+                registry.display()
+            ),
         )
         .await
         .unwrap();
@@ -438,33 +1157,71 @@ mod tests {
         let core = Core::builder()
             .with_config_file(temp.path().join("config.yml"))
             .expect("the config should be loaded")
+            .with_registry_cache_directory(temp.path().join("cache"))
             .with_console(console.clone())
             .build();
 
         let cmd = ConfigCommand {};
         let args = cmd
             .app()
-            .get_matches_from(vec!["config", "add", "apps/bash"]);
-
+            .get_matches_from(vec!["config", "add", "apps/internal-ide"]);
         cmd.assert_run_successful(&core, &args).await;
 
         assert!(
-            console.to_string().contains("Applying Bash\n> "),
-            "the output should describe what is being done"
+            console.to_string().contains("Applying Internal IDE\n"),
+            "the entry should be loaded from the configured registry: {console}"
         );
 
         let new_cfg = Config::from_file(&temp.path().join("config.yml")).unwrap();
         assert!(
-            new_cfg.get_app("bash").is_some(),
-            "the app should be added to the config file"
+            new_cfg.get_app("ide").is_some(),
+            "the app should have been added to the config file"
         );
     }
 
     #[tokio::test]
-    async fn run_alias_list() {
-        let cfg = Config::from_str(
-            r#"
-directory: /dev
+    #[cfg_attr(feature = "pure-tests", ignore)]
+    async fn run_add_with_file() {
+        let temp = tempfile::tempdir().unwrap();
+        tokio::fs::write(
+            temp.path().join("config.yml"),
+            Config::default().to_string().unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config_file(temp.path().join("config.yml"))
+            .expect("the config should be loaded")
+            .with_registry_cache_directory(temp.path().join("cache"))
+            .with_console(console.clone())
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "add", "apps/bash"]);
+
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert!(
+            console.to_string().contains("Applying Bash\n> "),
+            "the output should describe what is being done"
+        );
+
+        let new_cfg = Config::from_file(&temp.path().join("config.yml")).unwrap();
+        assert!(
+            new_cfg.get_app("bash").is_some(),
+            "the app should be added to the config file"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_alias_list() {
+        let cfg = Config::from_str(
+            r#"
+directory: /dev
 
 aliases:
   test1: example.com/tests/test1
@@ -560,6 +1317,44 @@ aliases:
         );
     }
 
+    #[tokio::test]
+    async fn run_alias_add_dry_run() {
+        let temp = tempfile::tempdir().unwrap();
+        let original = Config::default().to_string().unwrap();
+        tokio::fs::write(temp.path().join("config.yml"), &original)
+            .await
+            .unwrap();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config_file(temp.path().join("config.yml"))
+            .expect("the config should be loaded")
+            .with_console(console.clone())
+            .with_dry_run(true)
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args =
+            cmd.app()
+                .get_matches_from(vec!["config", "alias", "test", "example.com/tests/test"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output
+                .lines()
+                .any(|line| line.starts_with("+ ") && line.contains("test: example.com/tests/test")),
+            "the output should describe the change which would be made: {output}"
+        );
+        assert_eq!(
+            tokio::fs::read_to_string(temp.path().join("config.yml"))
+                .await
+                .unwrap(),
+            original,
+            "the config file should not be modified"
+        );
+    }
+
     #[tokio::test]
     async fn run_alias_delete() {
         let temp = tempfile::tempdir().unwrap();
@@ -748,4 +1543,700 @@ directory: "{}"
 
         test_completions_with_config(&cfg, "gt config", "", vec!["path"]).await;
     }
+
+    #[tokio::test]
+    async fn run_profile_list() {
+        let cfg = Config::from_str(
+            r#"
+directory: /dev
+profile: work
+profiles:
+    work:
+        directory: /work
+    personal: {}
+"#,
+        )
+        .unwrap()
+        .with_profile("personal")
+        .unwrap();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(console.clone())
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "profile", "list"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(console.to_string(), "* personal\n  work (default)\n");
+    }
+
+    #[tokio::test]
+    async fn run_profile_use() {
+        let temp = tempfile::tempdir().unwrap();
+        tokio::fs::write(
+            temp.path().join("config.yml"),
+            r#"
+directory: /dev
+profiles:
+    work:
+        directory: /work
+"#,
+        )
+        .await
+        .unwrap();
+
+        let cfg = Config::from_file(&temp.path().join("config.yml")).unwrap();
+        let core = Core::builder().with_config(cfg).with_null_console().build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "profile", "use", "work"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let new_cfg = Config::from_file(&temp.path().join("config.yml")).unwrap();
+        assert_eq!(new_cfg.get_default_profile_name(), Some("work"));
+        assert_eq!(
+            new_cfg.get_dev_directory(),
+            PathBuf::from("/dev"),
+            "the root directory should not be replaced by the profile's"
+        );
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "profile", "use", "missing"]);
+        cmd.run(&core, &args)
+            .await
+            .expect_err("an unknown profile should be rejected");
+    }
+
+    #[tokio::test]
+    async fn run_show_effective() {
+        let temp = tempfile::tempdir().unwrap();
+        tokio::fs::write(
+            temp.path().join("team.yml"),
+            r#"
+directory: /team
+aliases:
+    gt: gh:SierraSoftworks/git-tool
+"#,
+        )
+        .await
+        .unwrap();
+        tokio::fs::write(
+            temp.path().join("config.yml"),
+            r#"
+include:
+    - team.yml
+aliases:
+    mine: gh:example/mine
+"#,
+        )
+        .await
+        .unwrap();
+
+        let cfg = Config::from_file(&temp.path().join("config.yml"))
+            .unwrap()
+            .with_environment(vec![(
+                "GITTOOL_FEATURE_CREATE_REMOTE".to_string(),
+                "false".to_string(),
+            )]);
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(console.clone())
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "show", "--effective"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output.contains(&format!(
+                "directory = /team ({})\n",
+                temp.path().join("team.yml").display()
+            )),
+            "the directory should come from the included file: {output}"
+        );
+        assert!(
+            output.contains(&format!(
+                "aliases.mine = gh:example/mine ({})\n",
+                temp.path().join("config.yml").display()
+            )),
+            "the alias should come from the config file: {output}"
+        );
+        assert!(
+            output.contains("features.create_remote = false ($GITTOOL_FEATURE_CREATE_REMOTE)\n"),
+            "the feature flag should come from the environment: {output}"
+        );
+        assert!(
+            output.contains("apps = shell (default)\n"),
+            "the default apps should be listed: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_validate() {
+        let temp = tempfile::tempdir().unwrap();
+        tokio::fs::write(
+            temp.path().join("config.yml"),
+            format!(
+                r#"
+directory: "{}"
+scratchpad: /scratch
+"#,
+                temp.path().display()
+            ),
+        )
+        .await
+        .unwrap();
+
+        let cfg = Config::from_file(&temp.path().join("config.yml")).unwrap();
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(console.clone())
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd.app().get_matches_from(vec!["config", "validate"]);
+        assert_eq!(cmd.run(&core, &args).await.unwrap(), 1);
+
+        assert!(
+            console.to_string().contains(&format!(
+                "{}:3:1: error: Unknown key 'scratchpad'",
+                temp.path().join("config.yml").display()
+            )),
+            "the output should describe the unknown key: {console}"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_migrate() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.yml");
+        let original = format!(
+            r#"directory: "{}"
+services:
+  - domain: github.com
+    website: "https://{{{{ .Service.Domain }}}}/{{{{ .Repo.FullName }}}}"
+    httpUrl: "https://{{{{ .Service.Domain }}}}/{{{{ .Repo.FullName }}}}.git"
+    gitUrl: "git@{{{{ .Service.Domain }}}}:{{{{ .Repo.FullName }}}}.git"
+    pattern: "*/*"
+"#,
+            temp.path().display()
+        );
+        tokio::fs::write(&path, &original).await.unwrap();
+
+        let cfg = Config::from_file(&path).unwrap();
+        assert!(
+            cfg.get_service("github.com").is_ok(),
+            "older config files should be migrated when they are loaded"
+        );
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(console.clone())
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "migrate", "--check"]);
+        assert_eq!(cmd.run(&core, &args).await.unwrap(), 1);
+        assert!(
+            console
+                .to_string()
+                .contains("Renamed the `domain` of the 'github.com' service to `name`"),
+            "the output should describe the changes: {console}"
+        );
+        assert_eq!(
+            tokio::fs::read_to_string(&path).await.unwrap(),
+            original,
+            "checking should not modify the config file"
+        );
+
+        let args = cmd.app().get_matches_from(vec!["config", "migrate"]);
+        assert_eq!(cmd.run(&core, &args).await.unwrap(), 0);
+        assert_eq!(
+            tokio::fs::read_to_string(temp.path().join("config.yml.v1.bak"))
+                .await
+                .unwrap(),
+            original
+        );
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "migrate", "--check"]);
+        assert_eq!(cmd.run(&core, &args).await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            diff_lines("name: a\ncommand: b\n", "name: a\ncommand: c\nargs: []\n"),
+            vec!["  name: a", "- command: b", "+ command: c", "+ args: []"]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_outdated_and_upgrade() {
+        let temp = tempfile::tempdir().unwrap();
+        let registry = temp.path().join("registry");
+        std::fs::create_dir_all(registry.join("apps")).unwrap();
+
+        let write_template = |version: &str, command: &str| {
+            std::fs::write(
+                registry.join("apps").join("tool.yaml"),
+                format!(
+                    "name: Tool\ndescription: A test tool.\nversion: {version}\nconfigs:\n  - platform: any\n    app:\n      name: tool\n      command: {command}\n"
+                ),
+            )
+            .unwrap();
+        };
+
+        write_template("1.0.0", "tool");
+        let config_path = temp.path().join("config.yml");
+        tokio::fs::write(
+            &config_path,
+            format!(
+                "directory: /dev\nregistries:\n  - path: {}\n",
+                registry.display()
+            ),
+        )
+        .await
+        .unwrap();
+
+        let run = async |args: Vec<&str>| {
+            let console = crate::console::mock();
+            let core = Core::builder()
+                .with_config_file(&config_path)
+                .expect("the config should be loaded")
+                .with_registry_cache_directory(temp.path().join("cache"))
+                .with_console(console.clone())
+                .build();
+
+            let cmd = ConfigCommand {};
+            let args = cmd.app().get_matches_from(args);
+            cmd.assert_run_successful(&core, &args).await;
+            console.to_string()
+        };
+
+        run(vec!["config", "add", "apps/tool", "--name", "my-tool"]).await;
+        assert!(
+            run(vec!["config", "outdated"])
+                .await
+                .contains("All of the templates in your config file are up to date."),
+            "newly added templates should be up to date"
+        );
+
+        write_template("1.1.0", "tool2");
+        let output = run(vec!["config", "outdated"]).await;
+        assert!(
+            output.contains("apps/my-tool (apps/tool): 1.0.0 -> 1.1.0"),
+            "the output should list the outdated template: {output}"
+        );
+
+        let output = run(vec!["config", "upgrade", "apps/tool"]).await;
+        assert!(
+            output.contains("- command: tool\n") && output.contains("+ command: tool2\n"),
+            "the output should describe the changes which were made: {output}"
+        );
+
+        let cfg = Config::from_file(&config_path).unwrap();
+        assert_eq!(cfg.get_app("my-tool").unwrap().get_command(), "tool2");
+        assert_eq!(cfg.get_installed_templates()[0].1.version, "1.1.0");
+    }
+
+    #[tokio::test]
+    async fn run_upgrade_skips_modified_templates() {
+        let temp = tempfile::tempdir().unwrap();
+        let registry = temp.path().join("registry");
+        std::fs::create_dir_all(registry.join("apps")).unwrap();
+
+        let write_template = |version: &str, command: &str| {
+            std::fs::write(
+                registry.join("apps").join("tool.yaml"),
+                format!(
+                    "name: Tool\ndescription: A test tool.\nversion: {version}\nconfigs:\n  - platform: any\n    app:\n      name: tool\n      command: {command}\n"
+                ),
+            )
+            .unwrap();
+        };
+
+        write_template("1.0.0", "tool");
+        let config_path = temp.path().join("config.yml");
+        tokio::fs::write(
+            &config_path,
+            format!(
+                "directory: /dev\nregistries:\n  - path: {}\n",
+                registry.display()
+            ),
+        )
+        .await
+        .unwrap();
+
+        let run = async |args: Vec<&str>| {
+            let console = crate::console::mock();
+            let core = Core::builder()
+                .with_config_file(&config_path)
+                .expect("the config should be loaded")
+                .with_registry_cache_directory(temp.path().join("cache"))
+                .with_console(console.clone())
+                .build();
+
+            let cmd = ConfigCommand {};
+            let args = cmd.app().get_matches_from(args);
+            cmd.assert_run_successful(&core, &args).await;
+            console.to_string()
+        };
+
+        run(vec!["config", "add", "apps/tool"]).await;
+
+        let contents = tokio::fs::read_to_string(&config_path).await.unwrap();
+        assert!(contents.contains("command: tool\n"));
+        tokio::fs::write(
+            &config_path,
+            contents.replace("command: tool\n", "command: my-tool\n"),
+        )
+        .await
+        .unwrap();
+
+        write_template("1.1.0", "tool2");
+        let output = run(vec!["config", "upgrade"]).await;
+        assert!(
+            output.contains(
+                "Skipping apps/tool because it has been modified since it was installed."
+            ),
+            "the output should explain why the template was not upgraded: {output}"
+        );
+
+        let cfg = Config::from_file(&config_path).unwrap();
+        assert_eq!(
+            cfg.get_app("tool").unwrap().get_command(),
+            "my-tool",
+            "your changes to the app should be kept"
+        );
+        assert_eq!(cfg.get_installed_templates()[0].1.version, "1.0.0");
+    }
+
+    #[tokio::test]
+    async fn run_upgrade_skips_missing_templates() {
+        let temp = tempfile::tempdir().unwrap();
+        let registry = temp.path().join("registry");
+        std::fs::create_dir_all(registry.join("apps")).unwrap();
+
+        let write_template = |id: &str, version: &str| {
+            std::fs::write(
+                registry.join("apps").join(format!("{id}.yaml")),
+                format!(
+                    "name: {id}\ndescription: A test tool.\nversion: {version}\nconfigs:\n  - platform: any\n    app:\n      name: {id}\n      command: {id}-{version}\n"
+                ),
+            )
+            .unwrap();
+        };
+
+        write_template("a", "1.0.0");
+        write_template("b", "1.0.0");
+        let config_path = temp.path().join("config.yml");
+        tokio::fs::write(
+            &config_path,
+            format!(
+                "directory: /dev\nregistries:\n  - path: {}\n",
+                registry.display()
+            ),
+        )
+        .await
+        .unwrap();
+
+        let run = async |args: Vec<&str>| {
+            let console = crate::console::mock();
+            let core = Core::builder()
+                .with_config_file(&config_path)
+                .expect("the config should be loaded")
+                .with_registry_cache_directory(temp.path().join("cache"))
+                .with_console(console.clone())
+                .build();
+
+            let cmd = ConfigCommand {};
+            let args = cmd.app().get_matches_from(args);
+            cmd.assert_run_successful(&core, &args).await;
+            console.to_string()
+        };
+
+        run(vec!["config", "add", "apps/a"]).await;
+        run(vec!["config", "add", "apps/b"]).await;
+
+        std::fs::remove_file(registry.join("apps").join("a.yaml")).unwrap();
+        write_template("b", "1.1.0");
+
+        let output = run(vec!["config", "upgrade"]).await;
+        assert!(
+            output.contains("Could not check apps/a for updates"),
+            "the output should explain why the template was not upgraded: {output}"
+        );
+
+        let cfg = Config::from_file(&config_path).unwrap();
+        assert_eq!(cfg.get_app("a").unwrap().get_command(), "a-1.0.0");
+        assert_eq!(
+            cfg.get_app("b").unwrap().get_command(),
+            "b-1.1.0",
+            "the remaining templates should still be upgraded"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_search() {
+        let temp = tempfile::tempdir().unwrap();
+        let registry = temp.path().join("registry");
+        std::fs::create_dir_all(registry.join("apps")).unwrap();
+        for (id, name, description, platform) in [
+            ("vim", "Vim", "The ubiquitous text editor.", "any"),
+            ("notepad", "Notepad", "A simple text editor.", "plan9"),
+            ("htop", "htop", "An interactive process viewer.", "any"),
+        ] {
+            std::fs::write(
+                registry.join("apps").join(format!("{id}.yaml")),
+                format!(
+                    "name: {name}\ndescription: {description}\nversion: 1.0.0\nconfigs:\n  - platform: {platform}\n    app:\n      name: {id}\n      command: {id}\n"
+                ),
+            )
+            .unwrap();
+        }
+
+        let cfg = Config::from_str(&format!(
+            "directory: /dev\nregistries:\n  - path: {}\napps:\n  - name: vim\n    command: vim\n",
+            registry.display()
+        ))
+        .unwrap();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_registry_cache_directory(temp.path().join("cache"))
+            .with_console(console.clone())
+            .with_mock_http_client(vec![engine::MockHttpRoute::new(
+                "GET",
+                "/repos/SierraSoftworks/git-tool/git/trees/main",
+                200,
+                r#"{ "tree": [], "truncated": false }"#,
+            )])
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "search", "text editor"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output.contains("apps/vim: Vim [installed]\n  The ubiquitous text editor.\n"),
+            "the output should mark installed entries: {output}"
+        );
+        assert!(
+            output.contains("apps/notepad: Notepad [not available on"),
+            "the output should mark entries which are not available on this platform: {output}"
+        );
+        assert!(
+            !output.contains("apps/htop"),
+            "the output should not contain entries which don't match: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_registry() {
+        let temp = tempfile::tempdir().unwrap();
+        let cfg = Config::from_str(&format!(
+            "directory: /dev\nregistries:\n  - path: {}\n",
+            temp.path().display()
+        ))
+        .unwrap();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_registry_cache_directory(temp.path().join("cache"))
+            .with_console(console.clone())
+            .with_mock_http_client(vec![engine::MockHttpRoute::new(
+                "GET",
+                "/repos/SierraSoftworks/git-tool/git/trees/main",
+                200,
+                r#"{ "tree": [], "truncated": false }"#,
+            )])
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "registry", "list"]);
+        cmd.assert_run_successful(&core, &args).await;
+        assert_eq!(
+            console.to_string(),
+            format!(
+                "{}\n{GIT_TOOL_REGISTRY} (built-in)\n",
+                temp.path().display()
+            )
+        );
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["config", "registry", "refresh"]);
+        cmd.assert_run_successful(&core, &args).await;
+        assert!(
+            console
+                .to_string()
+                .ends_with("Your registries have been refreshed.\n"),
+            "the output should confirm that the registries were refreshed: {console}"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_add_with_variables() {
+        let temp = tempfile::tempdir().unwrap();
+        let registry = temp.path().join("registry");
+        std::fs::create_dir_all(registry.join("services")).unwrap();
+        std::fs::write(
+            registry.join("services").join("gitlab-internal.yaml"),
+            r#"
+name: Internal GitLab
+description: A self-hosted GitLab instance.
+version: 1.0.0
+variables:
+  - name: domain
+    description: The domain of your GitLab instance
+    validation: "[a-z0-9.-]+"
+  - name: user
+    default: git
+configs:
+  - platform: any
+    service:
+      name: "{{ .Variables.domain }}"
+      website: "https://{{ .Variables.domain }}/{{ .Repo.FullName }}"
+      gitUrl: "{{ .Variables.user }}@{{ .Variables.domain }}:{{ .Repo.FullName }}.git"
+      pattern: "*/*"
+"#,
+        )
+        .unwrap();
+
+        tokio::fs::write(
+            temp.path().join("config.yml"),
+            format!(
+                "directory: /dev\nregistries:\n  - path: {}\n",
+                registry.display()
+            ),
+        )
+        .await
+        .unwrap();
+
+        // The domain is provided with --set, so only the user is prompted for (and defaulted)
+        let console = crate::console::mock_with_input("\n");
+        let core = Core::builder()
+            .with_config_file(temp.path().join("config.yml"))
+            .expect("the config should be loaded")
+            .with_registry_cache_directory(temp.path().join("cache"))
+            .with_console(console.clone())
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args = cmd.app().get_matches_from(vec![
+            "config",
+            "add",
+            "services/gitlab-internal",
+            "--set",
+            "domain=gitlab.example.com",
+        ]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert!(
+            console.to_string().contains("user [git]: "),
+            "the user should be prompted for variables which were not provided: {console}"
+        );
+
+        let new_cfg = Config::from_file(&temp.path().join("config.yml")).unwrap();
+        let svc = new_cfg
+            .get_service("gitlab.example.com")
+            .expect("the service should have been added to the config file");
+        assert_eq!(
+            svc.website,
+            "https://gitlab.example.com/{{ .Repo.FullName }}"
+        );
+        assert_eq!(
+            svc.git_url,
+            "git@gitlab.example.com:{{ .Repo.FullName }}.git"
+        );
+
+        let installed = new_cfg.get_installed_templates();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(
+            installed[0].1.variables.get("domain").unwrap(),
+            "gitlab.example.com"
+        );
+
+        let args = cmd.app().get_matches_from(vec![
+            "config",
+            "add",
+            "services/gitlab-internal",
+            "--set",
+            "domain=https://gitlab.example.com",
+        ]);
+        cmd.run(&core, &args)
+            .await
+            .expect_err("values which don't pass validation should be rejected");
+    }
+
+    #[tokio::test]
+    async fn run_lint() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("apps")).unwrap();
+        std::fs::write(
+            temp.path().join("apps").join("shell.yaml"),
+            "name: Shell\ndescription: Opens a shell.\nversion: 1.0.0\nconfigs:\n  - platform: any\n    app:\n      name: shell\n      command: bash\n",
+        )
+        .unwrap();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_default_config()
+            .with_console(console.clone())
+            .build();
+
+        let cmd = ConfigCommand {};
+        let args =
+            cmd.app()
+                .get_matches_from(vec!["config", "lint", temp.path().to_str().unwrap()]);
+        cmd.assert_run_successful(&core, &args).await;
+        assert_eq!(console.to_string(), "All 1 templates are valid.\n");
+
+        std::fs::write(
+            temp.path().join("apps").join("broken.yaml"),
+            "name: Broken\ndescription: A broken template.\nversion: 1.0.0\nconfigs:\n  - platform: macos\n    app:\n      name: broken\n      command: \"{{ .Repo.Nmae }}\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            cmd.run(&core, &args).await.unwrap(),
+            1,
+            "the command should fail when a template has errors"
+        );
+        let output = console.to_string();
+        assert!(
+            output.contains("broken.yaml:5:5: error: The platform 'macos' is not supported"),
+            "the unsupported platform should be reported: {output}"
+        );
+        assert!(
+            output.contains("could not be rendered"),
+            "the broken template should be reported: {output}"
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use crate::errors::HumanErrorResultExt;
 
 use super::*;
+use crate::engine::{Service, Target};
 use tracing_batteries::prelude::*;
 
 pub struct DoctorCommand;
@@ -33,6 +34,24 @@ impl CommandRunnable for DoctorCommand {
             .to_human_error()?;
         }
 
+        let diagnostics = engine::validate_config(core.config());
+        if diagnostics.is_empty() {
+            writeln!(core.output(), "[OK] Config file is valid").to_human_error()?;
+        }
+
+        for diagnostic in diagnostics {
+            writeln!(
+                core.output(),
+                "[{}] {}",
+                match diagnostic.severity {
+                    engine::Severity::Error => "ERROR",
+                    engine::Severity::Warning => "WARNING",
+                },
+                diagnostic
+            )
+            .to_human_error()?;
+        }
+
         if !core.config().get_dev_directory().exists() {
             Err(human_errors::user(
                 "Your development directory does not exist.",
@@ -53,6 +72,25 @@ impl CommandRunnable for DoctorCommand {
             ))?;
         }
 
+        let missing_apps: Vec<_> = core
+            .config()
+            .get_apps()
+            .filter(|app| !app.is_available())
+            .collect();
+        if missing_apps.is_empty() {
+            writeln!(core.output(), "[OK] All of your apps are available").to_human_error()?;
+        }
+
+        for app in missing_apps {
+            writeln!(
+                core.output(),
+                "[WARNING] The '{}' app's command '{}' could not be found on your PATH",
+                app.get_name(),
+                app.get_command()
+            )
+            .to_human_error()?;
+        }
+
         for svc in core.config().get_services() {
             if let Some(online_service) = online::services().iter().find(|s| s.handles(svc)) {
                 match online_service.test(core, svc).await {
@@ -73,6 +111,34 @@ impl CommandRunnable for DoctorCommand {
             }
         }
 
+        for svc in core.config().get_services() {
+            if svc.identity.is_none() {
+                continue;
+            }
+
+            let mismatches = Self::identity_mismatches(core, svc).await?;
+            if mismatches.is_empty() {
+                writeln!(
+                    core.output(),
+                    "[OK] Repositories in '{}' use the configured git identity",
+                    &svc.name
+                )
+                .to_human_error()?;
+            }
+
+            for (repo, key) in mismatches {
+                writeln!(
+                    core.output(),
+                    "[WARNING] {} does not have the configured '{}' for '{}', run `git-tool fix --identity {}` to fix it",
+                    &repo,
+                    key,
+                    &svc.name,
+                    &repo
+                )
+                .to_human_error()?;
+            }
+        }
+
         Ok(0)
     }
 
@@ -83,9 +149,40 @@ impl CommandRunnable for DoctorCommand {
     async fn complete(&self, _core: &Core, _completer: &Completer, _matches: &ArgMatches) {}
 }
 
+impl DoctorCommand {
+    /// Finds the repositories belonging to a service whose local git config
+    /// doesn't match the service's configured identity, along with the first
+    /// git config key which differs.
+    async fn identity_mismatches(
+        core: &Core,
+        svc: &Service,
+    ) -> Result<Vec<(Repo, &'static str)>, engine::Error> {
+        let mut mismatches = Vec::new();
+
+        let Some(identity) = &svc.identity else {
+            return Ok(mismatches);
+        };
+
+        let repos: Vec<Repo> = core.resolve_many(svc)?;
+        for repo in repos.into_iter().filter(|r| r.valid()) {
+            for (key, expected) in identity.git_config() {
+                let actual = crate::git::git_config_get(&repo.get_path(), key).await?;
+                if actual.as_deref() != Some(expected) {
+                    mismatches.push((repo, key));
+                    break;
+                }
+            }
+        }
+
+        Ok(mismatches)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::Config;
+    use crate::tasks::Task;
 
     #[tokio::test]
     #[cfg_attr(feature = "pure-tests", ignore)]
@@ -123,4 +220,102 @@ mod tests {
             "the output should contain the default app"
         );
     }
+
+    #[tokio::test]
+    async fn missing_apps() {
+        let args = ArgMatches::default();
+        let temp = tempfile::tempdir().unwrap();
+
+        let config = Config::from_str(&format!(
+            r#"
+directory: {}
+scratchpads: {}
+apps:
+  - name: git
+    command: git
+  - name: missing
+    command: git-tool-definitely-not-a-real-command
+"#,
+            temp.path().display(),
+            temp.path().display()
+        ))
+        .unwrap();
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(config)
+            .with_console(console.clone())
+            .build();
+
+        let cmd = DoctorCommand {};
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output.contains("[WARNING] The 'missing' app's command 'git-tool-definitely-not-a-real-command' could not be found on your PATH"),
+            "the missing app should be reported: {output}"
+        );
+        assert!(
+            !output.contains("The 'git' app's command"),
+            "available apps should not be reported: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn identity_mismatches() {
+        let temp = tempfile::tempdir().unwrap();
+
+        let config = Config::from_str(&format!(
+            r#"
+directory: {}
+services:
+  - name: gh
+    website: "https://github.com/{{{{ .Repo.FullName }}}}"
+    gitUrl: "git@github.com:{{{{ .Repo.FullName }}}}.git"
+    pattern: "*/*"
+    identity:
+      name: Work User
+      email: work@example.com
+"#,
+            temp.path().display()
+        ))
+        .unwrap();
+
+        let core = Core::builder().with_config(config).build();
+
+        let configured = Repo::new(
+            "gh:sierrasoftworks/configured",
+            temp.path()
+                .join("gh")
+                .join("sierrasoftworks")
+                .join("configured"),
+        );
+        let unconfigured = Repo::new(
+            "gh:sierrasoftworks/unconfigured",
+            temp.path()
+                .join("gh")
+                .join("sierrasoftworks")
+                .join("unconfigured"),
+        );
+
+        crate::tasks::GitInit::default()
+            .apply_repo(&core, &configured)
+            .await
+            .unwrap();
+        crate::git::git_init(&unconfigured.get_path())
+            .await
+            .unwrap();
+        crate::git::git_config_set(&unconfigured.get_path(), "user.name", "Someone Else")
+            .await
+            .unwrap();
+
+        let svc = core.config().get_service("gh").unwrap();
+        let mismatches = DoctorCommand::identity_mismatches(&core, svc)
+            .await
+            .unwrap();
+
+        assert_eq!(mismatches.len(), 1, "only one repo should be misconfigured");
+        assert_eq!(mismatches[0].0.name, "unconfigured");
+        assert_eq!(mismatches[0].1, "user.name");
+    }
 }
@@ -30,16 +30,24 @@ impl CommandRunnable for FixCommand {
                 .short('R')
                 .help("prevent the creation of a remote repository (on supported services)")
                 .action(clap::ArgAction::SetTrue))
+            .arg(Arg::new("identity")
+                .long("identity")
+                .help("apply the git identity configured for the repository's service instead of fixing its remote")
+                .action(clap::ArgAction::SetTrue))
     }
 
     #[tracing::instrument(name = "gt fix", err, skip(self, core, matches))]
     async fn run(&self, core: &Core, matches: &ArgMatches) -> Result<i32, human_errors::Error> {
-        let tasks = sequence![
-            GitRemote { name: "origin" },
-            CreateRemote {
-                enabled: !matches.get_flag("no-create-remote")
-            }
-        ];
+        let tasks = if matches.get_flag("identity") {
+            sequence![GitIdentity {}]
+        } else {
+            sequence![
+                GitRemote { name: "origin" },
+                CreateRemote {
+                    enabled: !matches.get_flag("no-create-remote")
+                }
+            ]
+        };
 
         match matches.get_flag("all") {
             true => {
@@ -80,6 +88,7 @@ impl CommandRunnable for FixCommand {
         completer.offer_aliases(core);
         completer.offer("--all");
         completer.offer("--no-create-remote");
+        completer.offer("--identity");
         completer.offer_repos(core);
     }
 }
@@ -119,7 +128,7 @@ mod tests {
             .build();
 
         // Prep the repo
-        sequence![GitInit {}, GitRemote { name: "origin" }]
+        sequence![GitInit::default(), GitRemote { name: "origin" }]
             .apply_repo(
                 &core,
                 &Repo::new("gh:exampleA/test", core.config().get_dev_directory().into()),
@@ -128,4 +137,56 @@ mod tests {
             .unwrap();
         cmd.assert_run_successful(&core, &args).await;
     }
+
+    #[tokio::test]
+    async fn run_identity() {
+        let cmd = FixCommand {};
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["fix", "--identity", "repo"]);
+
+        let temp = tempfile::tempdir().unwrap();
+        let repo_path = temp.path().join("repo");
+
+        let config = Config::from_str(&format!(
+            r#"
+directory: {}
+services:
+  - name: gh
+    website: "https://github.com/{{{{ .Repo.FullName }}}}"
+    gitUrl: "git@github.com:{{{{ .Repo.FullName }}}}.git"
+    pattern: "*/*"
+    identity:
+      name: Work User
+      email: work@example.com
+"#,
+            temp.path().display()
+        ))
+        .unwrap();
+
+        let core = Core::builder()
+            .with_config(config)
+            .with_null_console()
+            .with_mock_resolver(|mock| {
+                let repo_path = repo_path.clone();
+                let identifier: Identifier = "repo".parse().unwrap();
+                mock.expect_get_best_repo()
+                    .with(eq(identifier))
+                    .returning(move |_| Ok(Repo::new("gh:exampleB/test", repo_path.clone())));
+            })
+            .build();
+
+        let repo = Repo::new("gh:exampleB/test", temp.path().join("repo"));
+        crate::git::git_init(&repo.get_path()).await.unwrap();
+
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(
+            crate::git::git_config_get(&repo.get_path(), "user.email")
+                .await
+                .unwrap(),
+            Some("work@example.com".to_string())
+        );
+    }
 }
@@ -27,6 +27,7 @@ mod open;
 mod prune;
 mod remove;
 mod rename;
+mod schema;
 mod scratch;
 mod services;
 mod setup;
@@ -54,6 +55,14 @@ pub fn app() -> clap::Command {
                 .help("The path to your git-tool configuration file.")
                 .action(ArgAction::Set),
         )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .env("GITTOOL_PROFILE")
+                .value_name("NAME")
+                .help("The name of the configuration profile to use.")
+                .action(ArgAction::Set),
+        )
         .arg(
             Arg::new("update-resume-internal")
                 .long("update-resume-internal")
@@ -68,6 +77,13 @@ pub fn app() -> clap::Command {
                 .help("Enable tracing for the current command and print the trace ID to assist with bug reports.")
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .global(true)
+                .help("Print the commands and changes Git-Tool would make, without running or applying them.")
+                .action(ArgAction::SetTrue),
+        )
         .arg(
             Arg::new("trace-context")
                 .long("trace-context")
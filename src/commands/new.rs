@@ -103,7 +103,7 @@ You may also provide an application to open the repository with once it has been
                 EnsureNoRemote {
                     enabled: !matches.get_flag("no-check-exists")
                 },
-                GitInit {},
+                GitInit::default(),
                 GitRemote { name: "origin" },
                 GitCheckout { branch: "main" },
                 CreateRemote {
@@ -64,7 +64,7 @@ New applications can be configured either by making changes to your configuratio
                 Ok(()) => {}
                 Err(_) if matches.get_flag("create") => {
                     sequence![
-                        GitInit {},
+                        GitInit::default(),
                         GitRemote { name: "origin" },
                         GitCheckout { branch: "main" },
                         CreateRemote {
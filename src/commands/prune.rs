@@ -2,12 +2,19 @@ use super::*;
 use crate::engine::Target;
 use crate::errors::HumanErrorResultExt;
 use crate::git;
+use crate::search;
 use crate::tasks::Task;
 use clap::Arg;
+use futures::StreamExt;
 use human_errors::ResultExt;
 use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
 use tracing_batteries::prelude::*;
 
+/// The number of repositories which are inspected at once when pruning all of them,
+/// bounding the number of concurrent git processes and service API requests.
+const PRUNE_CONCURRENCY: usize = 4;
+
 pub struct PruneCommand;
 crate::command!(PruneCommand);
 
@@ -26,6 +33,10 @@ impl CommandRunnable for PruneCommand {
                 into upstream branches and will proceed to delete them. This is particularly helpful
                 if you use feature branches as part of your workflow and want to get rid of old ones.
 
+                Branches which were squash or rebase merged are detected by comparing their changes
+                with the default branch, by checking whether their upstream branch has been deleted,
+                or by asking your online service whether their pull request has been merged.
+
                 It will also remove any Git worktrees for the repository which do not contain
                 uncommitted changes, leaving any worktree with pending work untouched.",
             )
@@ -50,6 +61,15 @@ impl CommandRunnable for PruneCommand {
                     .help("Prune clean worktrees (enabled by default unless --branches is set)")
                     .action(clap::ArgAction::SetTrue),
             )
+            .arg(
+                Arg::new("all")
+                    .long("all")
+                    .short('a')
+                    .value_name("FILTER")
+                    .help("Prune every repository in your dev directory (optionally only those matching FILTER)")
+                    .num_args(0..=1)
+                    .default_missing_value(""),
+            )
             .arg(Arg::new("pattern")
                 .help("Only prune branches and worktrees whose branch name contains one of these patterns")
                 .action(clap::ArgAction::Append))
@@ -57,8 +77,6 @@ impl CommandRunnable for PruneCommand {
 
     #[tracing::instrument(name = "gt prune", err, skip(self, core, matches))]
     async fn run(&self, core: &Core, matches: &ArgMatches) -> Result<i32, human_errors::Error> {
-        let repo: Repo = core.resolve(())?;
-
         // Determine which subsets we should prune. When neither flag is provided we
         // prune both branches and worktrees; specifying one flag restricts the
         // operation to just that subset.
@@ -76,37 +94,41 @@ impl CommandRunnable for PruneCommand {
             .map(|values| values.cloned().collect())
             .unwrap_or_default();
 
-        let branches_to_remove: Vec<String> = if prune_branches {
-            self.get_prune_branches(&repo, &patterns).await?
-        } else {
-            Vec::new()
+        let options = PruneOptions {
+            branches: prune_branches,
+            worktrees: prune_worktrees,
+            patterns,
         };
 
-        let worktrees_to_remove = if prune_worktrees {
-            self.get_prune_worktrees(&repo, &patterns).await?
-        } else {
-            Vec::new()
-        };
+        if let Some(filter) = matches.get_one::<String>("all") {
+            return self
+                .run_all(core, filter, &options, matches.get_flag("yes"))
+                .await;
+        }
 
-        if branches_to_remove.is_empty() && worktrees_to_remove.is_empty() {
+        let repo: Repo = core.resolve(())?;
+        let plan = self.get_prune_plan(core, repo, &options).await?;
+
+        if plan.is_empty() {
             writeln!(core.output(), "No branches or worktrees to remove").to_human_error()?;
             return Ok(0);
         }
 
         if !matches.get_flag("yes") {
-            if !branches_to_remove.is_empty() {
+            if !plan.branches.is_empty() {
                 writeln!(core.output(), "The following branches will be removed:")
                     .to_human_error()?;
-                for branch in branches_to_remove.iter() {
-                    writeln!(core.output(), "  {branch}").to_human_error()?;
+                for branch in plan.branches.iter() {
+                    writeln!(core.output(), "  {} ({})", branch.name, branch.reason)
+                        .to_human_error()?;
                 }
                 writeln!(core.output()).to_human_error()?;
             }
 
-            if !worktrees_to_remove.is_empty() {
+            if !plan.worktrees.is_empty() {
                 writeln!(core.output(), "The following worktrees will be removed:")
                     .to_human_error()?;
-                for worktree in worktrees_to_remove.iter() {
+                for worktree in plan.worktrees.iter() {
                     writeln!(core.output(), "  {}", worktree.path.display()).to_human_error()?;
                 }
                 writeln!(core.output()).to_human_error()?;
@@ -126,25 +148,7 @@ impl CommandRunnable for PruneCommand {
             }
         }
 
-        // Remove worktrees before deleting branches: a branch which is checked out
-        // in a worktree cannot be deleted until that worktree has been removed. We
-        // chain the individual operations together using a Sequence task so that
-        // they are applied in order.
-        let mut cleanup: Vec<std::sync::Arc<dyn crate::tasks::Task + Send + Sync>> = Vec::new();
-
-        for worktree in worktrees_to_remove {
-            cleanup.push(std::sync::Arc::new(tasks::GitWorktreeRemove {
-                path: worktree.path,
-            }));
-        }
-
-        for branch in branches_to_remove {
-            cleanup.push(std::sync::Arc::new(tasks::GitBranchDelete { branch }));
-        }
-
-        tasks::Sequence::new(cleanup)
-            .apply_repo(core, &repo)
-            .await?;
+        plan.apply(core).await?;
 
         Ok(0)
     }
@@ -159,6 +163,7 @@ impl CommandRunnable for PruneCommand {
             completer.offer("--yes");
             completer.offer("--branches");
             completer.offer("--worktrees");
+            completer.offer("--all");
             if let Ok(branches) = git::git_merged_branches(&repo.get_path()).await {
                 completer.offer_many(branches.iter().unique().sorted());
             }
@@ -166,14 +171,235 @@ impl CommandRunnable for PruneCommand {
     }
 }
 
+/// The reason that a branch is considered to have been merged and safe to prune.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MergeReason {
+    Merged,
+    UpstreamGone,
+    Rebased,
+    Squashed,
+    PullRequest,
+}
+
+impl std::fmt::Display for MergeReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeReason::Merged => write!(f, "merged"),
+            MergeReason::UpstreamGone => write!(f, "upstream deleted"),
+            MergeReason::Rebased => write!(f, "rebase merged"),
+            MergeReason::Squashed => write!(f, "squash merged"),
+            MergeReason::PullRequest => write!(f, "pull request merged"),
+        }
+    }
+}
+
+struct PruneBranch {
+    name: String,
+    reason: MergeReason,
+}
+
+struct PruneOptions {
+    branches: bool,
+    worktrees: bool,
+    patterns: Vec<String>,
+}
+
+/// The branches and worktrees which will be removed from a repository.
+struct PrunePlan {
+    repo: Repo,
+    branches: Vec<PruneBranch>,
+    worktrees: Vec<git::Worktree>,
+}
+
+impl PrunePlan {
+    fn is_empty(&self) -> bool {
+        self.branches.is_empty() && self.worktrees.is_empty()
+    }
+
+    async fn apply(self, core: &Core) -> Result<(), human_errors::Error> {
+        // Remove worktrees before deleting branches: a branch which is checked out
+        // in a worktree cannot be deleted until that worktree has been removed. We
+        // chain the individual operations together using a Sequence task so that
+        // they are applied in order.
+        let mut cleanup: Vec<std::sync::Arc<dyn crate::tasks::Task + Send + Sync>> = Vec::new();
+
+        for worktree in self.worktrees {
+            cleanup.push(std::sync::Arc::new(tasks::GitWorktreeRemove {
+                path: worktree.path,
+            }));
+        }
+
+        for branch in self.branches {
+            cleanup.push(std::sync::Arc::new(tasks::GitBranchDelete {
+                branch: branch.name,
+            }));
+        }
+
+        tasks::Sequence::new(cleanup)
+            .apply_repo(core, &self.repo)
+            .await
+    }
+}
+
 impl PruneCommand {
+    async fn run_all(
+        &self,
+        core: &Core,
+        filter: &str,
+        options: &PruneOptions,
+        yes: bool,
+    ) -> Result<i32, human_errors::Error> {
+        let repos: Vec<Repo> = core.resolve_many(())?;
+        let repos = search::best_matches_by(filter, repos, |r| {
+            format!("{}:{}", &r.service, r.get_full_name())
+        });
+
+        let results: Vec<_> = futures::stream::iter(repos.into_iter().filter(|repo| repo.valid()))
+            .map(|repo| async move {
+                let name = format!("{}:{}", &repo.service, repo.get_full_name());
+                (name, self.get_prune_plan(core, repo, options).await)
+            })
+            .buffered(PRUNE_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut failures = 0;
+        let mut plans = Vec::new();
+        for (name, result) in results {
+            match result {
+                Ok(plan) if plan.is_empty() => {}
+                Ok(plan) => plans.push((name, plan)),
+                Err(err) => {
+                    failures += 1;
+                    writeln!(
+                        core.output(),
+                        " [!] Could not determine what to prune in {name}: {}",
+                        err.message()
+                    )
+                    .to_human_error()?;
+                }
+            }
+        }
+
+        if plans.is_empty() {
+            writeln!(core.output(), "No branches or worktrees to remove").to_human_error()?;
+            return Ok(if failures > 0 { 1 } else { 0 });
+        }
+
+        if !yes {
+            writeln!(
+                core.output(),
+                "The following branches and worktrees will be removed:"
+            )
+            .to_human_error()?;
+            for (name, plan) in plans.iter() {
+                writeln!(core.output(), "\n{name}").to_human_error()?;
+                for branch in plan.branches.iter() {
+                    writeln!(
+                        core.output(),
+                        "  branch {} ({})",
+                        branch.name,
+                        branch.reason
+                    )
+                    .to_human_error()?;
+                }
+                for worktree in plan.worktrees.iter() {
+                    writeln!(core.output(), "  worktree {}", worktree.path.display())
+                        .to_human_error()?;
+                }
+            }
+            writeln!(core.output()).to_human_error()?;
+
+            let confirmed = core
+                .prompter()
+                .prompt_bool(
+                    &format!(
+                        "Are you sure you want to remove these branches and worktrees from {} repositories? [y/N]: ",
+                        plans.len()
+                    ),
+                    Some(false),
+                )?
+                .unwrap_or_default();
+
+            if !confirmed {
+                writeln!(core.output(), "Okay, we'll keep them as-is.").to_human_error()?;
+                return Ok(0);
+            }
+        }
+
+        let (mut branches, mut worktrees, mut repos) = (0, 0, 0);
+        for (name, plan) in plans {
+            let (plan_branches, plan_worktrees) = (plan.branches.len(), plan.worktrees.len());
+            match plan.apply(core).await {
+                Ok(()) => {
+                    repos += 1;
+                    branches += plan_branches;
+                    worktrees += plan_worktrees;
+                }
+                Err(err) => {
+                    failures += 1;
+                    writeln!(
+                        core.output(),
+                        " [!] Could not prune {name}: {}",
+                        err.message()
+                    )
+                    .to_human_error()?;
+                }
+            }
+        }
+
+        writeln!(
+            core.output(),
+            "Removed {branches} branches and {worktrees} worktrees from {repos} repositories."
+        )
+        .to_human_error()?;
+
+        if failures > 0 {
+            writeln!(
+                core.output(),
+                "{failures} repositories could not be pruned, see the messages above for details."
+            )
+            .to_human_error()?;
+            return Ok(1);
+        }
+
+        Ok(0)
+    }
+
+    async fn get_prune_plan(
+        &self,
+        core: &Core,
+        repo: Repo,
+        options: &PruneOptions,
+    ) -> Result<PrunePlan, human_errors::Error> {
+        let branches = if options.branches {
+            self.get_prune_branches(core, &repo, &options.patterns)
+                .await?
+        } else {
+            Vec::new()
+        };
+
+        let worktrees = if options.worktrees {
+            self.get_prune_worktrees(&repo, &options.patterns).await?
+        } else {
+            Vec::new()
+        };
+
+        Ok(PrunePlan {
+            repo,
+            branches,
+            worktrees,
+        })
+    }
+
     async fn get_prune_branches(
         &self,
+        core: &Core,
         repo: &engine::Repo,
         patterns: &[String],
-    ) -> Result<Vec<String>, human_errors::Error> {
-        let merged = match git::git_merged_branches(&repo.get_path()).await {
-            Ok(merged) => merged,
+    ) -> Result<Vec<PruneBranch>, human_errors::Error> {
+        let merged: HashSet<String> = match git::git_merged_branches(&repo.get_path()).await {
+            Ok(merged) => merged.into_iter().collect(),
             Err(e) => {
                 return Err(human_errors::wrap_user(
                     e,
@@ -190,13 +416,132 @@ impl PruneCommand {
             &["Make sure that you have a correctly configured `origin` and that you have run `git fetch` before running this command again."],
         )?;
 
-        let candidates: Vec<String> = merged
+        // Squash and rebase merges usually land on the remote's default branch, which
+        // may not have been pulled into the local copy yet, so we check both.
+        let mut targets = vec![default_branch.clone()];
+        if git::git_rev_parse(&repo.get_path(), &format!("origin/{default_branch}"))
+            .await
+            .is_ok()
+        {
+            targets.insert(0, format!("origin/{default_branch}"));
+        }
+
+        // Branches which are checked out (here or in a linked worktree) cannot be deleted.
+        let checked_out: HashSet<String> = git::git_worktree_list(&repo.get_path())
+            .await?
             .into_iter()
-            .filter(|b| b != &default_branch)
-            .unique()
+            .filter_map(|w| w.branch)
             .collect();
 
-        Ok(crate::search::matches_any(&patterns, candidates))
+        let branches: HashMap<String, git::BranchUpstream> =
+            git::git_branch_upstreams(&repo.get_path())
+                .await?
+                .into_iter()
+                .filter(|b| b.branch != default_branch && !checked_out.contains(&b.branch))
+                .map(|b| (b.branch.clone(), b))
+                .collect();
+
+        let online_service = core
+            .config()
+            .get_service(&repo.service)
+            .ok()
+            .and_then(|service| {
+                crate::online::services()
+                    .into_iter()
+                    .find(|s| s.handles(service))
+                    .map(|online| (service, online))
+            });
+
+        let mut candidates = Vec::new();
+        for name in crate::search::matches_any(&patterns, branches.keys().cloned())
+            .into_iter()
+            .sorted()
+        {
+            let branch = &branches[&name];
+
+            // Branches whose upstream is gone, or whose pull request was merged, are only
+            // pruned when their local commits have all made it to the remote. Otherwise
+            // the branch has gained work since it was pushed, which we mustn't lose.
+            let reason = if merged.contains(&name) {
+                Some(MergeReason::Merged)
+            } else if let Some(reason) = self.get_patch_merge_reason(repo, &name, &targets).await {
+                Some(reason)
+            } else if branch.gone
+                && git::git_branch_is_on_remote(&repo.get_path(), &name)
+                    .await
+                    .unwrap_or(false)
+            {
+                Some(MergeReason::UpstreamGone)
+            } else if let (Some(upstream), Some((service, online))) =
+                (&branch.upstream, &online_service)
+            {
+                // Only branches which have been pushed can have a pull request, and we ask
+                // about the branch name used on the remote.
+                let remote_branch = upstream.split_once('/').map(|(_, b)| b).unwrap_or(upstream);
+                match online
+                    .get_merged_pull_request_heads(core, service, repo, remote_branch)
+                    .await
+                {
+                    Ok(heads) => {
+                        let mut reason = None;
+                        for head in heads {
+                            if git::git_branch_is_contained(&repo.get_path(), &name, &head)
+                                .await
+                                .unwrap_or(false)
+                            {
+                                reason = Some(MergeReason::PullRequest);
+                                break;
+                            }
+                        }
+
+                        reason
+                    }
+                    Err(err) => {
+                        warn!(
+                            "Could not determine whether the pull request for '{}' has been merged: {}",
+                            name, err
+                        );
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            if let Some(reason) = reason {
+                candidates.push(PruneBranch { name, reason });
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    /// Determines whether the changes on `branch` have already been applied to one of the
+    /// `targets` by a rebase or squash merge.
+    async fn get_patch_merge_reason(
+        &self,
+        repo: &engine::Repo,
+        branch: &str,
+        targets: &[String],
+    ) -> Option<MergeReason> {
+        let path = repo.get_path();
+        for into in targets {
+            if git::git_branch_is_rebased(&path, branch, into)
+                .await
+                .unwrap_or(false)
+            {
+                return Some(MergeReason::Rebased);
+            }
+
+            if git::git_branch_is_squashed(&path, branch, into)
+                .await
+                .unwrap_or(false)
+            {
+                return Some(MergeReason::Squashed);
+            }
+        }
+
+        None
     }
 
     async fn get_prune_worktrees(
@@ -286,6 +631,14 @@ mod tests {
                         repo_path.clone(),
                     ))
                 });
+
+                let repo_path = repo_path.clone();
+                mock.expect_get_repos().returning(move || {
+                    Ok(vec![Repo::new(
+                        "gh:sierrasoftworks/test-git-switch-command",
+                        repo_path.clone(),
+                    )])
+                });
             })
             .build();
 
@@ -296,7 +649,7 @@ mod tests {
 
         sequence!(
             // Run a `git init` to setup the repo
-            tasks::GitInit {},
+            tasks::GitInit::default(),
             tasks::GitRemote { name: "origin" },
             // Create the branch we want to switch to
             tasks::GitCheckout {
@@ -319,10 +672,13 @@ mod tests {
         .await
         .unwrap();
 
-        sequence!(tasks::GitInit {}, tasks::GitRemote { name: "origin" })
-            .apply_repo(&core, &repo)
-            .await
-            .unwrap();
+        sequence!(
+            tasks::GitInit::default(),
+            tasks::GitRemote { name: "origin" }
+        )
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
 
         git::git_remote_set_url(
             &repo.get_path(),
@@ -338,10 +694,14 @@ mod tests {
             tasks::GitSwitch {
                 branch: "main".into(),
                 create_if_missing: false,
+                autostash: false,
+                fetch: false,
             },
             tasks::GitSwitch {
                 branch: "feature/test2".into(),
                 create_if_missing: true,
+                autostash: false,
+                fetch: false,
             },
             tasks::WriteFile {
                 path: "README.md".into(),
@@ -357,6 +717,8 @@ mod tests {
             tasks::GitSwitch {
                 branch: "main".into(),
                 create_if_missing: false,
+                autostash: false,
+                fetch: false,
             },
             tasks::WriteFile {
                 path: ".git/refs/remotes/origin/HEAD".into(),
@@ -438,6 +800,212 @@ mod tests {
         assert_eq!(branches, vec!["feature/test", "main"]);
     }
 
+    #[tokio::test]
+    async fn prune_squash_merged_branch() {
+        let cmd: PruneCommand = PruneCommand {};
+
+        let temp = tempdir().unwrap();
+
+        let console = crate::console::mock_with_input("y\n");
+        let (core, repo) = setup_test_repo_with_remote(
+            Core::builder()
+                .with_config_for_dev_directory(temp.path())
+                .with_console(console.clone()),
+            &temp,
+        )
+        .await;
+
+        git::git_cmd(
+            tokio::process::Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("merge")
+                .arg("--squash")
+                .arg("feature/test2"),
+        )
+        .await
+        .unwrap();
+        git::git_cmd(
+            tokio::process::Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("commit")
+                .arg("-m")
+                .arg("Squash merge feature/test2"),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            git::git_merged_branches(&repo.get_path())
+                .await
+                .unwrap()
+                .is_empty(),
+            "a squash merged branch isn't reported by `git branch --merged`"
+        );
+
+        let args: ArgMatches = cmd.app().get_matches_from(vec!["prune"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert!(
+            console
+                .to_string()
+                .contains("feature/test2 (squash merged)"),
+            "the output should explain why the branch is being removed: {console}"
+        );
+
+        let mut branches = git::git_branches(&repo.get_path()).await.unwrap();
+        branches.sort();
+        assert_eq!(branches, vec!["feature/test", "main"]);
+    }
+
+    #[tokio::test]
+    async fn prune_keeps_gone_branch_with_new_commits() {
+        let cmd: PruneCommand = PruneCommand {};
+
+        let temp = tempdir().unwrap();
+
+        // A service without an API, so that we don't ask GitHub about pull requests.
+        let cfg = Config::from_str(&format!(
+            r#"
+directory: {}
+services:
+  - name: gh
+    website: "https://github.com/{{{{ .Repo.FullName }}}}"
+    gitUrl: "git@github.com:{{{{ .Repo.FullName }}}}.git"
+    pattern: "*/*"
+"#,
+            temp.path().display()
+        ))
+        .unwrap();
+
+        let console = crate::console::mock_with_input("y\n");
+        let (core, repo) = setup_test_repo_with_remote(
+            Core::builder()
+                .with_config(cfg)
+                .with_console(console.clone()),
+            &temp,
+        )
+        .await;
+
+        git::git_cmd(
+            tokio::process::Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("push")
+                .arg("--set-upstream")
+                .arg("origin")
+                .arg("feature/test2"),
+        )
+        .await
+        .unwrap();
+
+        sequence!(
+            tasks::GitSwitch {
+                branch: "feature/test2".into(),
+                create_if_missing: false,
+                autostash: false,
+                fetch: false,
+            },
+            tasks::WriteFile {
+                path: "NOTES.md".into(),
+                content: "Work which was never pushed.",
+            },
+            tasks::GitAdd {
+                paths: vec!["NOTES.md"],
+            },
+            tasks::GitCommit {
+                message: "Add NOTES.md",
+                paths: vec!["NOTES.md"],
+            },
+            tasks::GitSwitch {
+                branch: "main".into(),
+                create_if_missing: false,
+                autostash: false,
+                fetch: false,
+            }
+        )
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        git::git_branch_delete(&temp.path().join("repo2"), "feature/test2")
+            .await
+            .unwrap();
+        git::git_cmd(
+            tokio::process::Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("fetch")
+                .arg("--prune")
+                .arg("origin"),
+        )
+        .await
+        .unwrap();
+
+        let upstreams = git::git_branch_upstreams(&repo.get_path()).await.unwrap();
+        assert!(
+            upstreams
+                .iter()
+                .any(|b| b.branch == "feature/test2" && b.gone),
+            "the upstream for feature/test2 should be gone"
+        );
+
+        let args: ArgMatches = cmd.app().get_matches_from(vec!["prune"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert!(
+            !console.to_string().contains("upstream deleted"),
+            "a branch with unpushed commits shouldn't be pruned: {console}"
+        );
+
+        let mut branches = git::git_branches(&repo.get_path()).await.unwrap();
+        branches.sort();
+        assert_eq!(branches, vec!["feature/test", "feature/test2", "main"]);
+    }
+
+    #[tokio::test]
+    async fn prune_all_repositories() {
+        let cmd: PruneCommand = PruneCommand {};
+
+        let temp = tempdir().unwrap();
+
+        let console = crate::console::mock_with_input("y\n");
+        let (core, repo) = setup_test_repo_with_remote(
+            Core::builder()
+                .with_config_for_dev_directory(temp.path())
+                .with_console(console.clone()),
+            &temp,
+        )
+        .await;
+
+        git::git_cmd(
+            tokio::process::Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("merge")
+                .arg("feature/test2"),
+        )
+        .await
+        .unwrap();
+
+        let args: ArgMatches =
+            cmd.app()
+                .get_matches_from(vec!["prune", "--all", "test-git-switch"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output.contains(
+                "gh:sierrasoftworks/test-git-switch-command\n  branch feature/test2 (merged)"
+            ),
+            "the branches should be grouped by repository: {output}"
+        );
+        assert!(
+            output.contains("Removed 1 branches and 0 worktrees from 1 repositories."),
+            "the output should summarise what was removed: {output}"
+        );
+
+        let mut branches = git::git_branches(&repo.get_path()).await.unwrap();
+        branches.sort();
+        assert_eq!(branches, vec!["feature/test", "main"]);
+    }
+
     #[tokio::test]
     async fn prune_clean_worktree() {
         let cmd: PruneCommand = PruneCommand {};
@@ -742,7 +1310,10 @@ mod tests {
             .build();
 
         // Run a `git init` to setup the repo
-        tasks::GitInit {}.apply_repo(&core, &repo).await.unwrap();
+        tasks::GitInit::default()
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
 
         assert!(repo.valid(), "the repository should exist and be valid");
 
@@ -70,16 +70,16 @@ impl CommandRunnable for RenameCommand {
             MoveRemote {
                 enabled: !no_move_remote,
                 target: new_repo.clone()
+            },
+            // Don't forget to update the remote URL to match the new repository name
+            GitUpdateRemote {
+                name: "origin",
+                target: new_repo.clone()
             }
         ]
         .apply_repo(core, &repo.clone())
         .await?;
 
-        // Don't forget to update the remote URL to match the new repository name
-        sequence![GitRemote { name: "origin" }]
-            .apply_repo(core, &new_repo)
-            .await?;
-
         Ok(0)
     }
 
@@ -160,7 +160,10 @@ mod tests {
 
         let src_repo: Repo = core.resolve(source_repo).unwrap();
 
-        GitInit {}.apply_repo(&core, &src_repo).await.unwrap();
+        GitInit::default()
+            .apply_repo(&core, &src_repo)
+            .await
+            .unwrap();
 
         assert!(src_repo.path.exists());
         assert!(src_repo.valid());
@@ -0,0 +1,132 @@
+use crate::errors::HumanErrorResultExt;
+
+use super::*;
+use clap::Arg;
+use tracing_batteries::prelude::*;
+
+pub struct SchemaCommand;
+crate::command!(SchemaCommand);
+
+#[async_trait]
+impl CommandRunnable for SchemaCommand {
+    fn name(&self) -> String {
+        String::from("schema")
+    }
+    fn app(&self) -> clap::Command {
+        clap::Command::new(self.name())
+            .version("1.0")
+            .about("prints the JSON schema for one of Git-Tool's file formats")
+            .long_about("Prints the JSON Schema for your config file (config), a repository's git-tool.yml file (repo) or a registry template (template). The schema is generated from the version of Git-Tool you are running, so it always matches the files it accepts and can be used by your editor to provide completion and validation.")
+            .arg(Arg::new("kind")
+                .help("the kind of file you want the schema for")
+                .value_parser(["config", "repo", "template"])
+                .required(true)
+                .index(1))
+    }
+
+    #[tracing::instrument(name = "gt schema", err, skip(self, core, matches))]
+    async fn run(&self, core: &Core, matches: &ArgMatches) -> Result<i32, engine::Error> {
+        let schema = match matches.get_one::<String>("kind").map(|s| s.as_str()) {
+            Some("config") => schemars::schema_for!(engine::Config),
+            Some("repo") => schemars::schema_for!(engine::RepoConfig),
+            Some("template") => schemars::schema_for!(online::registry::Entry),
+            _ => {
+                return Err(human_errors::user(
+                    "You did not specify which schema you want to view.",
+                    &[
+                        "Specify one of 'config', 'repo' or 'template', for example: `gt schema config`.",
+                    ],
+                ));
+            }
+        };
+
+        let json = serde_json::to_string_pretty(&schema).map_err(|err| {
+            human_errors::wrap_system(
+                err,
+                "We couldn't serialize the JSON schema for this file format.",
+                &["Please report this issue to us on GitHub so that we can resolve it."],
+            )
+        })?;
+
+        writeln!(core.output(), "{json}").to_human_error()?;
+
+        Ok(0)
+    }
+
+    #[tracing::instrument(
+        name = "gt complete -- gt schema",
+        skip(self, _core, completer, matches)
+    )]
+    async fn complete(&self, _core: &Core, completer: &Completer, matches: &ArgMatches) {
+        if !matches.contains_id("kind") {
+            completer.offer_many(vec!["config", "repo", "template"]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get_schema(kind: &str) -> serde_json::Value {
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_default_config()
+            .with_console(console.clone())
+            .build();
+
+        let cmd = SchemaCommand {};
+        let args = cmd.app().get_matches_from(vec!["schema", kind]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        serde_json::from_str(&console.to_string()).expect("the output should be valid JSON")
+    }
+
+    #[tokio::test]
+    async fn run_config() {
+        let schema = get_schema("config").await;
+
+        for key in [
+            "$schema",
+            "directory",
+            "services",
+            "apps",
+            "features",
+            "profiles",
+        ] {
+            assert!(
+                schema["properties"].get(key).is_some(),
+                "the config schema should describe the '{key}' key: {schema}"
+            );
+        }
+
+        assert!(
+            schema["properties"].get("config_file").is_none(),
+            "the config schema should not describe internal fields"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_repo() {
+        let schema = get_schema("repo").await;
+
+        for key in ["tasks", "worktree"] {
+            assert!(
+                schema["properties"].get(key).is_some(),
+                "the repo schema should describe the '{key}' key: {schema}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn run_template() {
+        let schema = get_schema("template").await;
+
+        for key in ["name", "description", "configs"] {
+            assert!(
+                schema["properties"].get(key).is_some(),
+                "the template schema should describe the '{key}' key: {schema}"
+            );
+        }
+    }
+}
@@ -1,7 +1,11 @@
 use crate::{
-    completion::get_shells, engine::Prompter, errors::HumanErrorResultExt, fs::to_native_path,
+    completion::get_shells,
+    engine::{Config, Prompter},
+    errors::HumanErrorResultExt,
+    fs::to_native_path,
+    online::registry::{Entry, Registry},
 };
-use std::{io::ErrorKind, path::PathBuf, writeln};
+use std::{collections::BTreeMap, io::ErrorKind, path::PathBuf, writeln};
 use tracing_batteries::prelude::*;
 
 use clap::Arg;
@@ -70,6 +74,7 @@ impl CommandRunnable for SetupCommand {
             .config()
             .with_dev_directory(&dev_directory)
             .with_feature_flag("telemetry", enable_telemetry);
+        let new_config = self.prompt_apps(core, &mut prompter, new_config).await?;
 
         new_config
             .save(
@@ -151,6 +156,160 @@ impl SetupCommand {
         Ok(to_native_path(dev_dir))
     }
 
+    /// Offers the apps in the registry which can detect whether they are installed,
+    /// pre-selecting those which are installed on this machine.
+    async fn prompt_apps(
+        &self,
+        core: &Core,
+        prompter: &mut Prompter,
+        config: Config,
+    ) -> Result<Config, human_errors::Error> {
+        let registry = online::CompositeRegistry::from_config(core.config());
+        let ids = match registry.get_entries(core).await {
+            Ok(ids) => ids,
+            Err(err) => {
+                warn!(
+                    "Could not list the apps in the registry, skipping app selection: {}",
+                    err
+                );
+                return Ok(config);
+            }
+        };
+
+        let registry = &registry;
+        let entries =
+            futures::future::join_all(ids.into_iter().filter(|id| id.starts_with("apps/")).map(
+                |id| async move {
+                    let entry = registry.get_entry(core, &id).await;
+                    (id, entry)
+                },
+            ))
+            .await;
+
+        let mut apps: Vec<(String, Entry, bool, Option<String>)> = Vec::new();
+        for (id, entry) in entries {
+            let Ok(entry) = entry else {
+                continue;
+            };
+
+            let Some(app) = entry
+                .configs
+                .iter()
+                .filter(|ec| ec.is_compatible())
+                .find_map(|ec| ec.app.as_ref().filter(|app| app.detect.is_some()))
+            else {
+                continue;
+            };
+
+            let installed = app.is_installed();
+            let version = if installed {
+                app.get_version().await
+            } else {
+                None
+            };
+
+            apps.push((id, entry, installed, version));
+        }
+
+        if apps.is_empty() {
+            return Ok(config);
+        }
+
+        writeln!(
+            core.output(),
+            "\nThese apps can be added to your config, those marked with [x] are installed on your machine:"
+        )
+        .to_human_error()?;
+        for (id, entry, installed, version) in apps.iter() {
+            writeln!(
+                core.output(),
+                "  [{}] {id} - {}{}",
+                if *installed { "x" } else { " " },
+                entry.name,
+                version
+                    .as_ref()
+                    .map(|v| format!(" ({v})"))
+                    .unwrap_or_default()
+            )
+            .to_human_error()?;
+        }
+
+        let defaults = apps
+            .iter()
+            .filter(|(_, _, installed, _)| *installed)
+            .map(|(id, _, _, _)| id.trim_start_matches("apps/"))
+            .join(",");
+        let find = |name: &str| {
+            apps.iter()
+                .position(|(id, _, _, _)| id == name || id.trim_start_matches("apps/") == name)
+        };
+
+        let selected = prompter
+            .prompt(
+                &format!(
+                    "Enter the apps you would like to add, separated by commas [{}]: ",
+                    if defaults.is_empty() {
+                        "none"
+                    } else {
+                        &defaults
+                    }
+                ),
+                |line| {
+                    if line.is_empty() || line == "none" {
+                        return true;
+                    }
+
+                    match line
+                        .split(',')
+                        .map(|name| name.trim())
+                        .find(|name| find(name).is_none())
+                    {
+                        Some(name) => {
+                            writeln!(
+                                core.output(),
+                                " [!] We couldn't find the '{name}' app, please try again."
+                            )
+                            .unwrap_or_default();
+                            false
+                        }
+                        None => true,
+                    }
+                },
+            )?
+            .filter(|line| !line.is_empty())
+            .unwrap_or(defaults);
+
+        let selected: Vec<usize> = selected
+            .split(',')
+            .filter_map(|name| find(name.trim()))
+            .unique()
+            .collect();
+
+        let mut config = config;
+        for (i, (id, entry, _, _)) in apps.into_iter().enumerate() {
+            if !selected.contains(&i) {
+                continue;
+            }
+
+            let variables = entry.get_variables(core, &BTreeMap::new())?;
+            for ec in entry.configs.into_iter().filter(|ec| ec.is_compatible()) {
+                let ec = ec.with_variables(&variables);
+                match config.apply_template(ec.clone(), false) {
+                    Ok(updated) => {
+                        config =
+                            updated.with_installed_template(&ec, &id, &entry.version, &variables);
+                    }
+                    Err(err) => {
+                        writeln!(core.output(), " [!] Skipping {id}: {}", err.message())
+                            .to_human_error()?;
+                    }
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
     fn prompt_setup_shell(
         &self,
         core: &Core,
@@ -217,10 +376,12 @@ mod tests {
     async fn run() {
         let temp = tempdir().unwrap();
 
+        // Answers the dev directory, telemetry, app selection and shell prompts in turn.
         let console =
-            crate::console::mock_with_input(&format!("{}\ny\nzsh\n", temp.path().display()));
+            crate::console::mock_with_input(&format!("{}\ny\nnone\nzsh\n", temp.path().display()));
         let core = Core::builder()
             .with_default_config()
+            .with_registry_cache_directory(temp.path().join("cache"))
             .with_console(console.clone())
             .build();
 
@@ -237,12 +398,74 @@ mod tests {
             "the output should contain the project directory"
         );
         assert!(
-            console.to_string().contains("zsh"),
-            "the output should contain the selected shell"
+            console
+                .to_string()
+                .contains("add the following to your shell's config file (~/.zshrc)"),
+            "the output should explain how to configure the selected shell"
         );
         assert!(
             console.to_string().contains("alias gt="),
             "the output should contain the alias command"
         );
     }
+
+    #[tokio::test]
+    async fn run_with_apps() {
+        let temp = tempdir().unwrap();
+        let registry = temp.path().join("registry");
+        std::fs::create_dir_all(registry.join("apps")).unwrap();
+        for (id, binary) in [("git", "git"), ("missing", "gt-missing-app")] {
+            std::fs::write(
+                registry.join("apps").join(format!("{id}.yaml")),
+                format!(
+                    "name: {id}\ndescription: Runs {id}.\nversion: 1.0.0\nconfigs:\n  - platform: any\n    app:\n      name: {id}\n      command: {binary}\n      detect:\n        binaries: [{binary}]\n"
+                ),
+            )
+            .unwrap();
+        }
+
+        let config_file = temp.path().join("config.yml");
+        std::fs::write(
+            &config_file,
+            format!(
+                "directory: {}\nregistries:\n  - path: {}\n",
+                temp.path().display(),
+                registry.display()
+            ),
+        )
+        .unwrap();
+
+        let console =
+            crate::console::mock_with_input(&format!("{}\ny\n\nzsh\n", temp.path().display()));
+        let core = Core::builder()
+            .with_config_file(&config_file)
+            .expect("the config file should be loaded")
+            .with_registry_cache_directory(temp.path().join("cache"))
+            .with_console(console.clone())
+            .with_mock_http_client(vec![engine::MockHttpRoute::new(
+                "GET",
+                "/repos/SierraSoftworks/git-tool/git/trees/main",
+                200,
+                r#"{ "tree": [], "truncated": false }"#,
+            )])
+            .build();
+
+        let cmd = SetupCommand {};
+        let args = cmd.app().get_matches_from(vec!["setup", "--force"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output.contains("[x] apps/git - git"),
+            "installed apps should be pre-selected: {output}"
+        );
+        assert!(
+            output.contains("[ ] apps/missing - missing"),
+            "apps which aren't installed should not be selected: {output}"
+        );
+
+        let config = Config::from_file(&config_file).unwrap();
+        assert!(config.get_app("git").is_some());
+        assert!(config.get_app("missing").is_none());
+    }
 }
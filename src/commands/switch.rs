@@ -1,7 +1,8 @@
 use super::*;
-use crate::engine::Target;
+use crate::engine::{Branch, Target, features};
 use crate::errors::HumanErrorResultExt;
 use crate::git;
+use crate::search;
 use crate::tasks::*;
 use clap::Arg;
 use itertools::Itertools;
@@ -22,18 +23,40 @@ impl CommandRunnable for SwitchCommand {
             .about("switches to the specified branch.")
             .visible_aliases(["sw", "branch", "b", "br"])
             .long_about(
-                "This command switches to the specified branch within the current repository.",
+                "This command switches to the specified branch within the current repository. The branch name is matched fuzzily against your local and remote branches, while new branches are only created when you provide the --create flag (and are named using your branch_template, if you have configured one).",
             )
             .arg(
                 Arg::new("branch")
                     .help("The name of the branch to switch to.")
                     .index(1),
             )
+            .arg(
+                Arg::new("create")
+                    .short('c')
+                    .long("create")
+                    .help("create a new branch, named using your branch_template if you have one.")
+                    .action(clap::ArgAction::SetTrue),
+            )
             .arg(
                 Arg::new("no-create")
                     .short('N')
                     .long("no-create")
-                    .help("don't create the branch if it doesn't exist.")
+                    .help("don't create the branch if it doesn't exist (this is the default unless --create is provided).")
+                    .conflicts_with("create")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("stash")
+                    .short('s')
+                    .long("stash")
+                    .help("stash any uncommitted changes, restoring them when you return to this branch.")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("fetch")
+                    .short('f')
+                    .long("fetch")
+                    .help("fetch the latest branches from your remotes if the branch isn't known locally.")
                     .action(clap::ArgAction::SetTrue),
             )
     }
@@ -43,22 +66,58 @@ impl CommandRunnable for SwitchCommand {
         let repo: Repo = core.resolve(())?;
 
         match matches.get_one::<String>("branch") {
-            Some(branch) => {
+            Some(query) => {
+                let create = matches.get_flag("create");
+                let fetch = matches.get_flag("fetch");
+                let branch = if create {
+                    match core.config().get_branch_template() {
+                        Some(template) => Branch::from_template(template, query)?.to_string(),
+                        None => query.clone(),
+                    }
+                } else {
+                    Self::find_branch(core, &repo, query, fetch).await?
+                };
+
+                let mut autostash = matches.get_flag("stash")
+                    || core.config().get_features().has(features::SWITCH_AUTOSTASH);
+
+                if !autostash
+                    && !git::git_worktree_is_clean(&repo.get_path())
+                        .await
+                        .unwrap_or(true)
+                {
+                    match Self::prompt_dirty_action(core, &repo, &branch)? {
+                        DirtyAction::Stash => autostash = true,
+                        DirtyAction::Bring => {}
+                        DirtyAction::Worktree => {
+                            // The worktree command applies the branch naming template itself
+                            // when creating a new branch, so we hand it the original query.
+                            let worktree = super::worktree::WorktreeCommand;
+                            let mut args = vec!["worktree"];
+                            if create {
+                                args.push(query.as_str());
+                            } else {
+                                args.extend(["--no-create", branch.as_str()]);
+                            }
+
+                            return worktree
+                                .run(core, &worktree.app().get_matches_from(args))
+                                .await;
+                        }
+                    }
+                }
+
                 sequence![GitSwitch {
-                    branch: branch.to_string(),
-                    create_if_missing: !matches.get_flag("no-create"),
+                    branch,
+                    create_if_missing: create,
+                    autostash,
+                    fetch,
                 }]
                 .apply_repo(core, &repo)
                 .await?;
             }
             None => {
-                let branches = git::git_branches(&repo.get_path()).await?;
-                for branch in branches
-                    .iter()
-                    .map(|v| Self::local_branch_name(v))
-                    .unique()
-                    .sorted()
-                {
+                for branch in Self::branch_names(&repo).await? {
                     writeln!(core.output(), "{branch}").to_human_error()?;
                 }
             }
@@ -74,27 +133,148 @@ impl CommandRunnable for SwitchCommand {
         let repo: Result<Repo, _> = core.resolve(());
         if let Ok(repo) = repo {
             completer.offer("--create");
-            if let Ok(branches) = git::git_branches(&repo.get_path()).await {
-                completer.offer_many(
-                    branches
-                        .iter()
-                        .map(|v| Self::local_branch_name(v))
-                        .unique()
-                        .sorted(),
-                );
+            completer.offer("--stash");
+            completer.offer("--fetch");
+            if let Ok(branches) = Self::branch_names(&repo).await {
+                completer.offer_many(branches);
             }
         }
     }
 }
 
+/// What to do with the uncommitted changes in a repository when switching branches.
+enum DirtyAction {
+    Stash,
+    Bring,
+    Worktree,
+}
+
 impl SwitchCommand {
-    fn local_branch_name(branch: &str) -> String {
-        if let Some(short_branch) = branch.strip_prefix("origin/") {
-            short_branch.to_owned()
-        } else {
-            branch.to_owned()
+    /// Finds the existing (local or remote) branch which best matches the query,
+    /// asking the user to choose when several branches match equally well. When
+    /// `fetch` is set and the branch isn't known locally, the latest branches are
+    /// fetched from the repository's remotes before searching.
+    async fn find_branch(
+        core: &Core,
+        repo: &Repo,
+        query: &str,
+        fetch: bool,
+    ) -> Result<String, human_errors::Error> {
+        let mut branches = Self::branch_names(repo).await?;
+        if fetch && !branches.iter().any(|branch| branch == query) {
+            git::git_fetch_all(&repo.get_path()).await?;
+            branches = Self::branch_names(repo).await?;
+        }
+
+        if branches.iter().any(|branch| branch == query) {
+            return Ok(query.to_string());
+        }
+
+        let matches = search::best_matches(query, branches);
+        match matches.len() {
+            0 => Err(human_errors::user(
+                format!("Could not find a branch matching '{query}' in {repo}."),
+                &["Use `gt switch --create <branch>` if you would like to create a new branch."],
+            )),
+            1 => Ok(matches[0].clone()),
+            _ if core
+                .config()
+                .get_features()
+                .has(features::ALWAYS_OPEN_BEST_MATCH) =>
+            {
+                Ok(matches[0].clone())
+            }
+            _ => Self::prompt_branch_choice(core, query, &matches),
         }
     }
+
+    fn prompt_branch_choice(
+        core: &Core,
+        query: &str,
+        matches: &[String],
+    ) -> Result<String, human_errors::Error> {
+        {
+            let mut output = core.output();
+            writeln!(
+                output,
+                "The branch name '{query}' matched more than one branch:"
+            )
+            .to_human_error()?;
+            for (index, branch) in matches.iter().enumerate() {
+                writeln!(output, "  {}) {branch}", index + 1).to_human_error()?;
+            }
+        }
+
+        let answer = core.prompter().prompt(
+            &format!(
+                "Which branch would you like to switch to? [1-{}]: ",
+                matches.len()
+            ),
+            |answer| {
+                answer
+                    .parse::<usize>()
+                    .is_ok_and(|index| index >= 1 && index <= matches.len())
+            },
+        )?;
+
+        match answer.and_then(|answer| answer.parse::<usize>().ok()) {
+            Some(index) => Ok(matches[index - 1].clone()),
+            None => Err(human_errors::user(
+                format!("The branch name '{query}' matched more than one branch."),
+                &[
+                    "Try entering a branch name that is unique, or the full branch name, to avoid confusion.",
+                ],
+            )),
+        }
+    }
+
+    fn prompt_dirty_action(
+        core: &Core,
+        repo: &Repo,
+        branch: &str,
+    ) -> Result<DirtyAction, human_errors::Error> {
+        let answer = core.prompter().prompt(
+            &format!(
+                "You have uncommitted changes in {repo}. Would you like to [s]tash them, [b]ring them with you, or open '{branch}' in a [w]orktree instead? [s/B/w]: "
+            ),
+            |answer| {
+                matches!(
+                    answer.to_lowercase().as_str(),
+                    "s" | "stash" | "b" | "bring" | "w" | "worktree"
+                )
+            },
+        )?;
+
+        Ok(
+            match answer.map(|answer| answer.to_lowercase()).as_deref() {
+                Some("s") | Some("stash") => DirtyAction::Stash,
+                Some("w") | Some("worktree") => DirtyAction::Worktree,
+                _ => DirtyAction::Bring,
+            },
+        )
+    }
+
+    /// Lists the names of the repository's local and remote branches, with remote
+    /// branches named as they would be once checked out locally.
+    async fn branch_names(repo: &Repo) -> Result<Vec<String>, human_errors::Error> {
+        let remotes = git::git_remote_list(&repo.get_path()).await?;
+
+        Ok(git::git_branches(&repo.get_path())
+            .await?
+            .iter()
+            .map(|v| Self::local_branch_name(v, &remotes))
+            .unique()
+            .sorted()
+            .collect())
+    }
+
+    fn local_branch_name(branch: &str, remotes: &[String]) -> String {
+        remotes
+            .iter()
+            .find_map(|remote| branch.strip_prefix(remote.as_str())?.strip_prefix('/'))
+            .unwrap_or(branch)
+            .to_owned()
+    }
 }
 
 #[cfg(test)]
@@ -146,7 +326,7 @@ mod tests {
 
         sequence!(
             // Run a `git init` to setup the repo
-            GitInit {},
+            GitInit::default(),
             GitRemote { name: "origin" },
             // Create the branch we want to switch to
             GitCheckout {
@@ -169,7 +349,7 @@ mod tests {
         .await
         .unwrap();
 
-        sequence!(GitInit {}, GitRemote { name: "origin" })
+        sequence!(GitInit::default(), GitRemote { name: "origin" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -280,6 +460,50 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn switch_dirty_prompts_to_stash() {
+        let cmd: SwitchCommand = SwitchCommand {};
+
+        let temp = tempdir().unwrap();
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_console(crate::console::mock_with_input("s\n"));
+
+        let (core, repo) = setup_test_repo_with_remote(core, &temp).await;
+
+        std::fs::write(repo.get_path().join("README.md"), "Uncommitted changes").unwrap();
+
+        let args: ArgMatches = cmd.app().get_matches_from(vec!["switch", "feature/test"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(
+            git::git_current_branch(&repo.get_path()).await.unwrap(),
+            "feature/test"
+        );
+
+        let stashes = git::git_stash_list(&repo.get_path()).await.unwrap();
+        assert_eq!(stashes.len(), 1, "the changes on main should be stashed");
+        assert!(stashes[0].message.ends_with("git-tool autostash: main"));
+
+        let args: ArgMatches = cmd
+            .app()
+            .get_matches_from(vec!["switch", "--stash", "main"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(
+            std::fs::read_to_string(repo.get_path().join("README.md")).unwrap(),
+            "Uncommitted changes",
+            "the auto-stash should be restored when returning to main"
+        );
+        assert!(
+            git::git_stash_list(&repo.get_path())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn switch_no_create_branch_exists() {
         let cmd: SwitchCommand = SwitchCommand {};
@@ -330,7 +554,9 @@ mod tests {
 
         let (core, repo) = setup_test_repo_with_remote(core, &temp).await;
 
-        let args: ArgMatches = cmd.app().get_matches_from(vec!["switch", "feature/test2"]);
+        let args: ArgMatches = cmd
+            .app()
+            .get_matches_from(vec!["switch", "-c", "feature/test2"]);
         cmd.assert_run_successful(&core, &args).await;
 
         assert_eq!(
@@ -339,6 +565,74 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn switch_without_create_fuzzy_matches() {
+        let cmd: SwitchCommand = SwitchCommand {};
+
+        let temp = tempdir().unwrap();
+
+        let core = Core::builder().with_config_for_dev_directory(temp.path());
+
+        let (core, repo) = setup_test_repo_with_remote(core, &temp).await;
+
+        let args: ArgMatches = cmd.app().get_matches_from(vec!["switch", "ftest"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(
+            git::git_current_branch(&repo.get_path()).await.unwrap(),
+            "feature/test"
+        );
+
+        let args: ArgMatches = cmd.app().get_matches_from(vec!["switch", "missing"]);
+        cmd.run(&core, &args)
+            .await
+            .expect_err("branches should not be created without --create");
+    }
+
+    #[tokio::test]
+    async fn switch_create_uses_branch_template() {
+        let cmd: SwitchCommand = SwitchCommand {};
+
+        let temp = tempdir().unwrap();
+
+        let cfg = Config::from_str(&format!(
+            "directory: {}\nbranch_template: \"feat/{{{{ .Ticket }}}}-{{{{ .Slug }}}}\"",
+            temp.path().display()
+        ))
+        .unwrap();
+        let core = Core::builder().with_config(cfg);
+
+        let (core, repo) = setup_test_repo_with_remote(core, &temp).await;
+
+        let args: ArgMatches =
+            cmd.app()
+                .get_matches_from(vec!["switch", "-c", "PROJ-7 Add a feature"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(
+            git::git_current_branch(&repo.get_path()).await.unwrap(),
+            "feat/PROJ-7-add-a-feature"
+        );
+    }
+
+    #[test]
+    fn local_branch_name_strips_remote_prefixes() {
+        let remotes = vec!["origin".to_string(), "upstream".to_string()];
+
+        assert_eq!(
+            SwitchCommand::local_branch_name("origin/feature/test", &remotes),
+            "feature/test"
+        );
+        assert_eq!(
+            SwitchCommand::local_branch_name("upstream/feature/test", &remotes),
+            "feature/test"
+        );
+        assert_eq!(
+            SwitchCommand::local_branch_name("feature/test", &remotes),
+            "feature/test"
+        );
+    }
+
     #[tokio::test]
     async fn switch_branch_bare_repo() {
         let cmd: SwitchCommand = SwitchCommand {};
@@ -364,11 +658,13 @@ mod tests {
             .build();
 
         // Run a `git init` to setup the repo
-        GitInit {}.apply_repo(&core, &repo).await.unwrap();
+        GitInit::default().apply_repo(&core, &repo).await.unwrap();
 
         assert!(repo.valid(), "the repository should exist and be valid");
 
-        let args: ArgMatches = cmd.app().get_matches_from(vec!["switch", "feature/test"]);
+        let args: ArgMatches =
+            cmd.app()
+                .get_matches_from(vec!["switch", "--create", "feature/test"]);
         cmd.assert_run_successful(&core, &args).await;
 
         assert!(repo.valid(), "the repository should still be valid");
@@ -1,5 +1,5 @@
 use super::*;
-use crate::engine::RepoConfig;
+use crate::engine::{RepoConfig, Worktree};
 use crate::errors::HumanErrorResultExt;
 use clap::Arg;
 use itertools::Itertools;
@@ -29,7 +29,13 @@ Tasks are only executed once you have confirmed that you trust the repository's
 
     #[tracing::instrument(name = "gt task", err, skip(self, core, matches))]
     async fn run(&self, core: &Core, matches: &ArgMatches) -> Result<i32, human_errors::Error> {
-        let repo: Repo = core.resolve(())?;
+        // Tasks run from within a linked worktree target the worktree, so that they
+        // see its checkout and the ports which have been allocated to it.
+        let worktree: Option<Worktree> = core.resolve(()).ok();
+        let repo: Repo = match &worktree {
+            Some(worktree) => worktree.target_repo().clone(),
+            None => core.resolve(())?,
+        };
         let task_name = matches.get_one::<String>("task").cloned();
 
         let config = RepoConfig::for_repo(&repo)?.ok_or_else(|| {
@@ -82,14 +88,21 @@ Tasks are only executed once you have confirmed that you trust the repository's
         }
 
         let app = task.to_app(&task_name);
-        let status = core.launcher().run(&app, &repo).await?;
+        let status = match &worktree {
+            Some(worktree) => core.launcher().run(&app, worktree).await?,
+            None => core.launcher().run(&app, &repo).await?,
+        };
 
         Ok(status)
     }
 
     #[tracing::instrument(name = "gt complete -- gt task", skip(self, core, completer, _matches))]
     async fn complete(&self, core: &Core, completer: &Completer, _matches: &ArgMatches) {
-        let repo: Result<Repo, _> = core.resolve(());
+        let worktree: Result<Worktree, _> = core.resolve(());
+        let repo: Result<Repo, _> = match worktree {
+            Ok(worktree) => Ok(worktree.target_repo().clone()),
+            Err(_) => core.resolve(()),
+        };
         if let Ok(repo) = repo
             && let Ok(Some(config)) = RepoConfig::for_repo(&repo)
         {
@@ -131,6 +144,9 @@ mod tests {
             .with_config(cfg)
             .with_null_console()
             .with_mock_resolver(move |mock| {
+                mock.expect_get_current_worktree()
+                    .returning(|| Err(human_errors::user("Not in a worktree.", &[])));
+
                 let repo_path = repo_path.clone();
                 mock.expect_get_current_repo().returning(move || {
                     Ok(Repo::new("gh:sierrasoftworks/test-task", repo_path.clone()))
@@ -146,6 +162,54 @@ mod tests {
         cmd.assert_run_successful(&core, &args).await;
     }
 
+    #[tokio::test]
+    async fn run_task_in_current_worktree_exposes_ports() {
+        let cmd = TaskCommand {};
+        let args = cmd.app().get_matches_from(vec!["task", "build"]);
+
+        let temp = tempfile::tempdir().unwrap();
+        let worktree_path = temp.path().join("worktrees").join("test-task-feature");
+        write_repo_config(&worktree_path, EXAMPLE_CONFIG);
+
+        let repo = Repo::new("gh:sierrasoftworks/test-task", temp.path().join("repo"));
+        let worktree = Worktree::at(
+            &repo,
+            &"feature/test".parse().unwrap(),
+            worktree_path.clone(),
+        )
+        .with_ports([("web".to_string(), 8081)].into());
+        let config = RepoConfig::for_repo(worktree.target_repo())
+            .unwrap()
+            .unwrap();
+
+        let cfg = Config::for_dev_directory(temp.path())
+            .with_trusted_repo(repo.to_string(), config.hash().unwrap());
+
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_null_console()
+            .with_mock_resolver(move |mock| {
+                let worktree = worktree.clone();
+                mock.expect_get_current_worktree()
+                    .returning(move || Ok(worktree.clone()));
+            })
+            .with_mock_launcher(move |mock| {
+                let worktree_path = worktree_path.clone();
+                mock.expect_run()
+                    .times(1)
+                    .withf(move |_, target| {
+                        target.get_path() == worktree_path
+                            && target
+                                .environment()
+                                .contains(&("WEB_PORT".to_string(), "8081".to_string()))
+                    })
+                    .returning(|_, _| Box::pin(async { Ok(0) }));
+            })
+            .build();
+
+        cmd.assert_run_successful(&core, &args).await;
+    }
+
     #[tokio::test]
     async fn list_tasks_in_current_repo() {
         let cmd = TaskCommand {};
@@ -160,6 +224,9 @@ mod tests {
             .with_config_for_dev_directory(temp.path())
             .with_console(console.clone())
             .with_mock_resolver(move |mock| {
+                mock.expect_get_current_worktree()
+                    .returning(|| Err(human_errors::user("Not in a worktree.", &[])));
+
                 let repo_path = repo_path.clone();
                 mock.expect_get_current_repo().returning(move || {
                     Ok(Repo::new("gh:sierrasoftworks/test-task", repo_path.clone()))
@@ -187,6 +254,9 @@ mod tests {
             .with_config_for_dev_directory(temp.path())
             .with_null_console()
             .with_mock_resolver(move |mock| {
+                mock.expect_get_current_worktree()
+                    .returning(|| Err(human_errors::user("Not in a worktree.", &[])));
+
                 let repo_path = repo_path.clone();
                 mock.expect_get_current_repo().returning(move || {
                     Ok(Repo::new("gh:sierrasoftworks/test-task", repo_path.clone()))
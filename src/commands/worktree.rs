@@ -1,13 +1,21 @@
 use super::*;
-use crate::engine::{Branch, Repo, Resolver, Target, Worktree};
+use crate::engine::{App, Branch, PortRegistry, Repo, Resolver, Target, Worktree};
 use crate::errors::HumanErrorResultExt;
 use crate::git;
+use crate::search;
 use crate::tasks::*;
 use clap::Arg;
-use human_errors::ResultExt;
+use futures::StreamExt;
+use human_errors::{OptionExt, ResultExt};
 use itertools::Itertools;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use tracing_batteries::prelude::*;
 
+/// The number of repositories (or worktrees) which are inspected at once, keeping the
+/// number of concurrent git processes (and open files) bounded on large dev directories.
+const WORKTREE_CONCURRENCY: usize = 8;
+
 pub struct WorktreeCommand;
 crate::command!(WorktreeCommand);
 
@@ -43,10 +51,58 @@ Worktrees are created within the worktree directory configured in your config fi
                     .long("rm")
                     .help("remove the worktree once the launched application exits.")
                     .action(clap::ArgAction::SetTrue))
+            .args_conflicts_with_subcommands(true)
+            .subcommand(clap::Command::new("gc")
+                .version("1.0")
+                .about("removes orphaned worktree directories and stale worktree registrations")
+                .long_about("Finds directories within your worktree directory which no longer belong to a repository (for example because the repository was removed or renamed) and worktrees which your repositories still have registered even though their directories have been deleted. After confirmation, the orphaned directories are removed and `git worktree prune` is run for each repository with stale registrations.")
+                .arg(Arg::new("yes")
+                    .long("yes")
+                    .short('y')
+                    .help("Do not prompt for confirmation before removing anything")
+                    .action(clap::ArgAction::SetTrue)))
+            .subcommand(clap::Command::new("list")
+                .version("1.0")
+                .alias("ls")
+                .about("lists worktrees along with their status")
+                .long_about("Lists the worktrees of the current repository (or every repository in your dev directory with `--all`), showing each worktree's branch, whether it has uncommitted changes, how many commits it is ahead of and behind its upstream (or the default branch), when it was last committed to and how much disk space it is using.")
+                .arg(Arg::new("all")
+                    .long("all")
+                    .short('a')
+                    .help("list the worktrees of every repository in your dev directory")
+                    .action(clap::ArgAction::SetTrue)))
+            .subcommand(clap::Command::new("open")
+                .version("1.0")
+                .about("opens an existing worktree from any repository using an application")
+                .long_about("Finds the existing worktree which best matches the provided repository and branch name (across every repository in your dev directory) and launches an application within it.")
+                .arg(Arg::new("worktree")
+                    .help("The repository and branch of the worktree to open, matched fuzzily (for example 'git-tool#feat/forgejo' or just 'forgejo').")
+                    .required(true))
+                .arg(Arg::new("app")
+                    .help("The application to launch within the worktree (defaults to your default app).")))
+            .subcommand(clap::Command::new("mv")
+                .version("1.0")
+                .alias("move")
+                .about("renames a worktree's branch and moves the worktree to match")
+                .long_about("Renames the branch checked out in one of the current repository's worktrees and moves the worktree to the directory named after the new branch, re-applying the repository's worktree symlinks and templates and keeping any ports allocated to it.")
+                .arg(Arg::new("old")
+                    .help("The branch of the worktree which should be renamed.")
+                    .required(true))
+                .arg(Arg::new("new")
+                    .help("The new name for the worktree's branch.")
+                    .required(true)))
     }
 
     #[tracing::instrument(name = "gt worktree", err, skip(self, core, matches))]
     async fn run(&self, core: &Core, matches: &ArgMatches) -> Result<i32, human_errors::Error> {
+        match matches.subcommand() {
+            Some(("gc", matches)) => return self.run_gc(core, matches).await,
+            Some(("list", matches)) => return self.run_list(core, matches).await,
+            Some(("open", matches)) => return self.run_open(core, matches).await,
+            Some(("mv", matches)) => return self.run_mv(core, matches).await,
+            _ => {}
+        }
+
         let no_create = matches.get_flag("no-create");
         let base = matches.get_one::<String>("base");
         let remove_after = matches.get_flag("rm");
@@ -87,14 +143,16 @@ Worktrees are created within the worktree directory configured in your config fi
         // implied context; a lone app-named token is therefore treated as the
         // branch.
         let parsed = crate::completion::parse::<Branch>(core, None, matches)?;
-        let branch = &parsed.target;
+        let branch = &self
+            .name_new_branch(core, &repo, &parsed.target, no_create)
+            .await?;
 
         // Overrides apply only to the launched application, never to the
         // repository's worktree automation tasks.
         let app = parsed.launch_app(core)?;
 
         // Resolve the branch to the worktree it maps to within this repository.
-        let worktree: Worktree = core.resolve((&repo, branch))?;
+        let mut worktree: Worktree = core.resolve((&repo, branch))?;
         let worktree_path = worktree.path();
 
         sequence![GitWorktree {
@@ -106,14 +164,15 @@ Worktrees are created within the worktree directory configured in your config fi
         .apply_repo(core, &repo)
         .await?;
 
-        // Apply any worktree automation (symlinks and setup tasks) defined in the
-        // repository's 'git-tool.yml' file before launching the application. The
-        // configuration must be trusted before we run any of its tasks; if the
-        // user declines we simply skip the automation and continue.
+        // Apply any worktree automation (ports, symlinks, copies, templates and setup
+        // tasks) defined in the repository's 'git-tool.yml' file before launching the
+        // application. The configuration must be trusted before we run any of its
+        // tasks; if the user declines we simply skip the automation and continue.
         if let Some(repo_config) = crate::engine::RepoConfig::for_repo(&repo)?
             && repo_config.worktree().is_some()
             && crate::commands::trust::ensure_trusted(core, &repo, &repo_config).await?
         {
+            worktree = self.allocate_worktree_ports(core, worktree, &repo_config)?;
             self.apply_worktree_automation(core, &repo, &worktree, &repo_config)
                 .await?;
         }
@@ -125,8 +184,23 @@ Worktrees are created within the worktree directory configured in your config fi
         // never force the removal: if there are uncommitted changes git will refuse
         // to delete the worktree and we surface that to the user so they don't lose
         // any work.
-        if remove_after {
+        if remove_after && core.dry_run() {
+            writeln!(
+                core.output(),
+                "[dry-run] Remove the worktree at '{}'",
+                worktree_path.display()
+            )
+            .to_human_error()?;
+        } else if remove_after {
             let cleanup = git::git_worktree_remove(&repo.get_path(), &worktree_path).await;
+            if cleanup.is_ok()
+                && let Err(err) = PortRegistry::for_config(core.config()).release(&worktree_path)
+            {
+                warn!(
+                    "Could not release the ports allocated to {}: {}",
+                    worktree, err
+                );
+            }
 
             // Surface the application's failure first, since that's the user's
             // primary concern, before reporting any cleanup problems.
@@ -152,7 +226,32 @@ Worktrees are created within the worktree directory configured in your config fi
         name = "gt complete -- gt worktree",
         skip(self, core, completer, _matches)
     )]
-    async fn complete(&self, core: &Core, completer: &Completer, _matches: &ArgMatches) {
+    async fn complete(&self, core: &Core, completer: &Completer, matches: &ArgMatches) {
+        match matches.subcommand() {
+            Some(("mv", _)) => {
+                let repo: Result<Repo, _> = core.resolve(());
+                if let Ok(repo) = repo
+                    && let Ok(worktrees) = git::git_worktree_list(&repo.get_path()).await
+                {
+                    completer.offer_many(
+                        worktrees
+                            .into_iter()
+                            .skip(1)
+                            .filter_map(|worktree| worktree.branch)
+                            .sorted(),
+                    );
+                }
+
+                return;
+            }
+            Some(_) => return,
+            None => {}
+        }
+
+        completer.offer("gc");
+        completer.offer("list");
+        completer.offer("open");
+        completer.offer("mv");
         completer.offer("--no-create");
         completer.offer("--base");
         completer.offer("--rm");
@@ -168,6 +267,475 @@ Worktrees are created within the worktree directory configured in your config fi
 }
 
 impl WorktreeCommand {
+    async fn run_list(
+        &self,
+        core: &Core,
+        matches: &ArgMatches,
+    ) -> Result<i32, human_errors::Error> {
+        let repos: Vec<Repo> = if matches.get_flag("all") {
+            core.resolve_many(())?
+        } else {
+            vec![core.resolve(())?]
+        };
+
+        let worktrees = self.get_linked_worktrees(repos).await;
+        if worktrees.is_empty() {
+            writeln!(core.output(), "No worktrees were found.").to_human_error()?;
+            return Ok(0);
+        }
+
+        let details: Vec<_> = futures::stream::iter(worktrees.iter())
+            .map(|(repo, worktree)| self.get_worktree_details(repo, worktree))
+            .buffered(WORKTREE_CONCURRENCY)
+            .collect()
+            .await;
+
+        let width = details
+            .iter()
+            .map(|d| d.label.len())
+            .max()
+            .unwrap_or_default();
+        for (repo, group) in &worktrees
+            .iter()
+            .zip(details.iter())
+            .chunk_by(|((repo, _), _)| repo.to_string())
+        {
+            writeln!(core.output(), "{repo}").to_human_error()?;
+            for ((_, worktree), details) in group {
+                writeln!(
+                    core.output(),
+                    "  {:width$}  {:7}  {:>9}  {:16}  {:>9}  {}",
+                    details.label,
+                    match details.clean {
+                        Some(true) => "clean",
+                        Some(false) => "changed",
+                        None => "unknown",
+                    },
+                    details
+                        .ahead_behind
+                        .map(|(ahead, behind)| format!("+{ahead}/-{behind}"))
+                        .unwrap_or_else(|| "-".into()),
+                    details.last_commit.as_deref().unwrap_or("-"),
+                    format_size(details.size),
+                    worktree.path.display()
+                )
+                .to_human_error()?;
+            }
+        }
+
+        Ok(0)
+    }
+
+    async fn run_open(
+        &self,
+        core: &Core,
+        matches: &ArgMatches,
+    ) -> Result<i32, human_errors::Error> {
+        let query = matches
+            .get_one::<String>("worktree")
+            .map(|s| s.as_str())
+            .unwrap_or_default();
+
+        let repos: Vec<Repo> = core.resolve_many(())?;
+        let worktrees = self
+            .get_linked_worktrees(repos)
+            .await
+            .into_iter()
+            .filter(|(_, worktree)| worktree.branch.is_some());
+
+        let (repo, worktree) = search::best_matches_by(query, worktrees, |(repo, worktree)| {
+            format!("{repo}#{}", worktree.branch.as_deref().unwrap_or_default())
+        })
+        .into_iter()
+        .next()
+        .ok_or_user_err(
+            format!("Could not find a worktree matching '{query}'."),
+            &["Use `gt worktree list --all` to see the worktrees which are available."],
+        )?;
+
+        let branch: Branch = worktree.branch.as_deref().unwrap_or_default().parse()?;
+        let app: App = match matches.get_one::<String>("app") {
+            Some(name) => core.resolve(name.as_str())?,
+            None => core.resolve(())?,
+        };
+
+        let ports = PortRegistry::for_config(core.config()).get(&worktree.path);
+        let target = Worktree::at(&repo, &branch, worktree.path).with_ports(ports);
+        Ok(core.launcher().run(&app, &target).await?)
+    }
+
+    /// Names the branch for a new worktree using the user's branch naming template
+    /// when the requested branch doesn't exist yet and we are allowed to create it.
+    async fn name_new_branch(
+        &self,
+        core: &Core,
+        repo: &Repo,
+        branch: &Branch,
+        no_create: bool,
+    ) -> Result<Branch, human_errors::Error> {
+        let Some(template) = core.config().get_branch_template() else {
+            return Ok(branch.clone());
+        };
+
+        if no_create
+            || git::git_branches(&repo.get_path())
+                .await?
+                .iter()
+                .any(|b| b == branch.as_str())
+        {
+            return Ok(branch.clone());
+        }
+
+        Branch::from_template(template, branch.as_str())
+    }
+
+    async fn run_mv(&self, core: &Core, matches: &ArgMatches) -> Result<i32, human_errors::Error> {
+        let repo: Repo = core.resolve(())?;
+        let old: Branch = matches
+            .get_one::<String>("old")
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+            .parse()?;
+        let new: Branch = matches
+            .get_one::<String>("new")
+            .map(|s| s.as_str())
+            .unwrap_or_default()
+            .parse()?;
+
+        let worktree = git::git_worktree_list(&repo.get_path())
+            .await?
+            .into_iter()
+            .skip(1)
+            .find(|worktree| worktree.branch.as_deref() == Some(old.as_str()))
+            .ok_or_user_err(
+                format!("Could not find a worktree for the branch '{old}' in {repo}."),
+                &["Use `gt worktree list` to see the worktrees which are available."],
+            )?;
+
+        let target: Worktree = core.resolve((&repo, &new))?;
+        let destination = target.path();
+        if destination.exists() {
+            return Err(human_errors::user(
+                format!(
+                    "Could not move the worktree for '{old}' because '{}' already exists.",
+                    destination.display()
+                ),
+                &[
+                    "Remove the existing directory (or use `gt worktree gc` to clean up orphaned worktrees) and then try again.",
+                ],
+            ));
+        }
+
+        // Renaming the branch and moving the worktree are applied together, so that a
+        // failed move (like a locked worktree) puts the branch back the way it was.
+        sequence![
+            GitBranchRename {
+                from: old.to_string(),
+                to: new.to_string(),
+            },
+            GitWorktreeMove {
+                path: worktree.path.clone(),
+                destination: destination.clone(),
+            }
+        ]
+        .apply_repo(core, &repo)
+        .await?;
+
+        if core.dry_run() {
+            return Ok(0);
+        }
+
+        let ports = PortRegistry::for_config(core.config());
+        let target = target.with_ports(ports.get(&destination));
+
+        // Symlinks which are missing from the new location are recreated, and templates
+        // are rendered again so that they reflect the worktree's new branch and path.
+        if let Some(repo_config) = crate::engine::RepoConfig::for_repo(&repo)?
+            && repo_config.worktree().is_some()
+            && crate::commands::trust::ensure_trusted(core, &repo, &repo_config).await?
+        {
+            self.apply_worktree_symlinks(core, &repo, &target, &repo_config)?;
+            self.apply_worktree_templates(core, &repo, &target, &repo_config)?;
+        }
+
+        writeln!(
+            core.output(),
+            "Moved the worktree for '{old}' to '{}' on the branch '{new}'.",
+            destination.display()
+        )
+        .to_human_error()?;
+
+        Ok(0)
+    }
+
+    /// Lists the linked worktrees (excluding the primary checkout) of each repository.
+    async fn get_linked_worktrees(&self, repos: Vec<Repo>) -> Vec<(Repo, git::Worktree)> {
+        let results: Vec<_> = futures::stream::iter(repos.into_iter().filter(|repo| repo.valid()))
+            .map(|repo| async move {
+                let worktrees = git::git_worktree_list(&repo.get_path()).await;
+                (repo, worktrees)
+            })
+            .buffered(WORKTREE_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut linked = Vec::new();
+        for (repo, worktrees) in results {
+            match worktrees {
+                Ok(worktrees) => linked.extend(
+                    worktrees
+                        .into_iter()
+                        .skip(1)
+                        .map(|worktree| (repo.clone(), worktree)),
+                ),
+                Err(err) => warn!("Could not list the worktrees for {}: {}", repo, err),
+            }
+        }
+
+        linked
+    }
+
+    async fn get_worktree_details(&self, repo: &Repo, worktree: &git::Worktree) -> WorktreeDetails {
+        let label = match (&worktree.branch, &worktree.head) {
+            (Some(branch), _) => branch.clone(),
+            (None, Some(head)) => format!("(detached HEAD {})", &head[..head.len().min(8)]),
+            (None, None) => "(detached HEAD)".to_string(),
+        };
+
+        if !worktree.path.exists() {
+            return WorktreeDetails {
+                label,
+                clean: None,
+                ahead_behind: None,
+                last_commit: Some("missing".into()),
+                size: 0,
+            };
+        }
+
+        // Worktree branches don't usually track an upstream, in which case we compare
+        // them with the repository's default branch instead.
+        let ahead_behind = match git::git_worktree_ahead_behind(&worktree.path, "@{upstream}").await
+        {
+            Ok(counts) => Some(counts),
+            Err(_) => match git::git_default_branch(&repo.get_path()).await {
+                Ok(default_branch) => git::git_worktree_ahead_behind(
+                    &worktree.path,
+                    &format!("origin/{default_branch}"),
+                )
+                .await
+                .ok(),
+                Err(_) => None,
+            },
+        };
+
+        let path = worktree.path.clone();
+        let size = tokio::task::spawn_blocking(move || crate::fs::disk_usage(&path))
+            .await
+            .unwrap_or_default();
+
+        WorktreeDetails {
+            label,
+            clean: git::git_worktree_is_clean(&worktree.path).await.ok(),
+            ahead_behind,
+            last_commit: git::git_worktree_last_commit(&worktree.path).await.ok(),
+            size,
+        }
+    }
+
+    async fn run_gc(&self, core: &Core, matches: &ArgMatches) -> Result<i32, human_errors::Error> {
+        let repos: Vec<Repo> = core.resolve_many(())?;
+        let results: Vec<_> = futures::stream::iter(repos.into_iter().filter(|repo| repo.valid()))
+            .map(|repo| async move {
+                let worktrees = git::git_worktree_list(&repo.get_path()).await;
+                (repo, worktrees)
+            })
+            .buffer_unordered(WORKTREE_CONCURRENCY)
+            .collect()
+            .await;
+
+        // Linked worktrees whose directories still exist are in use, while those whose
+        // directories have been deleted are stale registrations which git should forget.
+        let mut registered = HashSet::new();
+        let mut stale: Vec<(Repo, Vec<PathBuf>)> = Vec::new();
+        for (repo, worktrees) in results {
+            let worktrees = match worktrees {
+                Ok(worktrees) => worktrees,
+                Err(err) => {
+                    warn!("Could not list the worktrees for {}: {}", repo, err);
+                    continue;
+                }
+            };
+
+            let mut missing = Vec::new();
+            for worktree in worktrees.into_iter().skip(1) {
+                if worktree.path.exists() {
+                    registered
+                        .insert(std::fs::canonicalize(&worktree.path).unwrap_or(worktree.path));
+                } else {
+                    missing.push(worktree.path);
+                }
+            }
+
+            if !missing.is_empty() {
+                stale.push((repo, missing));
+            }
+        }
+
+        let worktree_directory = core.config().get_worktree_directory();
+        let orphans: Vec<PathBuf> = match std::fs::read_dir(&worktree_directory) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                // Only directories named like the worktrees we create are candidates, so
+                // that anything else you keep in the worktree directory is left alone.
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(Worktree::is_dir_name)
+                })
+                .filter(|path| {
+                    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+                    !registered.contains(&path) && !Self::is_live_worktree(&path)
+                })
+                .sorted()
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        if orphans.is_empty() && stale.is_empty() {
+            writeln!(core.output(), "No orphaned or stale worktrees were found.")
+                .to_human_error()?;
+            return Ok(0);
+        }
+
+        if !orphans.is_empty() {
+            writeln!(
+                core.output(),
+                "The following worktree directories no longer belong to a repository and will be removed:"
+            )
+            .to_human_error()?;
+            for path in orphans.iter() {
+                writeln!(core.output(), "  {}", path.display()).to_human_error()?;
+            }
+            writeln!(core.output()).to_human_error()?;
+        }
+
+        if !stale.is_empty() {
+            writeln!(
+                core.output(),
+                "The following worktrees no longer exist and will be unregistered:"
+            )
+            .to_human_error()?;
+            for (repo, paths) in stale.iter() {
+                for path in paths {
+                    writeln!(core.output(), "  {} ({})", path.display(), repo).to_human_error()?;
+                }
+            }
+            writeln!(core.output()).to_human_error()?;
+        }
+
+        if !matches.get_flag("yes") {
+            let confirmed = core
+                .prompter()
+                .prompt_bool(
+                    "Are you sure you want to remove these worktrees? Any files in the orphaned directories will be lost. [y/N]: ",
+                    Some(false),
+                )?
+                .unwrap_or_default();
+
+            if !confirmed {
+                writeln!(core.output(), "Okay, we'll keep them as-is.").to_human_error()?;
+                return Ok(0);
+            }
+        }
+
+        let ports = PortRegistry::for_config(core.config());
+        for path in orphans {
+            if core.dry_run() {
+                writeln!(core.output(), "[dry-run] Remove '{}'", path.display())
+                    .to_human_error()?;
+                continue;
+            }
+
+            std::fs::remove_dir_all(&path).wrap_user_err(
+                format!(
+                    "Could not remove the orphaned worktree directory '{}' due to an OS-level error.",
+                    path.display()
+                ),
+                &["Make sure that no applications are using files in this directory and then try again."],
+            )?;
+            ports.release(&path)?;
+        }
+
+        for (repo, paths) in stale {
+            if core.dry_run() {
+                writeln!(
+                    core.output(),
+                    "[dry-run] Run `git worktree prune` in {repo}"
+                )
+                .to_human_error()?;
+                continue;
+            }
+
+            git::git_worktree_prune(&repo.get_path()).await?;
+            for path in paths {
+                ports.release(&path)?;
+            }
+        }
+
+        Ok(0)
+    }
+
+    /// Allocates the ports declared in the repository's worktree configuration to the
+    /// worktree, reusing any ports it was previously given.
+    fn allocate_worktree_ports(
+        &self,
+        core: &Core,
+        worktree: Worktree,
+        config: &crate::engine::RepoConfig,
+    ) -> Result<Worktree, human_errors::Error> {
+        let declared = match config.worktree() {
+            Some(worktree_config) if !worktree_config.ports().is_empty() => worktree_config.ports(),
+            _ => return Ok(worktree),
+        };
+
+        let registry = PortRegistry::for_config(core.config());
+        if core.dry_run() {
+            for (name, base) in declared {
+                writeln!(
+                    core.output(),
+                    "[dry-run] Allocate a port above {base} for '{name}'"
+                )
+                .to_human_error()?;
+            }
+
+            let ports = registry.get(&worktree.path());
+            return Ok(worktree.with_ports(ports));
+        }
+
+        let ports = registry.allocate(&worktree.path(), declared)?;
+        Ok(worktree.with_ports(ports))
+    }
+
+    /// Determines whether a directory is a worktree which is still registered with a
+    /// repository, by checking that the git directory its `.git` file points to exists.
+    /// Directories containing a full repository are never treated as orphans.
+    fn is_live_worktree(path: &Path) -> bool {
+        let dot_git = path.join(".git");
+        if dot_git.is_dir() {
+            return true;
+        }
+
+        match std::fs::read_to_string(&dot_git) {
+            Ok(content) => match content.trim().strip_prefix("gitdir:") {
+                Some(gitdir) => path.join(gitdir.trim()).exists(),
+                None => false,
+            },
+            Err(_) => false,
+        }
+    }
+
     /// Applies the worktree automation defined in a repository's 'git-tool.yml'
     /// configuration: it creates the requested symlinks from the worktree back to
     /// the original repository and then runs the configured setup tasks within the
@@ -186,33 +754,48 @@ impl WorktreeCommand {
             None => return Ok(()),
         };
 
-        for entry in worktree.symlinks() {
+        self.apply_worktree_symlinks(core, repo, worktree_target, config)?;
+
+        for entry in worktree.copies() {
             let original = repo.get_path().join(entry);
-            let link = worktree_target.get_path().join(entry);
+            let destination = worktree_target.get_path().join(entry);
 
-            if link.exists() {
+            if destination.exists() {
                 continue;
             }
 
             if !original.exists() {
                 writeln!(
                     core.output(),
-                    "Warning: skipping worktree symlink '{entry}' because it does not exist in the source repository."
+                    "Warning: skipping worktree copy '{entry}' because it does not exist in the source repository."
                 )
                 .to_human_error()?;
                 continue;
             }
 
-            if let Err(err) = crate::fs::create_link(&original, &link) {
+            if core.dry_run() {
                 writeln!(
                     core.output(),
-                    "Warning: could not create worktree symlink '{entry}': {}",
+                    "[dry-run] Copy '{}' to '{}'",
+                    original.display(),
+                    destination.display()
+                )
+                .to_human_error()?;
+                continue;
+            }
+
+            if let Err(err) = crate::fs::copy_path(&original, &destination) {
+                writeln!(
+                    core.output(),
+                    "Warning: could not copy '{entry}' into the worktree: {}",
                     err.message()
                 )
                 .to_human_error()?;
             }
         }
 
+        self.apply_worktree_templates(core, repo, worktree_target, config)?;
+
         for task_name in worktree.tasks() {
             match config.get_task(task_name) {
                 Some(task) => {
@@ -236,7 +819,164 @@ impl WorktreeCommand {
             }
         }
 
-        Ok(())
+        Ok(())
+    }
+
+    /// Creates any missing symlinks from the worktree back to the original repository.
+    fn apply_worktree_symlinks(
+        &self,
+        core: &Core,
+        repo: &Repo,
+        worktree_target: &Worktree,
+        config: &crate::engine::RepoConfig,
+    ) -> Result<(), human_errors::Error> {
+        let worktree = match config.worktree() {
+            Some(worktree) => worktree,
+            None => return Ok(()),
+        };
+
+        for entry in worktree.symlinks() {
+            let original = repo.get_path().join(entry);
+            let link = worktree_target.get_path().join(entry);
+
+            if link.exists() {
+                continue;
+            }
+
+            if !original.exists() {
+                writeln!(
+                    core.output(),
+                    "Warning: skipping worktree symlink '{entry}' because it does not exist in the source repository."
+                )
+                .to_human_error()?;
+                continue;
+            }
+
+            if core.dry_run() {
+                writeln!(
+                    core.output(),
+                    "[dry-run] Link '{}' to '{}'",
+                    link.display(),
+                    original.display()
+                )
+                .to_human_error()?;
+                continue;
+            }
+
+            if let Err(err) = crate::fs::create_link(&original, &link) {
+                writeln!(
+                    core.output(),
+                    "Warning: could not create worktree symlink '{entry}': {}",
+                    err.message()
+                )
+                .to_human_error()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the configured templates from the original repository into the worktree.
+    fn apply_worktree_templates(
+        &self,
+        core: &Core,
+        repo: &Repo,
+        worktree_target: &Worktree,
+        config: &crate::engine::RepoConfig,
+    ) -> Result<(), human_errors::Error> {
+        let worktree = match config.worktree() {
+            Some(worktree) => worktree,
+            None => return Ok(()),
+        };
+
+        for entry in worktree.templates() {
+            let original = repo.get_path().join(entry);
+            let destination = worktree_target.get_path().join(entry);
+
+            if core.dry_run() {
+                writeln!(
+                    core.output(),
+                    "[dry-run] Render '{}' to '{}'",
+                    original.display(),
+                    destination.display()
+                )
+                .to_human_error()?;
+                continue;
+            }
+
+            if let Err(err) =
+                self.render_worktree_template(core, &original, &destination, worktree_target)
+            {
+                writeln!(
+                    core.output(),
+                    "Warning: could not render worktree template '{entry}': {}",
+                    err.message()
+                )
+                .to_human_error()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renders the template at `original` with the worktree's template context and
+    /// writes the result to `destination`, replacing any existing file there so that
+    /// the rendered values always reflect the worktree's current details.
+    fn render_worktree_template(
+        &self,
+        core: &Core,
+        original: &Path,
+        destination: &Path,
+        worktree_target: &Worktree,
+    ) -> Result<(), human_errors::Error> {
+        let template = std::fs::read_to_string(original).wrap_user_err(
+            format!(
+                "Could not read the template '{}' from the source repository.",
+                original.display()
+            ),
+            &["Make sure that the template exists and is a UTF-8 encoded text file."],
+        )?;
+
+        let rendered =
+            crate::engine::render(&template, worktree_target.template_context(core.config())?)?;
+
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent).to_human_error()?;
+        }
+
+        std::fs::write(destination, rendered).wrap_user_err(
+            format!(
+                "Could not write the rendered template to '{}'.",
+                destination.display()
+            ),
+            &["Check that Git-Tool has permission to write to the worktree directory."],
+        )
+    }
+}
+
+struct WorktreeDetails {
+    label: String,
+    clean: Option<bool>,
+    ahead_behind: Option<(usize, usize)>,
+    last_commit: Option<String>,
+    size: u64,
+}
+
+/// Formats a number of bytes using the largest unit which keeps it above 1.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
     }
 }
 
@@ -259,9 +999,11 @@ mod tests {
         );
         std::fs::create_dir_all(repo.get_path().join("target")).unwrap();
         std::fs::write(repo.get_path().join("target").join("marker.txt"), "built").unwrap();
+        std::fs::write(repo.get_path().join(".env.local"), "SECRET=1").unwrap();
+        std::fs::write(repo.get_path().join("branch.txt"), "{{ .Worktree.Branch }}").unwrap();
         std::fs::write(
             repo.get_path().join("git-tool.yml"),
-            "worktree:\n  symlinks:\n    - target\n  tasks:\n    - setup\ntasks:\n  setup:\n    command: echo\n    args:\n      - setup\n",
+            "worktree:\n  symlinks:\n    - target\n  copies:\n    - .env.local\n  templates:\n    - branch.txt\n  tasks:\n    - setup\ntasks:\n  setup:\n    command: echo\n    args:\n      - setup\n",
         )
         .unwrap();
 
@@ -293,6 +1035,19 @@ mod tests {
             std::fs::read_to_string(link.join("marker.txt")).unwrap(),
             "built"
         );
+
+        let copy = worktree_target.path().join(".env.local");
+        assert!(
+            !copy.is_symlink(),
+            "copies should not be linked to the original"
+        );
+        assert_eq!(std::fs::read_to_string(copy).unwrap(), "SECRET=1");
+
+        assert_eq!(
+            std::fs::read_to_string(worktree_target.path().join("branch.txt")).unwrap(),
+            "feature/automation",
+            "templates should be rendered with the worktree's details"
+        );
     }
 
     /// Initializes a real git repository (with an initial commit) and configures
@@ -308,13 +1063,21 @@ mod tests {
 
         let repo_path = repo.get_path();
         let core = core.with_mock_resolver(move |mock| {
-            let repo_path = repo_path.clone();
+            let current_path = repo_path.clone();
             mock.expect_get_current_repo().returning(move || {
                 Ok(Repo::new(
                     "gh:sierrasoftworks/test-worktree-command",
-                    repo_path.clone(),
+                    current_path.clone(),
                 ))
             });
+
+            let repo_path = repo_path.clone();
+            mock.expect_get_repos().returning(move || {
+                Ok(vec![Repo::new(
+                    "gh:sierrasoftworks/test-worktree-command",
+                    repo_path.clone(),
+                )])
+            });
         });
 
         (core, repo)
@@ -361,7 +1124,7 @@ mod tests {
             })
             .build();
 
-        sequence!(GitInit {}, GitCheckout { branch: "main" })
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -381,6 +1144,58 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_in_repo_names_new_branch_from_template() {
+        let cmd = WorktreeCommand {};
+        let temp = tempdir().unwrap();
+
+        let cfg = Config::from_str(&format!(
+            "
+directory: {}
+branch_template: \"feat/{{{{ .Ticket }}}}-{{{{ .Slug }}}}\"
+
+apps:
+  - name: shell
+    command: bash
+",
+            temp.path().display()
+        ))
+        .unwrap();
+        let core = Core::builder().with_config(cfg);
+        let (core, repo) = setup_current_repo(core, &temp).await;
+
+        let expected_path = temp.path().join("worktrees").join(Worktree::dir_name(
+            &repo,
+            &"feat/PROJ-7-add-search".parse().unwrap(),
+        ));
+        let expected_for_assert = expected_path.clone();
+
+        let core = core
+            .with_mock_launcher(move |mock| {
+                let expected_path = expected_path.clone();
+                mock.expect_run()
+                    .withf(move |_, target| target.get_path() == expected_path)
+                    .returning(|_, _| Box::pin(async { Ok(0) }));
+            })
+            .build();
+
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
+        commit_initial(&repo).await;
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["worktree", "PROJ-7 Add search"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert_eq!(
+            git::git_current_branch(&expected_for_assert).await.unwrap(),
+            "feat/PROJ-7-add-search"
+        );
+    }
+
     #[tokio::test]
     async fn run_in_repo_applies_env_override() {
         let cmd = WorktreeCommand {};
@@ -410,7 +1225,7 @@ mod tests {
             })
             .build();
 
-        sequence!(GitInit {}, GitCheckout { branch: "main" })
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -453,7 +1268,7 @@ mod tests {
             })
             .build();
 
-        sequence!(GitInit {}, GitCheckout { branch: "main" })
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -471,6 +1286,176 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_in_repo_allocates_and_releases_ports() {
+        let cmd = WorktreeCommand {};
+        let temp = tempdir().unwrap();
+
+        let cfg = Config::for_dev_directory(temp.path());
+        let registry = PortRegistry::for_config(&cfg);
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(crate::console::mock_with_input("once\n"));
+        let (core, repo) = setup_current_repo(core, &temp).await;
+
+        let expected_path = temp
+            .path()
+            .join("worktrees")
+            .join(Worktree::dir_name(&repo, &"feature/ports".parse().unwrap()));
+
+        let core = core
+            .with_mock_launcher(move |mock| {
+                mock.expect_run()
+                    .withf(|_, target| {
+                        target
+                            .environment()
+                            .contains(&("WEB_PORT".to_string(), "3001".to_string()))
+                    })
+                    .times(1)
+                    .returning(|_, _| Box::pin(async { Ok(0) }));
+            })
+            .build();
+
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
+        commit_initial(&repo).await;
+        std::fs::write(
+            repo.get_path().join("git-tool.yml"),
+            "worktree:\n  ports:\n    web: 3000\n",
+        )
+        .unwrap();
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["worktree", "feature/ports", "--rm"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert!(
+            !expected_path.exists(),
+            "the worktree should have been removed after the application exited"
+        );
+        assert!(
+            registry.get(&expected_path).is_empty(),
+            "the worktree's ports should have been released"
+        );
+    }
+
+    #[tokio::test]
+    async fn mv_renames_branch_and_moves_worktree() {
+        let cmd = WorktreeCommand {};
+        let temp = tempdir().unwrap();
+
+        let cfg = Config::for_dev_directory(temp.path());
+        let registry = PortRegistry::for_config(&cfg);
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(crate::console::mock_with_input("once\n"));
+        let (core, repo) = setup_current_repo(core, &temp).await;
+        let core = core.build();
+
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
+        commit_initial(&repo).await;
+        std::fs::write(repo.get_path().join("branch.txt"), "{{ .Worktree.Branch }}").unwrap();
+        std::fs::write(
+            repo.get_path().join("git-tool.yml"),
+            "worktree:\n  templates:\n    - branch.txt\n",
+        )
+        .unwrap();
+
+        let old_path: PathBuf =
+            Worktree::new(&repo, &"feature/old".parse().unwrap(), core.config()).path();
+        let new_path: PathBuf =
+            Worktree::new(&repo, &"feature/new".parse().unwrap(), core.config()).path();
+
+        git::git_worktree_add(&repo.get_path(), &old_path, "feature/old", true, None)
+            .await
+            .unwrap();
+        let ports = registry
+            .allocate(
+                &old_path,
+                &[("web".to_string(), 3000)].into_iter().collect(),
+            )
+            .unwrap();
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["worktree", "mv", "feature/old", "feature/new"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        assert!(
+            !old_path.exists(),
+            "the old worktree should have been moved"
+        );
+        assert_eq!(
+            git::git_current_branch(&new_path).await.unwrap(),
+            "feature/new"
+        );
+        assert_eq!(
+            std::fs::read_to_string(new_path.join("branch.txt")).unwrap(),
+            "feature/new",
+            "templates should be rendered for the new branch"
+        );
+        assert_eq!(
+            registry.get(&new_path),
+            ports,
+            "allocated ports should move with the worktree"
+        );
+    }
+
+    #[tokio::test]
+    async fn mv_restores_branch_when_move_fails() {
+        let cmd = WorktreeCommand {};
+        let temp = tempdir().unwrap();
+
+        let cfg = Config::for_dev_directory(temp.path());
+        let core = Core::builder().with_config(cfg);
+        let (core, repo) = setup_current_repo(core, &temp).await;
+        let core = core.build();
+
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
+        commit_initial(&repo).await;
+
+        let old_path: PathBuf =
+            Worktree::new(&repo, &"feature/old".parse().unwrap(), core.config()).path();
+        git::git_worktree_add(&repo.get_path(), &old_path, "feature/old", true, None)
+            .await
+            .unwrap();
+
+        // Git refuses to move a locked worktree, which lets us fail the move after
+        // the branch has already been renamed.
+        git::git_cmd(
+            tokio::process::Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("worktree")
+                .arg("lock")
+                .arg(&old_path),
+        )
+        .await
+        .unwrap();
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["worktree", "mv", "feature/old", "feature/new"]);
+        cmd.run(&core, &args)
+            .await
+            .expect_err("a locked worktree should not be moved");
+
+        assert!(old_path.exists(), "the worktree should not have moved");
+        assert_eq!(
+            git::git_current_branch(&old_path).await.unwrap(),
+            "feature/old",
+            "the branch should have been renamed back"
+        );
+    }
+
     #[tokio::test]
     async fn run_with_rm_cleans_up_after_launcher_failure() {
         let cmd = WorktreeCommand {};
@@ -499,7 +1484,7 @@ mod tests {
             })
             .build();
 
-        sequence!(GitInit {}, GitCheckout { branch: "main" })
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -546,7 +1531,7 @@ mod tests {
             })
             .build();
 
-        sequence!(GitInit {}, GitCheckout { branch: "main" })
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -584,7 +1569,7 @@ mod tests {
             })
             .build();
 
-        sequence!(GitInit {}, GitCheckout { branch: "main" })
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -623,7 +1608,7 @@ mod tests {
         let (core, repo) = setup_current_repo(core, &temp).await;
         let core = core.build();
 
-        sequence!(GitInit {}, GitCheckout { branch: "main" })
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -637,4 +1622,175 @@ mod tests {
             "the primary worktree should be labelled in the listing"
         );
     }
+
+    #[tokio::test]
+    async fn gc_removes_orphans_and_stale_registrations() {
+        let cmd = WorktreeCommand {};
+        let temp = tempdir().unwrap();
+        let console = crate::console::mock_with_input("y\n");
+
+        let cfg = Config::for_dev_directory(temp.path());
+        let worktree_directory = cfg.get_worktree_directory();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(console.clone());
+        let (core, repo) = setup_current_repo(core, &temp).await;
+        let core = core.build();
+
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
+        commit_initial(&repo).await;
+
+        let live = worktree_directory.join("live");
+        git::git_worktree_add(&repo.get_path(), &live, "feature/live", true, None)
+            .await
+            .unwrap();
+
+        let stale = worktree_directory.join("stale");
+        git::git_worktree_add(&repo.get_path(), &stale, "feature/stale", true, None)
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&stale).unwrap();
+
+        let orphan = worktree_directory.join("orphan-feature-12345678");
+        std::fs::create_dir_all(&orphan).unwrap();
+        std::fs::write(orphan.join(".git"), "gitdir: /does/not/exist").unwrap();
+
+        let unrelated = worktree_directory.join("notes");
+        std::fs::create_dir_all(&unrelated).unwrap();
+        std::fs::write(unrelated.join("todo.md"), "keep me").unwrap();
+
+        let args = cmd.app().get_matches_from(vec!["worktree", "gc"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output.contains("orphan-feature-12345678"),
+            "the orphaned directory should be reported: {output}"
+        );
+        assert!(
+            output.contains("stale"),
+            "the stale registration should be reported: {output}"
+        );
+        assert!(!orphan.exists(), "the orphaned directory should be removed");
+        assert!(live.exists(), "worktrees which are in use must be kept");
+        assert!(
+            unrelated.join("todo.md").exists(),
+            "directories which aren't named like worktrees must be kept"
+        );
+        assert!(
+            !output.contains("notes"),
+            "unrelated directories should not be reported: {output}"
+        );
+
+        let worktrees = git::git_worktree_list(&repo.get_path()).await.unwrap();
+        assert!(
+            !worktrees
+                .iter()
+                .any(|w| w.branch.as_deref() == Some("feature/stale")),
+            "the stale worktree should have been pruned"
+        );
+        assert!(
+            worktrees
+                .iter()
+                .any(|w| w.branch.as_deref() == Some("feature/live"))
+        );
+    }
+
+    #[tokio::test]
+    async fn list_all_reports_worktree_status() {
+        let cmd = WorktreeCommand {};
+        let temp = tempdir().unwrap();
+        let console = crate::console::mock();
+
+        let cfg = Config::for_dev_directory(temp.path());
+        let worktree_directory = cfg.get_worktree_directory();
+        let core = Core::builder()
+            .with_config(cfg)
+            .with_console(console.clone());
+        let (core, repo) = setup_current_repo(core, &temp).await;
+        let core = core.build();
+
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
+        commit_initial(&repo).await;
+
+        git::git_worktree_add(
+            &repo.get_path(),
+            &worktree_directory.join("listed"),
+            "feature/listed",
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["worktree", "list", "--all"]);
+        cmd.assert_run_successful(&core, &args).await;
+
+        let output = console.to_string();
+        assert!(
+            output.contains("gh:sierrasoftworks/test-worktree-command"),
+            "the repository should be listed: {output}"
+        );
+        assert!(
+            output.contains("feature/listed"),
+            "the worktree's branch should be listed: {output}"
+        );
+        assert!(
+            output.contains("clean"),
+            "the worktree's status should be listed: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn open_launches_matching_worktree() {
+        let cmd = WorktreeCommand {};
+        let temp = tempdir().unwrap();
+
+        let cfg = Config::for_dev_directory(temp.path());
+        let worktree_path = cfg.get_worktree_directory().join("opened");
+        let core = Core::builder().with_config(cfg);
+        let (core, repo) = setup_current_repo(core, &temp).await;
+
+        let expected_path = worktree_path.clone();
+        let core = core
+            .with_mock_launcher(move |mock| {
+                let expected_path = expected_path.clone();
+                mock.expect_run()
+                    .withf(move |app, target| {
+                        app.get_name() == "shell" && target.get_path() == expected_path
+                    })
+                    .times(1)
+                    .returning(|_, _| Box::pin(async { Ok(0) }));
+            })
+            .build();
+
+        sequence!(GitInit::default(), GitCheckout { branch: "main" })
+            .apply_repo(&core, &repo)
+            .await
+            .unwrap();
+        commit_initial(&repo).await;
+
+        git::git_worktree_add(
+            &repo.get_path(),
+            &worktree_path,
+            "feature/opened",
+            true,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let args = cmd
+            .app()
+            .get_matches_from(vec!["worktree", "open", "worktree-command#opened"]);
+        cmd.assert_run_successful(&core, &args).await;
+    }
 }
@@ -1,8 +1,9 @@
 use std::fmt::Display;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct App {
     name: String,
     command: String,
@@ -39,6 +40,12 @@ impl App {
         self.environment.clone()
     }
 
+    /// Determines whether this app's command can be found on your `PATH`. Commands which
+    /// are rendered from a template can't be checked and are assumed to be available.
+    pub fn is_available(&self) -> bool {
+        self.command.contains("{{") || crate::fs::find_executable(&self.command).is_some()
+    }
+
     /// Returns a copy of this application with the provided literal environment
     /// overrides attached. The overrides are applied verbatim at launch time and
     /// take precedence over any configured `environment` entries.
@@ -152,4 +159,22 @@ mod tests {
         assert_eq!(app.get_args(), vec!["-c", "echo $TEST"]);
         assert_eq!(app.get_environment(), vec!["TEST=test"]);
     }
+
+    #[test]
+    fn is_available() {
+        let app: App = App::builder().with_name("git").with_command("git").into();
+        assert!(app.is_available());
+
+        let app: App = App::builder()
+            .with_name("missing")
+            .with_command("git-tool-definitely-not-a-real-command")
+            .into();
+        assert!(!app.is_available());
+
+        let app: App = App::builder()
+            .with_name("templated")
+            .with_command("{{ .Target.Path }}/run")
+            .into();
+        assert!(app.is_available());
+    }
 }
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+use super::templates::{branch_context, render};
+
 /// A git branch name.
 ///
 /// Wrapping the raw branch name in a dedicated type lets us attach the behaviour
@@ -14,6 +16,13 @@ impl Branch {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Names a new branch by rendering a branch naming `template` with the
+    /// details parsed from a short `description` of the work (such as
+    /// `PROJ-123 Fix the login page`).
+    pub fn from_template(template: &str, description: &str) -> Result<Self, human_errors::Error> {
+        render(template, branch_context(description)?)?.parse()
+    }
 }
 
 impl FromStr for Branch {
@@ -49,6 +58,13 @@ mod tests {
         assert_eq!(branch.to_string(), "feature/test");
     }
 
+    #[test]
+    fn names_a_branch_from_a_template() {
+        let branch =
+            Branch::from_template("feat/{{ .Ticket }}-{{ .Slug }}", "PROJ-123 Fix login").unwrap();
+        assert_eq!(branch.as_str(), "feat/PROJ-123-fix-login");
+    }
+
     #[test]
     fn trims_and_rejects_empty() {
         assert_eq!("  main  ".parse::<Branch>().unwrap().as_str(), "main");
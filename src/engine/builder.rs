@@ -29,6 +29,7 @@ impl CoreBuilderWithoutConfig {
             http_client: http::client(),
             console: console::default(),
             analytics: Analytics::disabled(),
+            dry_run: false,
             config,
         }
     }
@@ -65,6 +66,7 @@ pub struct CoreBuilderWithConfig {
     pub(super) keychain: Arc<dyn KeyChain + Send + Sync>,
     pub(super) http_client: Arc<dyn HttpClient + Send + Sync>,
     pub(super) analytics: Analytics,
+    pub(super) dry_run: bool,
 }
 
 impl From<CoreBuilderWithConfig> for Core {
@@ -79,11 +81,18 @@ impl CoreBuilderWithConfig {
         // The launcher and HTTP client are wrapped here (rather than where they are
         // constructed) so that every implementation — including the mocks used in
         // tests — reports its telemetry events consistently, regardless of the order
-        // in which the builder methods were called.
+        // in which the builder methods were called. In dry-run mode the configured
+        // launcher is replaced entirely, so that nothing is ever spawned.
+        let launcher = if self.dry_run {
+            launcher::dry_run_launcher(self.config.clone(), self.console.clone())
+        } else {
+            self.launcher
+        };
+
         Core {
             config: self.config,
             launcher: Arc::new(launcher::InstrumentedLauncher {
-                inner: self.launcher,
+                inner: launcher,
                 analytics: self.analytics.clone(),
             }),
             resolver: self.resolver,
@@ -94,6 +103,37 @@ impl CoreBuilderWithConfig {
             }),
             console: self.console,
             analytics: self.analytics,
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Selects the configuration profile which should be used, falling back to the
+    /// profile matching the current directory or your default profile when `profile`
+    /// is not provided. This replaces the launcher and resolver, so it should be
+    /// called before customizing either of them.
+    pub fn with_profile(self, profile: Option<&str>) -> Result<Self, human_errors::Error> {
+        let cwd = std::env::current_dir().ok();
+        let config = self.config.select_profile(profile, cwd.as_deref())?;
+
+        Ok(self.with_updated_config(config))
+    }
+
+    /// Applies any `GITTOOL_*` environment variable overrides to the config. This
+    /// replaces the launcher and resolver, so it should be called before customizing
+    /// either of them.
+    pub fn with_environment_overrides(self) -> Self {
+        let config = self.config.with_environment(std::env::vars());
+        self.with_updated_config(config)
+    }
+
+    fn with_updated_config(self, config: Config) -> Self {
+        let config = Arc::new(config);
+
+        Self {
+            launcher: launcher::launcher(config.clone()),
+            resolver: ResolverBackend::True(TrueResolver::new(config.clone())),
+            config,
+            ..self
         }
     }
 
@@ -101,6 +141,10 @@ impl CoreBuilderWithConfig {
         Self { analytics, ..self }
     }
 
+    pub fn with_dry_run(self, dry_run: bool) -> Self {
+        Self { dry_run, ..self }
+    }
+
     pub fn with_console(self, console: Arc<dyn ConsoleProvider + Send + Sync>) -> Self {
         Self { console, ..self }
     }
@@ -154,4 +198,13 @@ impl CoreBuilderWithConfig {
     pub fn with_mock_http_client(self, routes: Vec<MockHttpRoute>) -> Self {
         self.with_http_client(http::mock(routes))
     }
+
+    /// Caches the registries which are downloaded from remote sources in `path`,
+    /// rather than your OS' cache directory. This replaces the launcher and resolver,
+    /// so it should be called before customizing either of them.
+    #[cfg(test)]
+    pub fn with_registry_cache_directory<P: Into<PathBuf>>(self, path: P) -> Self {
+        let config = self.config.with_registry_cache_directory(path);
+        self.with_updated_config(config)
+    }
 }
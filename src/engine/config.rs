@@ -1,17 +1,31 @@
 use human_errors::ResultExt;
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::env::consts::OS;
 use std::path::PathBuf;
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 use std::{path, sync::Arc};
 use tracing_batteries::prelude::*;
 
 use super::app;
 use super::features;
+use super::migration;
+use super::profile::Profile;
+use super::registry_source::RegistrySource;
 use super::service;
 use crate::online::registry::EntryConfig;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// The environment variables which may be used to override individual settings
+/// from your config file.
+const ENV_DIRECTORY: &str = "GITTOOL_DIRECTORY";
+const ENV_SCRATCHPADS: &str = "GITTOOL_SCRATCHPADS";
+const ENV_WORKTREES: &str = "GITTOOL_WORKTREES";
+const ENV_FEATURE_PREFIX: &str = "GITTOOL_FEATURE_";
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Config {
     #[serde(skip)]
     config_file: Option<PathBuf>,
@@ -19,11 +33,16 @@ pub struct Config {
     #[serde(rename = "$schema")]
     schema: Option<String>,
 
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    include: Vec<String>,
+
     #[serde(
+        default,
         rename = "directory",
         deserialize_with = "deserialize_expanded_path",
         serialize_with = "serialize_expanded_path"
     )]
+    #[schemars(with = "PathBuf")]
     dev_directory: PathBuf,
     #[serde(default, rename = "scratchpads")]
     scratch_directory: Option<PathBuf>,
@@ -33,6 +52,7 @@ pub struct Config {
         deserialize_with = "deserialize_optional_expanded_path",
         serialize_with = "serialize_optional_expanded_path"
     )]
+    #[schemars(with = "Option<PathBuf>")]
     worktree_directory: Option<PathBuf>,
 
     #[serde(default)]
@@ -42,11 +62,106 @@ pub struct Config {
     #[serde(default)]
     aliases: HashMap<String, String>,
 
+    /// The registries which are searched for templates, in priority order, before
+    /// the built-in Git-Tool registry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    registries: Vec<RegistrySource>,
+
+    /// The directory in which registries downloaded from remote sources are cached,
+    /// defaulting to your OS' cache directory.
+    #[serde(skip)]
+    registry_cache_directory: Option<PathBuf>,
+
     #[serde(default)]
     trusted_repos: HashMap<String, String>,
 
-    #[serde(default)]
+    /// The template used to name new branches, like `{{ .User }}/{{ .Slug }}`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    branch_template: Option<String>,
+
+    /// The registry templates which the apps and services in your config file were
+    /// installed from, keyed by `apps/{name}` or `services/{name}`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    templates: HashMap<String, InstalledTemplate>,
+
+    /// The flags set in this file. A file without a `features` section sets none, so
+    /// the built-in defaults are only applied by [`Config::default`] and files which
+    /// extend one another don't reset each other's flags.
+    #[serde(default = "features::Features::none")]
     features: features::Features,
+
+    #[serde(default, rename = "profile", skip_serializing_if = "Option::is_none")]
+    default_profile: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    profiles: HashMap<String, Arc<Profile>>,
+
+    /// The profile which has been selected for this invocation.
+    #[serde(skip)]
+    active_profile: Option<(String, Arc<Profile>)>,
+
+    /// The overrides provided through `GITTOOL_*` environment variables, which take
+    /// precedence over both the config file and the active profile.
+    #[serde(skip)]
+    environment: Option<Arc<Profile>>,
+
+    /// The feature flags which result from layering the active profile and the
+    /// environment overrides on top of the config file's own flags.
+    #[serde(skip)]
+    effective_features: Option<features::Features>,
+
+    /// The merged contents of the files included by the config file, which are
+    /// left out when the config file is saved.
+    #[serde(skip)]
+    included: Option<Arc<Config>>,
+
+    /// The raw contents of each file which contributed to this config, in the
+    /// order they were merged, used to report where each value came from.
+    #[serde(skip)]
+    layers: Vec<(PathBuf, Arc<serde_yaml::Value>)>,
+}
+
+/// A single value from your effective configuration, along with the source it
+/// was taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigValue {
+    pub key: String,
+    pub value: String,
+    pub source: String,
+}
+
+impl std::fmt::Display for ConfigValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} = {} ({})", self.key, self.value, self.source)
+    }
+}
+
+/// The registry template which an app or service in your config file was installed
+/// from, used to find templates which have been updated since they were installed.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct InstalledTemplate {
+    pub id: String,
+    pub version: String,
+    /// The values which were provided for the template's variables when it was installed.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    /// A hash of the app or service as it was installed, used to detect changes you
+    /// have made to it since then.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub checksum: String,
+}
+
+impl InstalledTemplate {
+    /// Determines whether `latest` is a newer version of this template, comparing
+    /// them as semantic versions where possible.
+    pub fn is_outdated(&self, latest: &str) -> bool {
+        match (
+            semver::Version::parse(&self.version),
+            semver::Version::parse(latest),
+        ) {
+            (Ok(installed), Ok(latest)) => latest > installed,
+            _ => self.version != latest,
+        }
+    }
 }
 
 impl Config {
@@ -87,12 +202,157 @@ impl Config {
         into
     }
 
+    #[cfg(test)]
+    pub fn with_registry_cache_directory<P: Into<PathBuf>>(&self, cache_dir: P) -> Self {
+        let mut into = self.clone();
+        into.registry_cache_directory = Some(cache_dir.into());
+        into
+    }
+
     pub fn with_feature_flag(&self, flag: &str, enabled: bool) -> Self {
         let mut into = self.clone();
         into.features = self.features.to_builder().with(flag, enabled).build();
+        into.refresh_features();
+        into
+    }
+
+    /// Applies the overrides provided through `GITTOOL_*` environment variables
+    /// (such as `GITTOOL_DIRECTORY` or `GITTOOL_FEATURE_CREATE_REMOTE=false`).
+    pub fn with_environment<I>(&self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut overrides = Profile::default();
+        let mut flags: Option<features::FeaturesBuilder> = None;
+
+        for (key, value) in vars {
+            match key.as_str() {
+                ENV_DIRECTORY => overrides.dev_directory = Some(expand_path(value)),
+                ENV_SCRATCHPADS => overrides.scratch_directory = Some(expand_path(value)),
+                ENV_WORKTREES => overrides.worktree_directory = Some(expand_path(value)),
+                _ => {
+                    if let Some(flag) = key.strip_prefix(ENV_FEATURE_PREFIX) {
+                        match value.as_str() {
+                            "true" | "false" => {
+                                flags = Some(
+                                    flags
+                                        .unwrap_or_else(features::Features::overrides)
+                                        .with(&flag.to_lowercase(), value == "true"),
+                                )
+                            }
+                            _ => warn!(
+                                "Ignoring the {} environment variable because '{}' is not a valid value (expected 'true' or 'false').",
+                                key, value
+                            ),
+                        }
+                    }
+                }
+            }
+        }
+
+        overrides.features = flags.map(|f| f.build());
+
+        let mut into = self.clone();
+        into.environment = Some(Arc::new(overrides));
+        into.refresh_features();
         into
     }
 
+    /// Selects the named profile for this invocation, layering it on top of the
+    /// rest of the configuration.
+    pub fn with_profile(&self, name: &str) -> Result<Self, human_errors::Error> {
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            human_errors::user(
+                format!("Could not find a profile called '{name}' in your config file."),
+                &["Run `gt config profile list` to see the profiles which are available to you."],
+            )
+        })?;
+
+        let mut into = self.clone();
+        into.active_profile = Some((name.to_string(), profile.clone()));
+        into.refresh_features();
+        Ok(into)
+    }
+
+    /// Sets the profile which is used when no other profile has been selected.
+    pub fn with_default_profile(&self, name: &str) -> Result<Self, human_errors::Error> {
+        let mut into = self.with_profile(name)?;
+        into.default_profile = Some(name.to_string());
+        Ok(into)
+    }
+
+    /// Selects the profile which should be used for this invocation. An explicitly
+    /// requested profile takes precedence, followed by the profile whose directory
+    /// contains `cwd` (the most specific one if several do) and finally the default
+    /// profile from your config file.
+    pub fn select_profile(
+        &self,
+        requested: Option<&str>,
+        cwd: Option<&Path>,
+    ) -> Result<Self, human_errors::Error> {
+        if let Some(name) = requested {
+            return self.with_profile(name);
+        }
+
+        let matching_profile = cwd.and_then(|cwd| {
+            self.profiles
+                .iter()
+                .filter(|(_, profile)| profile.contains(cwd))
+                .max_by_key(|(_, profile)| {
+                    profile
+                        .dev_directory
+                        .as_ref()
+                        .map(|d| d.components().count())
+                })
+                .map(|(name, _)| name.as_str())
+        });
+
+        match matching_profile.or(self.default_profile.as_deref()) {
+            Some(name) => self.with_profile(name),
+            None => Ok(self.clone()),
+        }
+    }
+
+    /// Gets the layers which override the config file, starting with the one
+    /// which takes the highest precedence.
+    fn get_overrides(&self) -> impl DoubleEndedIterator<Item = &Profile> {
+        self.environment
+            .iter()
+            .map(|p| p.as_ref())
+            .chain(self.active_profile.iter().map(|(_, p)| p.as_ref()))
+    }
+
+    fn refresh_features(&mut self) {
+        let mut builder: Option<features::FeaturesBuilder> = None;
+        for layer in self.get_overrides().rev() {
+            if let Some(flags) = &layer.features {
+                builder = Some(
+                    builder
+                        .unwrap_or_else(|| {
+                            features::Features::builder().with_features(&self.features)
+                        })
+                        .with_features(flags),
+                );
+            }
+        }
+
+        self.effective_features = builder.map(|b| b.build());
+    }
+
+    pub fn get_active_profile_name(&self) -> Option<&str> {
+        self.active_profile.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    pub fn get_default_profile_name(&self) -> Option<&str> {
+        self.default_profile.as_deref()
+    }
+
+    pub fn get_profile_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.profiles.keys().map(|k| k.as_str()).collect();
+        names.sort();
+        names
+    }
+
     pub fn with_trusted_repo<K: Into<String>, V: Into<String>>(&self, repo: K, hash: V) -> Self {
         let mut into = self.clone();
         into.trusted_repos.insert(repo.into(), hash.into());
@@ -139,8 +399,9 @@ impl Config {
             into.apps.clone_from(&from.apps);
         }
 
-        into.features = features::Features::builder()
-            .with_features(&into.features)
+        into.features = into
+            .features
+            .to_builder()
             .with_features(&from.features)
             .build();
 
@@ -152,6 +413,30 @@ impl Config {
             into.trusted_repos.insert(k.clone(), v.clone());
         }
 
+        if let Some(template) = from.branch_template {
+            into.branch_template = Some(template)
+        }
+
+        for (k, v) in from.templates.iter() {
+            into.templates.insert(k.clone(), v.clone());
+        }
+
+        if !from.registries.is_empty() {
+            into.registries.clone_from(&from.registries);
+        }
+
+        if !from.include.is_empty() {
+            into.include.clone_from(&from.include);
+        }
+
+        if let Some(profile) = from.default_profile {
+            into.default_profile = Some(profile)
+        }
+
+        for (k, v) in from.profiles.iter() {
+            into.profiles.insert(k.clone(), v.clone());
+        }
+
         into
     }
 
@@ -200,7 +485,11 @@ impl Config {
                         &["Check your configuration file to see existing services."],
                     ));
                 } else {
-                    into.services[existing_position] = Arc::new(svc.into());
+                    // The identity isn't part of a template, so we keep the one you have
+                    // configured for the service when it is replaced.
+                    let mut svc: service::Service = svc.into();
+                    svc.identity = into.services[existing_position].identity.clone();
+                    into.services[existing_position] = Arc::new(svc);
                 }
             } else {
                 into.services.push(Arc::new(svc.into()));
@@ -210,6 +499,87 @@ impl Config {
         Ok(into)
     }
 
+    /// Records the registry template (and its version and variables) which the app
+    /// and service in `template` were installed from.
+    pub fn with_installed_template(
+        &self,
+        template: &EntryConfig,
+        id: &str,
+        version: &str,
+        variables: &BTreeMap<String, String>,
+    ) -> Self {
+        let mut into = self.clone();
+        let keys = template
+            .app
+            .iter()
+            .map(|app| format!("apps/{}", app.name))
+            .chain(
+                template
+                    .service
+                    .iter()
+                    .map(|svc| format!("services/{}", svc.name)),
+            );
+
+        for key in keys {
+            let installed = InstalledTemplate {
+                id: id.to_string(),
+                version: version.to_string(),
+                variables: variables.clone(),
+                checksum: self.get_template_checksum(&key).unwrap_or_default(),
+            };
+
+            into.templates.insert(key, installed);
+        }
+
+        into
+    }
+
+    /// Determines whether the app or service installed under `key` (`apps/{name}` or
+    /// `services/{name}`) has been changed since it was installed from its template,
+    /// in which case re-applying the template would discard your changes.
+    pub fn is_template_modified(&self, key: &str) -> bool {
+        match self.templates.get(key) {
+            Some(installed) => {
+                self.get_template_checksum(key).as_deref() != Some(installed.checksum.as_str())
+            }
+            None => false,
+        }
+    }
+
+    fn get_template_checksum(&self, key: &str) -> Option<String> {
+        let yaml = match key.split_once('/')? {
+            ("apps", name) => serde_yaml::to_string(self.get_app(name)?).ok()?,
+            ("services", name) => {
+                // The identity isn't part of a template, so configuring it doesn't count
+                // as a change to the service.
+                let mut svc = self.get_service(name).ok()?.clone();
+                svc.identity = None;
+                serde_yaml::to_string(&svc).ok()?
+            }
+            _ => return None,
+        };
+
+        Some(super::repo_config::hash_bytes(yaml.as_bytes()))
+    }
+
+    /// Gets the registry templates which the apps and services in your config file
+    /// were installed from, keyed by `apps/{name}` or `services/{name}`.
+    pub fn get_installed_templates(&self) -> Vec<(&str, &InstalledTemplate)> {
+        let mut templates: Vec<(&str, &InstalledTemplate)> = self
+            .templates
+            .iter()
+            .filter(|(key, _)| match key.split_once('/') {
+                Some(("apps", name)) => self.apps.iter().any(|a| a.get_name() == name),
+                Some(("services", name)) => self.services.iter().any(|s| s.name == name),
+                _ => false,
+            })
+            .map(|(key, installed)| (key.as_str(), installed))
+            .collect();
+
+        templates.sort_by_key(|(key, _)| *key);
+        templates
+    }
+
     #[cfg(test)]
     pub fn for_dev_directory(dir: &Path) -> Self {
         Self {
@@ -237,15 +607,114 @@ impl Config {
 
     #[tracing::instrument(name = "config:from_file" err, skip(path))]
     pub fn from_file(path: &Path) -> Result<Self, human_errors::Error> {
-        let f = std::fs::File::open(path).wrap_user_err(
+        let (local, local_value) = Self::read_layer(path)?;
+
+        let mut included = Vec::new();
+        let mut visited = vec![path.canonicalize().unwrap_or_else(|_| path.to_path_buf())];
+        Self::read_includes(path, &local, &mut visited, &mut included)?;
+
+        let mut cfg = Config::default();
+        let mut included_cfg: Option<Config> = None;
+        let mut layers = Vec::new();
+        for (include_path, include, include_value) in included {
+            cfg = cfg.extend(include.clone());
+            included_cfg = Some(match included_cfg {
+                Some(merged) => merged.extend(include),
+                None => include,
+            });
+            layers.push((include_path, Arc::new(include_value)));
+        }
+
+        cfg = cfg.extend(local);
+        layers.push((path.to_path_buf(), Arc::new(local_value)));
+
+        cfg.included = included_cfg.map(Arc::new);
+        cfg.layers = layers;
+        cfg.config_file = Some(path.to_path_buf());
+
+        Ok(cfg)
+    }
+
+    /// Reads a single config file, without merging it with the defaults or any of
+    /// the files it includes.
+    fn read_layer(path: &Path) -> Result<(Self, serde_yaml::Value), human_errors::Error> {
+        let content = std::fs::read_to_string(path).wrap_user_err(
             format!("We could not open your Git-Tool config file '{}' for reading.", path.display()),
             &["Check that your config file exists and is readable by the user running git-tool before trying again."],
         )?;
 
-        let mut cfg = Config::default().extend(Config::from_reader(f)?);
-        cfg.config_file = Some(path.to_path_buf());
+        let parse_err = |e: serde_yaml::Error| {
+            human_errors::wrap_user(
+                e,
+                format!(
+                    "We couldn't parse your configuration file '{}' due to a YAML parser error.",
+                    path.display()
+                ),
+                &["Check that the YAML in your configuration file is correctly formatted."],
+            )
+        };
 
-        Ok(cfg)
+        let value: serde_yaml::Value = serde_yaml::from_str(&content).map_err(parse_err)?;
+        let migration = migration::migrate(value)?;
+        if !migration.is_required() {
+            let cfg: Config = serde_yaml::from_str(&content).map_err(parse_err)?;
+            return Ok((cfg, migration.get_document().clone()));
+        }
+
+        warn!(
+            "Your config file '{}' uses v{} of the config format, run `gt config migrate` to upgrade it to v{}.",
+            path.display(),
+            migration.from,
+            migration.to
+        );
+
+        let value = migration.get_document().clone();
+        let cfg: Config = serde_yaml::from_value(value.clone()).map_err(parse_err)?;
+
+        Ok((cfg, value))
+    }
+
+    /// Reads the files included by a config file (and the files they include in
+    /// turn), in the order they should be merged.
+    fn read_includes(
+        path: &Path,
+        cfg: &Config,
+        visited: &mut Vec<PathBuf>,
+        into: &mut Vec<(PathBuf, Config, serde_yaml::Value)>,
+    ) -> Result<(), human_errors::Error> {
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+        for include in cfg.include.iter() {
+            let include_path = base_dir.join(expand_path(include));
+            let canonical_path = include_path.canonicalize().wrap_user_err(
+                format!(
+                    "Could not find the config file '{}' which is included by '{}'.",
+                    include_path.display(),
+                    path.display()
+                ),
+                &["Check the paths in your `include` list, keeping in mind that relative paths are resolved from the directory containing the file which includes them."],
+            )?;
+
+            if visited.contains(&canonical_path) {
+                return Err(human_errors::user(
+                    format!(
+                        "The config file '{}' includes itself, either directly or through one of the files it includes.",
+                        include_path.display()
+                    ),
+                    &["Remove the circular entry from the `include` list in your config files."],
+                ));
+            }
+
+            let (included, value) = Self::read_layer(&include_path)?;
+
+            visited.push(canonical_path);
+            Self::read_includes(&include_path, &included, visited, into)?;
+            visited.pop();
+
+            into.push((include_path, included, value));
+        }
+
+        Ok(())
     }
 
     pub fn from_file_or_default(path: &Path) -> Self {
@@ -291,7 +760,18 @@ impl Config {
     }
 
     pub fn to_string(&self) -> Result<String, human_errors::Error> {
-        let config = serde_yaml::to_string(self).map_err(|e| {
+        let config = match &self.included {
+            // Values which come from an included file are left out, so that saving
+            // your config doesn't copy them into your own config file.
+            Some(included) => serde_yaml::to_value(self)
+                .and_then(|value| {
+                    serde_yaml::to_value(included.as_ref())
+                        .map(|included| without_included(value, &included))
+                })
+                .and_then(|value| serde_yaml::to_string(&value)),
+            None => serde_yaml::to_string(self),
+        }
+        .map_err(|e| {
             human_errors::wrap_system(
                 e,
                 "We couldn't serialize your configuration to YAML due to a YAML serializer error.",
@@ -315,34 +795,94 @@ impl Config {
         self.config_file.clone()
     }
 
+    /// Gets the files which this config was loaded from, starting with the files
+    /// it includes and ending with the config file itself.
+    pub fn get_config_files(&self) -> Vec<&Path> {
+        if !self.layers.is_empty() {
+            self.layers.iter().map(|(path, _)| path.as_path()).collect()
+        } else {
+            self.config_file
+                .iter()
+                .map(|path| path.as_path())
+                .filter(|path| path.exists())
+                .collect()
+        }
+    }
+
     pub fn get_dev_directory(&self) -> &Path {
-        &self.dev_directory
+        self.get_overrides()
+            .find_map(|p| p.dev_directory.as_deref())
+            .unwrap_or(&self.dev_directory)
     }
 
     pub fn get_scratch_directory(&self) -> PathBuf {
-        match self.scratch_directory.clone() {
+        match self.get_profile_directory(|p| &p.scratch_directory, &self.scratch_directory) {
             Some(dir) => dir,
             None => self.get_dev_directory().join("scratch"),
         }
     }
 
     pub fn get_worktree_directory(&self) -> PathBuf {
-        match self.worktree_directory.clone() {
+        match self.get_profile_directory(|p| &p.worktree_directory, &self.worktree_directory) {
             Some(dir) => dir,
             None => self.get_dev_directory().join("worktrees"),
         }
     }
 
+    /// Gets the directory in which registries downloaded from remote sources are cached.
+    pub fn get_registry_cache_directory(&self) -> PathBuf {
+        match &self.registry_cache_directory {
+            Some(dir) => dir.clone(),
+            None => match directories_next::ProjectDirs::from("com", "SierraSoftworks", "Git-Tool")
+            {
+                Some(dirs) => dirs.cache_dir().join("registries"),
+                None => std::env::temp_dir().join("git-tool").join("registries"),
+            },
+        }
+    }
+
+    /// Gets a directory which is derived from the dev directory unless explicitly
+    /// configured. A layer which moves the dev directory also moves these derived
+    /// directories, rather than inheriting an explicit choice from a lower layer.
+    fn get_profile_directory<F>(&self, field: F, root: &Option<PathBuf>) -> Option<PathBuf>
+    where
+        F: Fn(&Profile) -> &Option<PathBuf>,
+    {
+        for layer in self.get_overrides() {
+            if let Some(dir) = field(layer) {
+                return Some(dir.clone());
+            } else if layer.dev_directory.is_some() {
+                return None;
+            }
+        }
+
+        root.clone()
+    }
+
+    fn get_app_list(&self) -> &Vec<Arc<app::App>> {
+        self.get_overrides()
+            .map(|p| &p.apps)
+            .find(|apps| !apps.is_empty())
+            .unwrap_or(&self.apps)
+    }
+
+    fn get_service_list(&self) -> &Vec<Arc<service::Service>> {
+        self.get_overrides()
+            .map(|p| &p.services)
+            .find(|services| !services.is_empty())
+            .unwrap_or(&self.services)
+    }
+
     pub fn get_apps(&self) -> core::slice::Iter<'_, Arc<app::App>> {
-        self.apps.iter()
+        self.get_app_list().iter()
     }
 
     pub fn get_default_app(&self) -> Option<&app::App> {
-        self.apps.first().map(|f| f.as_ref())
+        self.get_app_list().first().map(|f| f.as_ref())
     }
 
     pub fn get_app(&self, name: &str) -> Option<&app::App> {
-        for app in self.apps.iter() {
+        for app in self.get_app_list().iter() {
             if app.get_name() == name {
                 return Some(app.as_ref());
             }
@@ -352,15 +892,15 @@ impl Config {
     }
 
     pub fn get_services(&self) -> core::slice::Iter<'_, Arc<service::Service>> {
-        self.services.iter()
+        self.get_service_list().iter()
     }
 
     pub fn get_default_service(&self) -> Option<&service::Service> {
-        self.services.first().map(|f| f.as_ref())
+        self.get_service_list().first().map(|f| f.as_ref())
     }
 
     pub fn get_service(&self, domain: &str) -> Result<&service::Service, human_errors::Error> {
-        for svc in self.services.iter() {
+        for svc in self.get_service_list().iter() {
             if svc.name == domain {
                 return Ok(svc.as_ref());
             }
@@ -379,6 +919,15 @@ impl Config {
         self.aliases.get(name).cloned()
     }
 
+    /// The template used to name new branches, if one has been configured.
+    pub fn get_branch_template(&self) -> Option<&str> {
+        self.branch_template.as_deref()
+    }
+
+    pub fn get_registries(&self) -> &[RegistrySource] {
+        &self.registries
+    }
+
     pub fn get_aliases(&self) -> std::collections::hash_map::Iter<'_, String, String> {
         self.aliases.iter()
     }
@@ -392,10 +941,158 @@ impl Config {
     }
 
     pub fn get_features(&self) -> &features::Features {
-        &self.features
+        self.effective_features.as_ref().unwrap_or(&self.features)
+    }
+
+    /// Lists the values in your effective configuration (after includes, the active
+    /// profile and environment overrides have been applied) along with their sources.
+    pub fn get_effective_values(&self) -> Vec<ConfigValue> {
+        let mut values = vec![
+            ConfigValue {
+                key: "directory".into(),
+                value: self.get_dev_directory().display().to_string(),
+                source: self.get_source(
+                    Some(ENV_DIRECTORY),
+                    |p| p.dev_directory.is_some(),
+                    &["directory"],
+                ),
+            },
+            ConfigValue {
+                key: "scratchpads".into(),
+                value: self.get_scratch_directory().display().to_string(),
+                source: self.get_source(
+                    Some(ENV_SCRATCHPADS),
+                    |p| p.scratch_directory.is_some(),
+                    &["scratchpads"],
+                ),
+            },
+            ConfigValue {
+                key: "worktrees".into(),
+                value: self.get_worktree_directory().display().to_string(),
+                source: self.get_source(
+                    Some(ENV_WORKTREES),
+                    |p| p.worktree_directory.is_some(),
+                    &["worktrees"],
+                ),
+            },
+        ];
+
+        let services_source = self.get_source(None, |p| !p.services.is_empty(), &["services"]);
+        for svc in self.get_services() {
+            values.push(ConfigValue {
+                key: "services".into(),
+                value: svc.name.clone(),
+                source: services_source.clone(),
+            });
+        }
+
+        let apps_source = self.get_source(None, |p| !p.apps.is_empty(), &["apps"]);
+        for app in self.get_apps() {
+            values.push(ConfigValue {
+                key: "apps".into(),
+                value: app.get_name().to_string(),
+                source: apps_source.clone(),
+            });
+        }
+
+        let mut aliases: Vec<(&String, &String)> = self.aliases.iter().collect();
+        aliases.sort();
+        for (alias, repo) in aliases {
+            values.push(ConfigValue {
+                key: format!("aliases.{alias}"),
+                value: repo.clone(),
+                source: self.get_source(None, |_| false, &["aliases", alias]),
+            });
+        }
+
+        for (flag, enabled) in self.get_features().iter() {
+            values.push(ConfigValue {
+                key: format!("features.{flag}"),
+                value: enabled.to_string(),
+                source: self.get_source(
+                    Some(&format!("{ENV_FEATURE_PREFIX}{}", flag.to_uppercase())),
+                    |p| p.features.as_ref().and_then(|f| f.get(flag)).is_some(),
+                    &["features", flag],
+                ),
+            });
+        }
+
+        values
+    }
+
+    /// Determines which layer of the configuration provided a value, checking the
+    /// environment, the active profile, and then each of the files which were merged.
+    fn get_source<F>(&self, env_var: Option<&str>, is_set: F, path: &[&str]) -> String
+    where
+        F: Fn(&Profile) -> bool,
+    {
+        if let (Some(env_var), Some(environment)) = (env_var, &self.environment) {
+            if is_set(environment) {
+                return format!("${env_var}");
+            }
+        }
+
+        if let Some((name, profile)) = &self.active_profile {
+            if is_set(profile) {
+                return format!("profile '{name}'");
+            }
+        }
+
+        for (file, layer) in self.layers.iter().rev() {
+            let value = path
+                .iter()
+                .try_fold(layer.as_ref(), |value, &key| value.get(key));
+
+            match value {
+                None | Some(serde_yaml::Value::Null) => {}
+                Some(serde_yaml::Value::Sequence(items)) if items.is_empty() => {}
+                Some(_) => return file.display().to_string(),
+            }
+        }
+
+        "default".into()
     }
 }
 
+/// Removes the values which are identical to those in the included config files,
+/// including individual entries from the keyed collections which [`Config::extend`]
+/// merges entry by entry.
+fn without_included(value: serde_yaml::Value, included: &serde_yaml::Value) -> serde_yaml::Value {
+    use serde_yaml::Value;
+
+    let Value::Mapping(map) = value else {
+        return value;
+    };
+
+    Value::Mapping(
+        map.into_iter()
+            .filter_map(|(key, value)| {
+                let included = match key.as_str() {
+                    Some("$schema") | Some("include") => None,
+                    _ => included.get(&key),
+                };
+
+                match (included, value) {
+                    (Some(included), value) if included == &value => None,
+                    (Some(included), Value::Mapping(entries))
+                        if matches!(
+                            key.as_str(),
+                            Some("aliases") | Some("trusted_repos") | Some("features")
+                        ) =>
+                    {
+                        let entries = entries
+                            .into_iter()
+                            .filter(|(k, v)| included.get(k) != Some(v))
+                            .collect();
+                        Some((key, Value::Mapping(entries)))
+                    }
+                    (_, value) => Some((key, value)),
+                }
+            })
+            .collect(),
+    )
+}
+
 fn expand_path<S: AsRef<str>>(input: S) -> PathBuf {
     let input = input.as_ref();
 
@@ -420,7 +1117,7 @@ fn expand_path<S: AsRef<str>>(input: S) -> PathBuf {
     Path::new(with_expanded_vars.as_ref()).to_path_buf()
 }
 
-fn deserialize_expanded_path<'de, D>(deserializer: D) -> Result<PathBuf, D::Error>
+pub(super) fn deserialize_expanded_path<'de, D>(deserializer: D) -> Result<PathBuf, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -428,7 +1125,9 @@ where
     Ok(expand_path(s))
 }
 
-fn deserialize_optional_expanded_path<'de, D>(deserializer: D) -> Result<Option<PathBuf>, D::Error>
+pub(super) fn deserialize_optional_expanded_path<'de, D>(
+    deserializer: D,
+) -> Result<Option<PathBuf>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -436,14 +1135,14 @@ where
     Ok(s.map(expand_path))
 }
 
-fn serialize_expanded_path<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
+pub(super) fn serialize_expanded_path<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
     serializer.serialize_str(&escape_expanded_path::<S::Error>(path)?)
 }
 
-fn serialize_optional_expanded_path<S>(
+pub(super) fn serialize_optional_expanded_path<S>(
     path: &Option<PathBuf>,
     serializer: S,
 ) -> Result<S::Ok, S::Error>
@@ -531,6 +1230,7 @@ impl Default for Config {
                         kind: "GitHub/v3".into(),
                         url: "https://api.github.com".into(),
                     }),
+                    identity: None,
                 }),
                 Arc::new(service::Service {
                     name: "ghp".into(),
@@ -538,6 +1238,7 @@ impl Default for Config {
                     website: "https://github.com/{{ .Repo.FullName }}".into(),
                     git_url: "https://github.com/{{ .Repo.FullName }}.git".into(),
                     api: None,
+                    identity: None,
                 }),
                 Arc::new(service::Service {
                     name: "gitlab".into(),
@@ -545,6 +1246,7 @@ impl Default for Config {
                     website: "https://gitlab.com/{{ .Repo.FullName }}".into(),
                     git_url: if has_ssh_keys { "git@gitlab.com:{{ .Repo.FullName }}.git" } else { "https://gitlab.com/{{ .Repo.FullName }}.git" }.into(),
                     api: None,
+                    identity: None,
                 }),
                 Arc::new(service::Service {
                     name: "bitbucket".into(),
@@ -552,6 +1254,7 @@ impl Default for Config {
                     website: "https://bitbucket.org/{{ .Repo.FullName }}".into(),
                     git_url: if has_ssh_keys { "git@gbitbucket.org:{{ .Repo.FullName }}.git" } else { "https://bitbucket.org/{{ .Repo.FullName }}.git" }.into(),
                     api: None,
+                    identity: None,
                 }),
                 Arc::new(service::Service {
                     name: "ado".into(),
@@ -559,11 +1262,24 @@ impl Default for Config {
                     website: "https://dev.azure.com/{{ .Repo.Namespace | urlquery }}/_git/{{ .Repo.Name | urlquery }}".into(),
                     git_url: if has_ssh_keys { "git@ssh.dev.azure.com:v3/{{ .Repo.FullName | urlquery }}" } else { "https://dev.azure.com/{{ .Repo.Namespace | urlquery }}/_git/{{ .Repo.Name | urlquery }}" }.into(),
                     api: None,
+                    identity: None,
                 }),
             ],
             aliases: HashMap::new(),
+            registries: Vec::new(),
+            registry_cache_directory: None,
             trusted_repos: HashMap::new(),
+            branch_template: None,
+            templates: HashMap::new(),
             features: Default::default(),
+            default_profile: None,
+            profiles: HashMap::new(),
+            active_profile: None,
+            include: Vec::new(),
+            environment: None,
+            effective_features: None,
+            included: None,
+            layers: Vec::new(),
         }
     }
 }
@@ -572,6 +1288,7 @@ impl Default for Config {
 mod tests {
     use super::Config;
     use crate::{
+        engine::RegistrySource,
         online::registry::{EntryApp, EntryConfig, EntryService},
         test::get_repo_root,
     };
@@ -623,6 +1340,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn load_from_string_with_branch_template() {
+        let cfg =
+            Config::from_str("directory: /test/dev\nbranch_template: \"{{ .User }}/{{ .Slug }}\"")
+                .unwrap();
+        assert_eq!(cfg.get_branch_template(), Some("{{ .User }}/{{ .Slug }}"));
+
+        let cfg = Config::from_str("directory: /test/dev").unwrap();
+        assert_eq!(cfg.get_branch_template(), None);
+    }
+
     #[test]
     fn expanded_paths_with_literal_dollars_round_trip() {
         let cfg =
@@ -699,6 +1427,220 @@ mod tests {
         assert!(removed.is_repo_trusted("gh:sierrasoftworks/other", "def456"));
     }
 
+    #[test]
+    fn profiles() {
+        let cfg = Config::from_str(
+            r#"
+directory: /test/dev
+features:
+    always_open_best_match: true
+profiles:
+    work:
+        directory: /test/work
+        services:
+            - name: corp
+              website: "https://git.example.com/{{ .Repo.FullName }}"
+              gitUrl: "git@git.example.com:{{ .Repo.FullName }}.git"
+              pattern: "*/*"
+        features:
+            create_remote: false
+    personal:
+        scratchpads: /test/scratch
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.get_profile_names(), vec!["personal", "work"]);
+        assert_eq!(cfg.get_active_profile_name(), None);
+
+        let work = cfg.with_profile("work").unwrap();
+        assert_eq!(work.get_active_profile_name(), Some("work"));
+        assert_eq!(work.get_dev_directory(), PathBuf::from("/test/work"));
+        assert_eq!(
+            work.get_scratch_directory(),
+            PathBuf::from("/test/work/scratch")
+        );
+        assert!(work.get_service("corp").is_ok());
+        assert!(
+            work.get_service("gh").is_err(),
+            "the profile's services should replace the root services"
+        );
+        assert!(work.get_app("shell").is_some());
+        assert!(!work.get_features().has("create_remote"));
+        assert!(work.get_features().has("always_open_best_match"));
+
+        let personal = cfg.with_profile("personal").unwrap();
+        assert_eq!(personal.get_dev_directory(), PathBuf::from("/test/dev"));
+        assert_eq!(
+            personal.get_scratch_directory(),
+            PathBuf::from("/test/scratch")
+        );
+        assert!(personal.get_service("gh").is_ok());
+        assert!(personal.get_features().has("create_remote"));
+
+        assert!(cfg.with_profile("missing").is_err());
+
+        // Selecting a profile shouldn't change the config which gets saved.
+        assert_eq!(work.to_string().unwrap(), cfg.to_string().unwrap());
+    }
+
+    #[test]
+    fn select_profile() {
+        let cfg = Config::from_str(
+            r#"
+directory: /test/dev
+profile: personal
+profiles:
+    work:
+        directory: /test/work
+    client:
+        directory: /test/work/client
+    personal: {}
+"#,
+        )
+        .unwrap();
+
+        let selected = cfg.select_profile(Some("work"), None).unwrap();
+        assert_eq!(selected.get_active_profile_name(), Some("work"));
+
+        let selected = cfg
+            .select_profile(None, Some(&PathBuf::from("/test/work/client/gh/a/b")))
+            .unwrap();
+        assert_eq!(
+            selected.get_active_profile_name(),
+            Some("client"),
+            "the most specific directory should win"
+        );
+
+        let selected = cfg
+            .select_profile(None, Some(&PathBuf::from("/test/work/gh/a/b")))
+            .unwrap();
+        assert_eq!(selected.get_active_profile_name(), Some("work"));
+
+        let selected = cfg
+            .select_profile(None, Some(&PathBuf::from("/elsewhere")))
+            .unwrap();
+        assert_eq!(
+            selected.get_active_profile_name(),
+            Some("personal"),
+            "the default profile should be used outside of any profile's directory"
+        );
+
+        let selected = cfg.with_default_profile("work").unwrap();
+        assert_eq!(selected.get_default_profile_name(), Some("work"));
+        assert!(cfg.with_default_profile("missing").is_err());
+    }
+
+    #[test]
+    fn includes() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("team")).unwrap();
+        std::fs::write(
+            temp.path().join("team").join("base.yml"),
+            r#"
+directory: /team/dev
+services:
+    - name: corp
+      website: "https://git.example.com/{{ .Repo.FullName }}"
+      gitUrl: "git@git.example.com:{{ .Repo.FullName }}.git"
+      pattern: "*/*"
+aliases:
+    gt: gh:SierraSoftworks/git-tool
+features:
+    create_remote: false
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("config.yml"),
+            r#"
+include:
+    - team/base.yml
+aliases:
+    mine: gh:example/mine
+"#,
+        )
+        .unwrap();
+
+        let cfg = Config::from_file(&temp.path().join("config.yml")).unwrap();
+        assert_eq!(cfg.get_dev_directory(), PathBuf::from("/team/dev"));
+        assert!(cfg.get_service("corp").is_ok());
+        assert!(cfg.get_service("gh").is_err());
+        assert_eq!(
+            cfg.get_alias("gt"),
+            Some("gh:SierraSoftworks/git-tool".into())
+        );
+        assert_eq!(cfg.get_alias("mine"), Some("gh:example/mine".into()));
+        assert!(!cfg.get_features().has("create_remote"));
+
+        // Saving the config shouldn't copy the included values into the config file.
+        let mut updated = cfg.clone();
+        updated.add_alias("new", "gh:example/new");
+        let saved: serde_yaml::Value = serde_yaml::from_str(&updated.to_string().unwrap()).unwrap();
+        assert!(saved.get("services").is_none());
+        assert!(saved.get("directory").is_none());
+        assert!(saved["aliases"].get("gt").is_none());
+        assert_eq!(saved["aliases"]["new"].as_str(), Some("gh:example/new"));
+        assert_eq!(saved["include"][0].as_str(), Some("team/base.yml"));
+    }
+
+    #[test]
+    fn missing_features_keep_defaults_and_included_flags() {
+        let cfg = Config::from_str("directory: /test/dev").unwrap();
+        assert!(cfg.get_features().has("create_remote"));
+
+        let included = Config::from_str("directory: /test/dev\nfeatures:\n  create_remote: false")
+            .unwrap()
+            .extend(serde_yaml::from_str("directory: /test/dev").unwrap());
+        assert!(!included.get_features().has("create_remote"));
+        assert!(included.get_features().has("check_exists"));
+    }
+
+    #[test]
+    fn includes_cycle() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("a.yml"), "include: [b.yml]").unwrap();
+        std::fs::write(temp.path().join("b.yml"), "include: [a.yml]").unwrap();
+
+        assert!(Config::from_file(&temp.path().join("a.yml")).is_err());
+
+        std::fs::write(temp.path().join("c.yml"), "include: [missing.yml]").unwrap();
+        assert!(Config::from_file(&temp.path().join("c.yml")).is_err());
+    }
+
+    #[test]
+    fn environment_overrides() {
+        let cfg = Config::from_str("directory: /test/dev\nscratchpads: /test/scratch").unwrap();
+
+        let overridden = cfg.with_environment(vec![
+            ("GITTOOL_DIRECTORY".to_string(), "/env/dev".to_string()),
+            (
+                "GITTOOL_FEATURE_CREATE_REMOTE".to_string(),
+                "false".to_string(),
+            ),
+            (
+                "GITTOOL_FEATURE_CHECK_EXISTS".to_string(),
+                "invalid".to_string(),
+            ),
+            ("UNRELATED".to_string(), "value".to_string()),
+        ]);
+
+        assert_eq!(overridden.get_dev_directory(), PathBuf::from("/env/dev"));
+        assert_eq!(
+            overridden.get_scratch_directory(),
+            PathBuf::from("/env/dev/scratch")
+        );
+        assert!(!overridden.get_features().has("create_remote"));
+        assert!(overridden.get_features().has("check_exists"));
+
+        // Environment overrides shouldn't be saved to the config file.
+        assert_eq!(overridden.to_string().unwrap(), cfg.to_string().unwrap());
+
+        // They should also continue to apply after the config is updated.
+        let updated = overridden.with_feature_flag("create_remote", true);
+        assert!(!updated.get_features().has("create_remote"));
+    }
+
     #[test]
     fn load_from_string_with_new_apps() {
         match Config::from_str(
@@ -741,6 +1683,7 @@ apps:
                 command: "/bin/true".to_string(),
                 args: vec![],
                 environment: vec![],
+                detect: None,
             }),
             service: Some(EntryService {
                 name: "example.com".to_string(),
@@ -765,6 +1708,107 @@ apps:
         assert!(new_cfg.apply_template(template, true).is_ok());
     }
 
+    #[test]
+    fn installed_templates() {
+        let cfg = Config::from_str("directory: /dev").unwrap();
+        let template = EntryConfig {
+            platform: "any".to_string(),
+            app: Some(EntryApp {
+                name: "test-app".to_string(),
+                command: "/bin/true".to_string(),
+                args: vec![],
+                environment: vec![],
+                detect: None,
+            }),
+            service: None,
+        };
+
+        let cfg = cfg
+            .apply_template(template.clone(), false)
+            .unwrap()
+            .with_installed_template(
+                &template,
+                "apps/test",
+                "1.0.0",
+                &[("flavour".to_string(), "vanilla".to_string())].into(),
+            );
+
+        let installed = cfg.get_installed_templates();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].0, "apps/test-app");
+        assert_eq!(installed[0].1.id, "apps/test");
+        assert_eq!(installed[0].1.variables.get("flavour").unwrap(), "vanilla");
+
+        assert!(installed[0].1.is_outdated("1.1.0"));
+        assert!(!installed[0].1.is_outdated("1.0.0"));
+        assert!(!installed[0].1.is_outdated("0.9.0"));
+
+        let cfg = Config::from_str(&cfg.to_string().unwrap()).unwrap();
+        assert_eq!(
+            cfg.get_installed_templates().len(),
+            1,
+            "the installed templates should be saved in the config file"
+        );
+        assert!(!cfg.is_template_modified("apps/test-app"));
+
+        let modified = cfg
+            .apply_template(
+                EntryConfig {
+                    platform: "any".to_string(),
+                    app: Some(EntryApp {
+                        name: "test-app".to_string(),
+                        command: "/bin/true".to_string(),
+                        args: vec!["--verbose".to_string()],
+                        environment: vec![],
+                        detect: None,
+                    }),
+                    service: None,
+                },
+                true,
+            )
+            .unwrap();
+        assert!(
+            modified.is_template_modified("apps/test-app"),
+            "changes to the app's args should be detected"
+        );
+    }
+
+    #[test]
+    fn registries() {
+        let cfg = Config::from_str(
+            r#"
+directory: /dev
+registries:
+  - path: /opt/git-tool/registry
+  - git: https://gitlab.example.com/platform/git-tool-registry.git
+    branch: stable
+  - url: https://templates.example.com/git-tool
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cfg.get_registries(),
+            &[
+                RegistrySource::Directory {
+                    path: PathBuf::from("/opt/git-tool/registry")
+                },
+                RegistrySource::Git {
+                    git: "https://gitlab.example.com/platform/git-tool-registry.git".into(),
+                    branch: Some("stable".into())
+                },
+                RegistrySource::Http {
+                    url: "https://templates.example.com/git-tool".into()
+                },
+            ]
+        );
+
+        assert!(
+            Config::default().get_registries().is_empty(),
+            "no additional registries should be configured by default"
+        );
+    }
+
     #[test]
     fn test_load_file() {
         let file_path = get_repo_root()
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -9,6 +10,7 @@ pub const FORK_REMOTE: &str = "fork_remote";
 
 pub const OPEN_NEW_REPO: &str = "open_new_repo_in_default_app";
 pub const ALWAYS_OPEN_BEST_MATCH: &str = "always_open_best_match";
+pub const SWITCH_AUTOSTASH: &str = "switch_autostash";
 
 pub const TELEMETRY: &str = "telemetry";
 pub const CHECK_FOR_UPDATES: &str = "check_for_updates";
@@ -22,13 +24,14 @@ lazy_static! {
         FORK_REMOTE,
         OPEN_NEW_REPO,
         ALWAYS_OPEN_BEST_MATCH,
+        SWITCH_AUTOSTASH,
         #[cfg(feature = "telemetry")]
         TELEMETRY,
         CHECK_FOR_UPDATES,
     ];
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Features {
     #[serde(flatten)]
     flags: HashMap<String, bool>,
@@ -52,6 +55,32 @@ impl Features {
         self.flags.get(flag).copied().unwrap_or_default()
     }
 
+    /// Gets the value of a flag if it has been explicitly set.
+    pub fn get(&self, flag: &str) -> Option<bool> {
+        self.flags.get(flag).copied()
+    }
+
+    /// Lists the flags which have been set, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, bool)> {
+        let mut flags: Vec<(&str, bool)> =
+            self.flags.iter().map(|(k, &v)| (k.as_str(), v)).collect();
+        flags.sort();
+        flags.into_iter()
+    }
+
+    /// A set of features in which no flags have been set, not even the defaults.
+    pub fn none() -> Self {
+        Self::overrides().build()
+    }
+
+    /// Creates a builder without the default flags, which is used to describe a
+    /// set of flags that will be layered on top of another.
+    pub fn overrides() -> FeaturesBuilder {
+        FeaturesBuilder {
+            flags: HashMap::new(),
+        }
+    }
+
     pub fn to_builder(&self) -> FeaturesBuilder {
         FeaturesBuilder {
             flags: self.flags.clone(),
@@ -102,4 +131,10 @@ mod tests {
         assert!(Features::default().has(CREATE_REMOTE));
         assert!(Features::default().has(CHECK_FOR_UPDATES));
     }
+
+    #[test]
+    fn none() {
+        assert_eq!(Features::none().iter().count(), 0);
+        assert_eq!(Features::none().get(CREATE_REMOTE), None);
+    }
 }
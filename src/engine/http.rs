@@ -166,6 +166,8 @@ pub(super) mod mocks {
         path: String,
         status: u16,
         body: String,
+        headers: Vec<(String, String)>,
+        request_headers: Vec<(String, String)>,
     }
 
     impl MockHttpRoute {
@@ -175,8 +177,22 @@ pub(super) mod mocks {
                 path: path.into(),
                 status,
                 body: body.into(),
+                headers: Vec::new(),
+                request_headers: Vec::new(),
             }
         }
+
+        /// Adds a header to the response returned by this route.
+        pub fn with_header(mut self, name: &str, value: &str) -> Self {
+            self.headers.push((name.into(), value.into()));
+            self
+        }
+
+        /// Only matches requests which include the provided header value.
+        pub fn with_request_header(mut self, name: &str, value: &str) -> Self {
+            self.request_headers.push((name.into(), value.into()));
+            self
+        }
     }
 
     pub fn mock_http_client(routes: Vec<MockHttpRoute>) -> Arc<dyn HttpClient + Send + Sync> {
@@ -187,20 +203,29 @@ pub(super) mod mocks {
             let path = route.path;
             let body = route.body;
             let status = route.status;
+            let headers = route.headers;
+            let request_headers = route.request_headers;
 
             mock.expect_request()
                 .withf(move |req| {
                     req.method().as_str().eq_ignore_ascii_case(&method)
                         && (req.url().path().eq_ignore_ascii_case(&path)
                             || req.url().as_str().eq_ignore_ascii_case(&path))
+                        && request_headers.iter().all(|(name, value)| {
+                            req.headers()
+                                .get(name.as_str())
+                                .and_then(|v| v.to_str().ok())
+                                == Some(value.as_str())
+                        })
                 })
                 .returning(move |_| {
-                    let res = response::Builder::new()
-                        .header("Content-Type", "application/vnd.github.v3+json")
-                        .status(status)
-                        .body(body.clone())
-                        .unwrap()
-                        .into();
+                    let mut res = response::Builder::new()
+                        .header("Content-Type", "application/vnd.github.v3+json");
+                    for (name, value) in headers.iter() {
+                        res = res.header(name.as_str(), value.as_str());
+                    }
+
+                    let res = res.status(status).body(body.clone()).unwrap().into();
                     Ok(res)
                 });
         }
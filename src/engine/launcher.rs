@@ -3,8 +3,11 @@ use super::{
     Config, Target,
     templates::{render, render_list},
 };
+use crate::console::ConsoleProvider;
+use crate::errors::HumanErrorResultExt;
 use futures::{FutureExt, pin_mut};
 use human_errors::ResultExt;
+use std::io::Write;
 use tracing_batteries::prelude::*;
 
 #[cfg(test)]
@@ -28,6 +31,74 @@ pub fn launcher(config: Arc<Config>) -> Arc<dyn Launcher + Send + Sync> {
     Arc::new(TrueLauncher { config })
 }
 
+/// Constructs a [`Launcher`] which prints the fully rendered command it would
+/// have run (along with its arguments, environment and working directory) to
+/// the console instead of spawning it. This backs Git-Tool's `--dry-run` mode.
+pub fn dry_run_launcher(
+    config: Arc<Config>,
+    console: Arc<dyn ConsoleProvider + Send + Sync>,
+) -> Arc<dyn Launcher + Send + Sync> {
+    Arc::new(DryRunLauncher { config, console })
+}
+
+/// The fully rendered form of an application launch, with every template in the
+/// application's command, arguments and environment resolved against the target.
+struct LaunchPlan {
+    program: String,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    cwd: std::path::PathBuf,
+}
+
+impl LaunchPlan {
+    fn new(
+        config: &Config,
+        a: &app::App,
+        t: &(dyn Target + Send + Sync),
+    ) -> Result<Self, human_errors::Error> {
+        let context = t.template_context(config)?;
+
+        let program = render(a.get_command(), context.clone())?;
+        let args = render_list(a.get_args(), context.clone())?;
+        let env_args = render_list(a.get_environment(), context.clone())?;
+        // Configured environment entries are rendered through the template engine
+        // above; any entry which isn't shaped like `KEY=VALUE` is simply ignored
+        // rather than panicking. The literal launch-time overrides are appended
+        // last so that, under `Command::envs` later-wins semantics, they take
+        // precedence over any configured value with the same key. The target's own
+        // environment comes first, so that apps may override it.
+        let mut env: Vec<(String, String)> = t.environment();
+        env.extend(env_args.iter().filter_map(|e| {
+            e.split_once('=')
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+        }));
+        env.extend(a.get_overrides().iter().cloned());
+
+        Ok(Self {
+            program,
+            args,
+            env,
+            cwd: t.get_path(),
+        })
+    }
+
+    /// Renders the program and its arguments as a single command line, quoting
+    /// any argument which contains whitespace so that it reads unambiguously.
+    fn command_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(self.args.iter())
+            .map(|arg| {
+                if arg.contains(char::is_whitespace) {
+                    format!("{arg:?}")
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
 /// Wraps a [`Launcher`] so that every application launch records privacy-preserving
 /// telemetry events: that an application was launched, and how it exited. Nothing
 /// about the application itself (its name, command, arguments or target) is reported.
@@ -91,29 +162,12 @@ impl Launcher for TrueLauncher {
         a: &app::App,
         t: &(dyn Target + Send + Sync),
     ) -> Result<i32, human_errors::Error> {
-        let context = t.template_context(&self.config)?;
-
-        let program = render(a.get_command(), context.clone())?;
-        let args = render_list(a.get_args(), context.clone())?;
-        let env_args = render_list(a.get_environment(), context.clone())?;
-        // Configured environment entries are rendered through the template engine
-        // above; any entry which isn't shaped like `KEY=VALUE` is simply ignored
-        // rather than panicking. The literal launch-time overrides are appended
-        // last so that, under `Command::envs` later-wins semantics, they take
-        // precedence over any configured value with the same key.
-        let mut env_tuples: Vec<(String, String)> = env_args
-            .iter()
-            .filter_map(|e| {
-                e.split_once('=')
-                    .map(|(k, v)| (k.to_string(), v.to_string()))
-            })
-            .collect();
-        env_tuples.extend(a.get_overrides().iter().cloned());
+        let plan = LaunchPlan::new(&self.config, a, t)?;
 
-        let mut child = Command::new(program)
-            .args(args)
-            .current_dir(t.get_path())
-            .envs(env_tuples)
+        let mut child = Command::new(plan.program)
+            .args(plan.args)
+            .current_dir(plan.cwd)
+            .envs(plan.env)
             .spawn()
             .wrap_user_err(
                 format!("Could not launch the application '{}' due to an OS-level error.", a.get_command()),
@@ -124,6 +178,38 @@ impl Launcher for TrueLauncher {
     }
 }
 
+struct DryRunLauncher {
+    config: Arc<Config>,
+    console: Arc<dyn ConsoleProvider + Send + Sync>,
+}
+
+#[async_trait::async_trait]
+impl Launcher for DryRunLauncher {
+    #[tracing::instrument(name = "launch(dry-run)", err, skip(self, t, a), fields(app=%a, target=%t))]
+    async fn run(
+        &self,
+        a: &app::App,
+        t: &(dyn Target + Send + Sync),
+    ) -> Result<i32, human_errors::Error> {
+        let plan = LaunchPlan::new(&self.config, a, t)?;
+
+        let mut output = self.console.output();
+        writeln!(
+            output,
+            "[dry-run] Would launch '{}': {}",
+            a.get_name(),
+            plan.command_line()
+        )
+        .to_human_error()?;
+        writeln!(output, "  working directory: {}", plan.cwd.display()).to_human_error()?;
+        for (key, value) in plan.env.iter() {
+            writeln!(output, "  environment: {key}={value}").to_human_error()?;
+        }
+
+        Ok(0)
+    }
+}
+
 impl TrueLauncher {
     #[cfg(windows)]
     async fn forward_signals(
@@ -154,8 +240,6 @@ impl TrueLauncher {
         &self,
         child: &mut tokio::process::Child,
     ) -> Result<i32, human_errors::Error> {
-        use crate::errors::HumanErrorResultExt as _;
-
         let child_id = child.id().ok_or_else(|| human_errors::user("Unable to determine the child process's PID because the child process has already exited.", &["This might not be a problem, depending on the program you are running, however it may also indicate that the process is not running correctly."]))?;
 
         let pid = nix::unistd::Pid::from_raw(child_id.try_into().map_err(|err| human_errors::wrap_system(
@@ -285,6 +369,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn run_app_dry_run() {
+        let a: app::App = app::App::builder()
+            .with_name("test")
+            .with_command("sh")
+            .with_args(vec!["-c", "exit $TEST_CODE"])
+            .with_environment(vec!["TEST_CODE={{ .Target.Name }}"])
+            .into();
+
+        let test_dir = get_dev_dir();
+        let t = Scratchpad::new("123", test_dir.clone());
+
+        let console = crate::console::mock();
+        let launcher = dry_run_launcher(Arc::new(Config::default()), console.clone());
+
+        let result = launcher.run(&a, &t).await.unwrap();
+        assert_eq!(result, 0, "a dry run should always report success");
+
+        let output = console.to_string();
+        assert!(
+            output.contains("sh -c \"exit $TEST_CODE\""),
+            "the rendered command should be printed: {output}"
+        );
+        assert!(
+            output.contains(&format!("working directory: {}", test_dir.display())),
+            "the working directory should be printed: {output}"
+        );
+        assert!(
+            output.contains("TEST_CODE=123"),
+            "the rendered environment should be printed: {output}"
+        );
+    }
+
     #[tokio::test]
     #[cfg(windows)]
     async fn run_app_override_wins() {
@@ -0,0 +1,439 @@
+use human_errors::ResultExt;
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+
+/// The version of the config file format which is understood by this version of
+/// Git-Tool. Config files written for older versions are upgraded by applying each
+/// of the [`STEPS`] which follow on from their version, in order.
+pub const CURRENT_VERSION: u32 = 2;
+
+/// A single step in the migration pipeline, which upgrades a config file from the
+/// `from` version to the version which follows it.
+struct Step {
+    from: u32,
+    apply: fn(&mut Mapping, &mut Vec<String>),
+}
+
+const STEPS: &[Step] = &[Step {
+    from: 1,
+    apply: v1_to_v2,
+}];
+
+/// The result of upgrading a config file to the [`CURRENT_VERSION`], including a
+/// description of each change which was made along the way.
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub from: u32,
+    pub to: u32,
+    pub changes: Vec<String>,
+    document: Value,
+}
+
+impl Migration {
+    pub fn is_required(&self) -> bool {
+        self.from < self.to
+    }
+
+    pub fn get_document(&self) -> &Value {
+        &self.document
+    }
+
+    /// Writes the upgraded config to `path`, after copying the original file to a
+    /// backup alongside it. Returns the path of the backup.
+    pub async fn save(&self, path: &Path) -> Result<PathBuf, human_errors::Error> {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(format!(".v{}.bak", self.from));
+        let backup = PathBuf::from(backup);
+
+        tokio::fs::copy(path, &backup).await.wrap_user_err(
+            format!(
+                "Could not back up your config file '{}' to '{}' due to an OS-level error.",
+                path.display(),
+                backup.display()
+            ),
+            &["Make sure that Git-Tool has permission to write to your config directory and then try again."],
+        )?;
+
+        let content = serde_yaml::to_string(&self.document).map_err(|e| {
+            human_errors::wrap_system(
+                e,
+                "We couldn't serialize your upgraded configuration to YAML due to a YAML serializer error.",
+                &["Please report this issue on GitHub so that we can try and resolve it."],
+            )
+        })?;
+
+        tokio::fs::write(path, content).await.wrap_user_err(
+            format!("Could not write your upgraded config to the config file '{}' due to an OS-level error.", path.display()),
+            &["Make sure that Git-Tool has permission to write to your config file and then try again.", "Your original config file has been left unchanged."],
+        )?;
+
+        Ok(backup)
+    }
+}
+
+/// Determines which version of the config file format a document was written
+/// for, using its `$schema` if it has one and otherwise looking for fields which
+/// were only present in older versions.
+pub fn detect_version(document: &Value) -> u32 {
+    if let Some(version) = document
+        .get("$schema")
+        .and_then(|s| s.as_str())
+        .and_then(|schema| {
+            schema
+                .split('/')
+                .find_map(|part| part.strip_prefix('v')?.parse().ok())
+        })
+    {
+        return version;
+    }
+
+    let has_v1_services = document
+        .get("services")
+        .and_then(|s| s.as_sequence())
+        .map(|services| {
+            services
+                .iter()
+                .any(|s| s.get("domain").is_some() && s.get("name").is_none())
+        })
+        .unwrap_or_default();
+
+    if has_v1_services { 1 } else { CURRENT_VERSION }
+}
+
+/// Upgrades a config document to the [`CURRENT_VERSION`] by applying each of the
+/// migration steps which follow on from its detected version.
+pub fn migrate(document: Value) -> Result<Migration, human_errors::Error> {
+    let from = detect_version(&document);
+    if from > CURRENT_VERSION {
+        return Err(human_errors::user(
+            format!(
+                "Your config file was written for v{from} of the config format, but this version of Git-Tool only understands up to v{CURRENT_VERSION}."
+            ),
+            &["Update Git-Tool to the latest version using `gt update` and then try again."],
+        ));
+    }
+
+    let mut document = document;
+    let mut changes = Vec::new();
+
+    if let Some(root) = document.as_mapping_mut() {
+        for step in STEPS.iter().filter(|s| s.from >= from) {
+            (step.apply)(root, &mut changes);
+        }
+    }
+
+    Ok(Migration {
+        from,
+        to: CURRENT_VERSION,
+        changes,
+        document,
+    })
+}
+
+/// Reads a config file and determines how it should be upgraded, without
+/// modifying it.
+pub fn migrate_file(path: &Path) -> Result<Migration, human_errors::Error> {
+    let content = std::fs::read_to_string(path).wrap_user_err(
+        format!("We could not open your Git-Tool config file '{}' for reading.", path.display()),
+        &["Check that your config file exists and is readable by the user running git-tool before trying again."],
+    )?;
+
+    let document = serde_yaml::from_str(&content).map_err(|e| {
+        human_errors::wrap_user(
+            e,
+            format!(
+                "We couldn't parse your configuration file '{}' due to a YAML parser error.",
+                path.display()
+            ),
+            &["Check that the YAML in your configuration file is correctly formatted."],
+        )
+    })?;
+
+    migrate(document)
+}
+
+/// Git-Tool v3 identifies services by a short `name` rather than their `domain`,
+/// drops the separate `httpUrl` (and the `http_transport` flag which selected it)
+/// and uses the order of services and apps, rather than a `default` field, to
+/// choose the default.
+fn v1_to_v2(root: &mut Mapping, changes: &mut Vec<String>) {
+    if let Some(Value::String(schema)) = root.get_mut("$schema") {
+        *schema = schema.replace("/v1/", "/v2/");
+        changes.push("Updated $schema to the v2 config schema".to_string());
+    }
+
+    let http_transport = root
+        .get("features")
+        .and_then(|f| f.as_mapping())
+        .and_then(|f| f.get("http_transport"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or_default();
+
+    let mut domains = Vec::new();
+    if let Some(Value::Sequence(services)) = root.get_mut("services") {
+        for service in services.iter_mut() {
+            let Some(service) = service.as_mapping_mut() else {
+                continue;
+            };
+
+            let Some(domain) = service.remove("domain") else {
+                continue;
+            };
+
+            let name = domain.as_str().unwrap_or_default().to_string();
+            if !service.contains_key("name") {
+                service.insert("name".into(), domain);
+                changes.push(format!(
+                    "Renamed the `domain` of the '{name}' service to `name`"
+                ));
+            }
+
+            if let Some(http_url) = service.remove("httpUrl") {
+                if http_transport {
+                    service.insert("gitUrl".into(), http_url);
+                    changes.push(format!(
+                        "Replaced the `gitUrl` of the '{name}' service with its `httpUrl` (because http_transport was enabled)"
+                    ));
+                } else {
+                    changes.push(format!("Removed the `httpUrl` of the '{name}' service"));
+                }
+            }
+
+            if name == "github.com" && !service.contains_key("api") {
+                let mut api = Mapping::new();
+                api.insert("kind".into(), "GitHub/v3".into());
+                api.insert("url".into(), "https://api.github.com".into());
+                service.insert("api".into(), Value::Mapping(api));
+                changes.push(format!("Added the GitHub API to the '{name}' service"));
+            }
+
+            domains.push(name);
+        }
+    }
+
+    for list in ["services", "apps"] {
+        if let Some(Value::Sequence(items)) = root.get_mut(list) {
+            let default = items.iter().position(|item| {
+                item.get("default")
+                    .and_then(|d| d.as_bool())
+                    .unwrap_or_default()
+            });
+
+            for item in items.iter_mut() {
+                if let Some(item) = item.as_mapping_mut() {
+                    item.remove("default");
+                }
+            }
+
+            if let Some(default) = default {
+                let item = items.remove(default);
+                let name = item
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                items.insert(0, item);
+                changes.push(format!(
+                    "Moved the default entry '{name}' to the start of your {list}"
+                ));
+            }
+        }
+    }
+
+    if let Some(Value::Mapping(aliases)) = root.get_mut("aliases") {
+        for (alias, target) in aliases.iter_mut() {
+            let Value::String(target) = target else {
+                continue;
+            };
+
+            if let Some((domain, path)) = target.split_once('/')
+                && domains.iter().any(|d| d == domain)
+            {
+                *target = format!("{domain}:{path}");
+                changes.push(format!(
+                    "Updated the '{}' alias to use the `service:path` format",
+                    alias.as_str().unwrap_or_default()
+                ));
+            }
+        }
+    }
+
+    if let Some(Value::Mapping(features)) = root.get_mut("features") {
+        for flag in ["http_transport", "native_clone"] {
+            if features.remove(flag).is_some() {
+                changes.push(format!("Removed the unsupported `{flag}` feature flag"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(yaml: &str) -> Value {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn detect_versions() {
+        assert_eq!(
+            detect_version(&document(
+                "$schema: https://schemas.sierrasoftworks.com/git-tool/v1/config.schema.json"
+            )),
+            1
+        );
+        assert_eq!(
+            detect_version(&document(
+                "$schema: https://schemas.sierrasoftworks.com/git-tool/v2/config.schema.json"
+            )),
+            2
+        );
+        assert_eq!(
+            detect_version(&document("services:\n  - domain: github.com\n")),
+            1
+        );
+        assert_eq!(
+            detect_version(&document("services:\n  - name: gh\n")),
+            CURRENT_VERSION
+        );
+    }
+
+    #[test]
+    fn migrate_current() {
+        let migration = migrate(document("directory: /dev\nservices:\n  - name: gh\n")).unwrap();
+        assert!(!migration.is_required());
+        assert!(migration.changes.is_empty());
+    }
+
+    #[test]
+    fn migrate_newer() {
+        migrate(document(
+            "$schema: https://schemas.sierrasoftworks.com/git-tool/v99/config.schema.json",
+        ))
+        .expect_err("newer config files should not be accepted");
+    }
+
+    #[test]
+    fn migrate_v1_to_v2() {
+        let migration = migrate(document(
+            r#"
+$schema: https://schemas.sierrasoftworks.com/git-tool/v1/config.schema.json
+directory: /dev
+services:
+  - domain: gitlab.com
+    website: "https://{{ .Service.Domain }}/{{ .Repo.FullName }}"
+    httpUrl: "https://{{ .Service.Domain }}/{{ .Repo.FullName }}.git"
+    gitUrl: "git@{{ .Service.Domain }}:{{ .Repo.FullName }}.git"
+    pattern: "*/*"
+  - domain: github.com
+    website: "https://{{ .Service.Domain }}/{{ .Repo.FullName }}"
+    httpUrl: "https://{{ .Service.Domain }}/{{ .Repo.FullName }}.git"
+    gitUrl: "git@{{ .Service.Domain }}:{{ .Repo.FullName }}.git"
+    default: true
+    pattern: "*/*"
+apps:
+  - name: shell
+    command: sh
+  - name: code
+    command: code
+    default: true
+aliases:
+  gt: github.com/SierraSoftworks/git-tool
+features:
+  http_transport: false
+  native_clone: false
+  create_remote: true
+"#,
+        ))
+        .unwrap();
+
+        assert!(migration.is_required());
+        assert_eq!(migration.from, 1);
+        assert_eq!(migration.to, 2);
+
+        let doc = migration.get_document();
+        assert_eq!(
+            doc["$schema"].as_str(),
+            Some("https://schemas.sierrasoftworks.com/git-tool/v2/config.schema.json")
+        );
+
+        let github = &doc["services"][0];
+        assert_eq!(github["name"].as_str(), Some("github.com"));
+        assert!(github.get("domain").is_none());
+        assert!(github.get("httpUrl").is_none());
+        assert!(github.get("default").is_none());
+        assert_eq!(
+            github["gitUrl"].as_str(),
+            Some("git@{{ .Service.Domain }}:{{ .Repo.FullName }}.git")
+        );
+        assert_eq!(github["api"]["kind"].as_str(), Some("GitHub/v3"));
+
+        let gitlab = &doc["services"][1];
+        assert_eq!(gitlab["name"].as_str(), Some("gitlab.com"));
+        assert!(gitlab.get("api").is_none());
+
+        assert_eq!(doc["apps"][0]["name"].as_str(), Some("code"));
+        assert!(doc["apps"][0].get("default").is_none());
+
+        assert_eq!(
+            doc["aliases"]["gt"].as_str(),
+            Some("github.com:SierraSoftworks/git-tool")
+        );
+
+        assert!(doc["features"].get("http_transport").is_none());
+        assert!(doc["features"].get("native_clone").is_none());
+        assert_eq!(doc["features"]["create_remote"].as_bool(), Some(true));
+
+        let config: super::super::Config = serde_yaml::from_value(doc.clone())
+            .expect("the migrated config should be readable by this version of Git-Tool");
+        assert_eq!(
+            config.get_service("github.com").unwrap().git_url,
+            "git@{{ .Service.Domain }}:{{ .Repo.FullName }}.git"
+        );
+    }
+
+    #[test]
+    fn migrate_v1_to_v2_http_transport() {
+        let migration = migrate(document(
+            r#"
+directory: /dev
+services:
+  - domain: github.com
+    website: "https://{{ .Service.Domain }}/{{ .Repo.FullName }}"
+    httpUrl: "https://{{ .Service.Domain }}/{{ .Repo.FullName }}.git"
+    gitUrl: "git@{{ .Service.Domain }}:{{ .Repo.FullName }}.git"
+    pattern: "*/*"
+features:
+  http_transport: true
+"#,
+        ))
+        .unwrap();
+
+        assert_eq!(migration.from, 1);
+        assert_eq!(
+            migration.get_document()["services"][0]["gitUrl"].as_str(),
+            Some("https://{{ .Service.Domain }}/{{ .Repo.FullName }}.git")
+        );
+    }
+
+    #[tokio::test]
+    async fn save() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.yml");
+        let original = "directory: /dev\nservices:\n  - domain: github.com\n    website: https://github.com\n    gitUrl: git@github.com\n    pattern: \"*/*\"\n";
+        std::fs::write(&path, original).unwrap();
+
+        let migration = migrate_file(&path).unwrap();
+        let backup = migration.save(&path).await.unwrap();
+
+        assert_eq!(backup, temp.path().join("config.yml.v1.bak"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+
+        let upgraded = migrate_file(&path).unwrap();
+        assert!(
+            !upgraded.is_required(),
+            "the saved config should not need to be migrated again"
+        );
+    }
+}
@@ -8,7 +8,11 @@ pub mod features;
 mod http;
 mod identifier;
 mod launcher;
+pub mod migration;
+mod ports;
+mod profile;
 mod prompt;
+mod registry_source;
 mod repo;
 mod repo_config;
 mod resolve;
@@ -16,6 +20,7 @@ mod scratchpad;
 mod service;
 mod target;
 mod templates;
+mod validation;
 mod worktree;
 
 use std::{io::Write, sync::Arc};
@@ -32,18 +37,21 @@ pub use analytics::Analytics;
 pub use app::App;
 pub use auth::KeyChain;
 pub use branch::Branch;
-pub use config::Config;
+pub use config::{Config, ConfigValue};
 pub use identifier::Identifier;
 pub use launcher::Launcher;
+pub use ports::PortRegistry;
 pub use prompt::Prompter;
+pub use registry_source::RegistrySource;
 pub use repo::Repo;
 pub use repo_config::RepoConfig;
 use resolve::ResolverBackend;
 pub use resolve::{ResolveMany, Resolver};
 pub use scratchpad::Scratchpad;
-pub use service::{Service, ServiceAPI};
+pub use service::{Service, ServiceAPI, ServiceIdentity};
 pub use target::{Target, TempMode, TempTarget};
 pub use templates::{render, render_list};
+pub use validation::{Diagnostic, Severity, validate_config, validate_template};
 pub use worktree::Worktree;
 
 pub struct Core {
@@ -54,6 +62,7 @@ pub struct Core {
     keychain: Arc<dyn KeyChain + Send + Sync>,
     http_client: Arc<dyn HttpClient + Send + Sync>,
     analytics: Analytics,
+    dry_run: bool,
 }
 
 impl Core {
@@ -92,4 +101,10 @@ impl Core {
     pub fn analytics(&self) -> &Analytics {
         &self.analytics
     }
+
+    /// Whether Git-Tool is running in `--dry-run` mode, in which case tasks and
+    /// application launches should describe their effects rather than apply them.
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
 }
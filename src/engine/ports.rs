@@ -0,0 +1,343 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use human_errors::ResultExt;
+
+use super::Config;
+use crate::errors::HumanErrorResultExt;
+
+/// The name of the file, within the worktree directory, which records the ports
+/// that have been allocated to each worktree.
+const PORT_REGISTRY_FILE: &str = ".ports.json";
+
+/// The ports allocated to each worktree, keyed by the worktree's canonical path.
+type Allocations = BTreeMap<String, BTreeMap<String, u16>>;
+
+/// Keeps track of the ports which have been allocated to each worktree so that
+/// several worktrees of the same service can run side by side without fighting
+/// over the same port. Allocations are persisted in the worktree directory and
+/// remain stable for the lifetime of a worktree.
+pub struct PortRegistry {
+    path: PathBuf,
+}
+
+impl PortRegistry {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// The registry used for the worktrees in the configured worktree directory.
+    pub fn for_config(config: &Config) -> Self {
+        Self::new(config.get_worktree_directory().join(PORT_REGISTRY_FILE))
+    }
+
+    /// Retrieves the ports which are currently allocated to the worktree at `worktree`.
+    pub fn get(&self, worktree: &Path) -> BTreeMap<String, u16> {
+        self.load()
+            .ok()
+            .and_then(|mut allocations| allocations.remove(&registry_key(worktree)))
+            .unwrap_or_default()
+    }
+
+    /// Allocates a port for each of the named `ports` to the worktree at `worktree`.
+    /// Ports which have already been allocated to the worktree are kept, while new
+    /// ones are assigned the first port above their base which isn't in use by any
+    /// other worktree (leaving the base itself for the primary checkout).
+    pub fn allocate(
+        &self,
+        worktree: &Path,
+        ports: &BTreeMap<String, u16>,
+    ) -> Result<BTreeMap<String, u16>, human_errors::Error> {
+        let key = registry_key(worktree);
+        self.update(|allocations| {
+            let existing = allocations.remove(&key).unwrap_or_default();
+
+            let mut in_use: HashSet<u16> = allocations
+                .values()
+                .flat_map(|ports| ports.values().copied())
+                .collect();
+
+            let mut allocated = BTreeMap::new();
+            for (name, base) in ports {
+                let port = match existing.get(name) {
+                    Some(port) if !in_use.contains(port) => *port,
+                    _ => (base.saturating_add(1)..=u16::MAX)
+                        .find(|port| !in_use.contains(port))
+                        .ok_or_else(|| {
+                            human_errors::user(
+                                format!(
+                                    "Could not allocate a port for '{name}' because every port above {base} is already in use by another worktree."
+                                ),
+                                &["Remove some of your unused worktrees with `gt worktree gc` or `gt prune` and then try again."],
+                            )
+                        })?,
+                };
+
+                in_use.insert(port);
+                allocated.insert(name.clone(), port);
+            }
+
+            if !allocated.is_empty() {
+                allocations.insert(key.clone(), allocated.clone());
+            }
+
+            Ok(allocated)
+        })
+    }
+
+    /// Moves the ports allocated to the worktree at `from` so that they belong to the
+    /// worktree at `to`, for when a worktree has been moved.
+    pub fn rename(&self, from: &Path, to: &Path) -> Result<(), human_errors::Error> {
+        self.update(|allocations| {
+            if let Some(ports) = allocations.remove(&registry_key(from)) {
+                allocations.insert(registry_key(to), ports);
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Releases any ports which were allocated to the worktree at `worktree`, making
+    /// them available to other worktrees.
+    pub fn release(&self, worktree: &Path) -> Result<(), human_errors::Error> {
+        self.update(|allocations| {
+            allocations.remove(&registry_key(worktree));
+            Ok(())
+        })
+    }
+
+    /// Applies `change` to the allocations while holding an exclusive lock on the
+    /// registry, so that concurrent Git-Tool processes can't hand out the same port
+    /// or overwrite one another's allocations. The registry is only written if the
+    /// allocations were changed.
+    fn update<T, F>(&self, change: F) -> Result<T, human_errors::Error>
+    where
+        F: FnOnce(&mut Allocations) -> Result<T, human_errors::Error>,
+    {
+        let _lock = self.lock()?;
+
+        let mut allocations = self.load()?;
+        let original = allocations.clone();
+        let result = change(&mut allocations)?;
+
+        if allocations != original {
+            self.save(&allocations)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Takes an exclusive lock on the registry, which is released when the returned
+    /// file is dropped.
+    fn lock(&self) -> Result<std::fs::File, human_errors::Error> {
+        self.create_parent()?;
+
+        let path = self.path.with_extension("json.lock");
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .wrap_user_err(
+                format!(
+                    "Could not open the worktree port registry lock '{}' due to an OS-level error.",
+                    path.display()
+                ),
+                &["Make sure that Git-Tool has permission to write to your worktree directory and then try again."],
+            )?;
+
+        file.lock().wrap_user_err(
+            format!(
+                "Could not lock the worktree port registry '{}' due to an OS-level error.",
+                self.path.display()
+            ),
+            &["Make sure that no other Git-Tool processes are stuck and then try again."],
+        )?;
+
+        Ok(file)
+    }
+
+    fn load(&self) -> Result<Allocations, human_errors::Error> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+
+        let content = std::fs::read(&self.path).wrap_user_err(
+            format!(
+                "Could not read the worktree port registry '{}' due to an OS-level error.",
+                self.path.display()
+            ),
+            &["Make sure that Git-Tool has permission to read the file and then try again."],
+        )?;
+
+        serde_json::from_slice(&content).wrap_user_err(
+            format!(
+                "Could not parse the worktree port registry '{}'.",
+                self.path.display()
+            ),
+            &["Delete the file to reset your worktree port allocations and then try again."],
+        )
+    }
+
+    /// Writes the allocations to a temporary file alongside the registry and then
+    /// moves it into place, so that a crash part way through never leaves a
+    /// truncated registry behind.
+    fn save(&self, allocations: &Allocations) -> Result<(), human_errors::Error> {
+        let parent = self.create_parent()?;
+
+        let content = serde_json::to_vec_pretty(allocations).to_human_error()?;
+        let mut file = tempfile::NamedTempFile::new_in(&parent)
+            .and_then(|mut file| file.write_all(&content).map(|_| file))
+            .wrap_user_err(
+                format!(
+                    "Could not write the worktree port registry '{}' due to an OS-level error.",
+                    self.path.display()
+                ),
+                &["Make sure that Git-Tool has permission to write to your worktree directory and then try again."],
+            )?;
+        file.as_file_mut().sync_all().to_human_error()?;
+
+        file.persist(&self.path).wrap_user_err(
+            format!(
+                "Could not write the worktree port registry '{}' due to an OS-level error.",
+                self.path.display()
+            ),
+            &["Make sure that Git-Tool has permission to write to your worktree directory and then try again."],
+        )?;
+
+        Ok(())
+    }
+
+    fn create_parent(&self) -> Result<PathBuf, human_errors::Error> {
+        let parent = self
+            .path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        std::fs::create_dir_all(&parent).wrap_user_err(
+            format!(
+                "Could not create the worktree directory '{}' due to an OS-level error.",
+                parent.display()
+            ),
+            &["Make sure that Git-Tool has permission to write to your worktree directory and then try again."],
+        )?;
+
+        Ok(parent)
+    }
+}
+
+/// Builds the name of the environment variable used to expose a named port to
+/// applications, for example `web` becomes `WEB_PORT`.
+pub(crate) fn port_variable(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("{name}_PORT")
+}
+
+/// Worktrees are identified by their canonical path so that the same worktree is
+/// recognized regardless of whether its path was reported by git or Git-Tool. We
+/// fall back to canonicalizing the parent directory for worktrees which have
+/// already been deleted.
+fn registry_key(worktree: &Path) -> String {
+    std::fs::canonicalize(worktree)
+        .ok()
+        .or_else(|| {
+            let parent = std::fs::canonicalize(worktree.parent()?).ok()?;
+            Some(parent.join(worktree.file_name()?))
+        })
+        .unwrap_or_else(|| worktree.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn allocate_unique_and_persistent_ports() {
+        let temp = tempdir().unwrap();
+        let registry = PortRegistry::new(temp.path().join(PORT_REGISTRY_FILE));
+        let ports: BTreeMap<String, u16> = [("web".to_string(), 3000), ("db".to_string(), 5432)]
+            .into_iter()
+            .collect();
+
+        let a = registry.allocate(&temp.path().join("a"), &ports).unwrap();
+        let b = registry.allocate(&temp.path().join("b"), &ports).unwrap();
+
+        assert_eq!(a.get("web"), Some(&3001));
+        assert_eq!(a.get("db"), Some(&5433));
+        assert_eq!(b.get("web"), Some(&3002));
+        assert_eq!(b.get("db"), Some(&5434));
+
+        assert_eq!(
+            registry.allocate(&temp.path().join("a"), &ports).unwrap(),
+            a,
+            "allocations should be stable for a worktree"
+        );
+        assert_eq!(registry.get(&temp.path().join("b")), b);
+
+        registry.release(&temp.path().join("a")).unwrap();
+        assert!(registry.get(&temp.path().join("a")).is_empty());
+
+        registry
+            .rename(&temp.path().join("b"), &temp.path().join("d"))
+            .unwrap();
+        assert!(registry.get(&temp.path().join("b")).is_empty());
+        assert_eq!(registry.get(&temp.path().join("d")), b);
+
+        let c = registry.allocate(&temp.path().join("c"), &ports).unwrap();
+        assert_eq!(c.get("web"), Some(&3001), "released ports should be reused");
+    }
+
+    #[test]
+    fn allocate_concurrently() {
+        let temp = tempdir().unwrap();
+        let path = temp.path().join(PORT_REGISTRY_FILE);
+        let ports: BTreeMap<String, u16> = [("web".to_string(), 3000)].into_iter().collect();
+
+        let allocated: Vec<u16> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|i| {
+                    let registry = PortRegistry::new(path.clone());
+                    let worktree = temp.path().join(format!("worktree-{i}"));
+                    let ports = &ports;
+                    scope.spawn(move || registry.allocate(&worktree, ports).unwrap()["web"])
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let unique: HashSet<u16> = allocated.iter().copied().collect();
+        assert_eq!(unique.len(), 8, "each worktree should get its own port");
+
+        let registry = PortRegistry::new(path);
+        for i in 0..8 {
+            assert!(
+                !registry
+                    .get(&temp.path().join(format!("worktree-{i}")))
+                    .is_empty(),
+                "no allocation should be lost"
+            );
+        }
+    }
+
+    #[test]
+    fn port_variable_names() {
+        assert_eq!(port_variable("web"), "WEB_PORT");
+        assert_eq!(port_variable("admin-api"), "ADMIN_API_PORT");
+    }
+}
@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::app;
+use super::config::{deserialize_optional_expanded_path, serialize_optional_expanded_path};
+use super::features;
+use super::service;
+
+/// A named set of overrides which is layered on top of your config file when it
+/// is selected, allowing you to keep (for example) work and personal setups apart.
+///
+/// Any field which is not set on the profile falls back to the value in the
+/// root of your config file, while `services` and `apps` replace the root lists
+/// entirely when they are provided (matching the behaviour of [`super::Config::extend`]).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct Profile {
+    #[serde(
+        default,
+        rename = "directory",
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_expanded_path",
+        serialize_with = "serialize_optional_expanded_path"
+    )]
+    #[schemars(with = "Option<PathBuf>")]
+    pub(super) dev_directory: Option<PathBuf>,
+    #[serde(
+        default,
+        rename = "scratchpads",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub(super) scratch_directory: Option<PathBuf>,
+    #[serde(
+        default,
+        rename = "worktrees",
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_expanded_path",
+        serialize_with = "serialize_optional_expanded_path"
+    )]
+    #[schemars(with = "Option<PathBuf>")]
+    pub(super) worktree_directory: Option<PathBuf>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) services: Vec<Arc<service::Service>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(super) apps: Vec<Arc<app::App>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(super) features: Option<features::Features>,
+}
+
+impl Profile {
+    /// Determines whether the provided directory falls within this profile's
+    /// development directory, which is used to automatically select a profile
+    /// based on where Git-Tool is being run from.
+    pub fn contains(&self, dir: &Path) -> bool {
+        match &self.dev_directory {
+            Some(dev_dir) => {
+                dir.starts_with(dev_dir)
+                    || dev_dir
+                        .canonicalize()
+                        .map(|dev_dir| dir.starts_with(dev_dir))
+                        .unwrap_or_default()
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use super::config::{deserialize_expanded_path, serialize_expanded_path};
+
+/// A registry of app and service templates which `gt config add` and `gt config list`
+/// search before falling back to the built-in Git-Tool registry.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RegistrySource {
+    /// A directory on your machine containing `apps/*.yaml` and `services/*.yaml` templates.
+    Directory {
+        #[serde(
+            deserialize_with = "deserialize_expanded_path",
+            serialize_with = "serialize_expanded_path"
+        )]
+        #[schemars(with = "PathBuf")]
+        path: PathBuf,
+    },
+
+    /// A Git repository containing templates, either in its root or in a `registry/` directory.
+    Git {
+        git: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branch: Option<String>,
+    },
+
+    /// An HTTP endpoint which serves an `index.yaml` listing its templates, alongside
+    /// the templates themselves.
+    Http { url: String },
+}
+
+impl Display for RegistrySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistrySource::Directory { path } => write!(f, "{}", path.display()),
+            RegistrySource::Git {
+                git,
+                branch: Some(branch),
+            } => write!(f, "{git}#{branch}"),
+            RegistrySource::Git { git, branch: None } => write!(f, "{git}"),
+            RegistrySource::Http { url } => write!(f, "{url}"),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
 use human_errors::ResultExt;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
@@ -14,7 +15,7 @@ pub const REPO_CONFIG_FILE: &str = "git-tool.yml";
 /// repository (in a `git-tool.yml` file). It allows a repository to define a
 /// series of named tasks which can be executed using the `gt task` command, as
 /// well as automation which should be applied when a worktree is created.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct RepoConfig {
     #[serde(default)]
     tasks: BTreeMap<String, RepoTask>,
@@ -27,7 +28,7 @@ pub struct RepoConfig {
 /// worktree created from it). Tasks mirror the structure of an [`App`] and are
 /// executed through the same launcher, giving them templating and signal
 /// forwarding for free.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct RepoTask {
     command: String,
 
@@ -40,7 +41,7 @@ pub struct RepoTask {
 
 /// Configuration which controls the automation applied when a worktree is
 /// created for a repository.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct WorktreeConfig {
     /// A list of paths (relative to the repository root) which should be
     /// symlinked from the worktree back to the original repository. This is
@@ -49,6 +50,24 @@ pub struct WorktreeConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     symlinks: Vec<String>,
 
+    /// A list of paths (relative to the repository root) which should be
+    /// copied from the original repository into the worktree. Unlike symlinks,
+    /// copies are free to diverge, which suits files like `.env.local`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    copies: Vec<String>,
+
+    /// A list of files (relative to the repository root) which should be
+    /// rendered as templates, with the worktree's details available through
+    /// `{{ .Worktree }}`, and written to the same path within the worktree.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    templates: Vec<String>,
+
+    /// Named ports (and the base port they should be allocated above) which each
+    /// worktree should receive its own unique port for, allowing several
+    /// worktrees of the same service to run side by side.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    ports: BTreeMap<String, u16>,
+
     /// A list of task names which should be executed (within the context of the
     /// worktree) once it has been created.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -154,6 +173,24 @@ impl WorktreeConfig {
         &self.symlinks
     }
 
+    /// The paths (relative to the repository root) which should be copied from
+    /// the original repository into the worktree.
+    pub fn copies(&self) -> &[String] {
+        &self.copies
+    }
+
+    /// The files (relative to the repository root) which should be rendered into
+    /// the worktree as templates.
+    pub fn templates(&self) -> &[String] {
+        &self.templates
+    }
+
+    /// The named ports, along with their base port, which should be allocated to
+    /// each worktree.
+    pub fn ports(&self) -> &BTreeMap<String, u16> {
+        &self.ports
+    }
+
     /// The names of the tasks which should be run once the worktree is created.
     pub fn tasks(&self) -> &[String] {
         &self.tasks
@@ -161,7 +198,7 @@ impl WorktreeConfig {
 }
 
 /// Computes the hex-encoded SHA-256 hash of the provided bytes.
-fn hash_bytes(bytes: &[u8]) -> String {
+pub(super) fn hash_bytes(bytes: &[u8]) -> String {
     let digest = Sha256::digest(bytes);
     let mut output = String::with_capacity(digest.len() * 2);
     for byte in digest.iter() {
@@ -190,6 +227,12 @@ worktree:
   symlinks:
     - node_modules
     - target
+  copies:
+    - .env.local
+  templates:
+    - .vscode/settings.json
+  ports:
+    web: 3000
   tasks:
     - build
 "#;
@@ -207,6 +250,9 @@ worktree:
 
         let worktree = config.worktree().expect("worktree config should exist");
         assert_eq!(worktree.symlinks(), &["node_modules", "target"]);
+        assert_eq!(worktree.copies(), &[".env.local"]);
+        assert_eq!(worktree.templates(), &[".vscode/settings.json"]);
+        assert_eq!(worktree.ports().get("web"), Some(&3000));
         assert_eq!(worktree.tasks(), &["build"]);
     }
 
@@ -227,6 +273,14 @@ worktree:
 
         let c = RepoConfig::from_bytes(b"tasks: {}").unwrap();
         assert_ne!(a.hash().unwrap(), c.hash().unwrap());
+
+        let d = RepoConfig::from_bytes(b"worktree:\n  templates: [.env.local]").unwrap();
+        let e = RepoConfig::from_bytes(b"worktree:\n  copies: [.env.local]").unwrap();
+        assert_ne!(
+            d.hash().unwrap(),
+            e.hash().unwrap(),
+            "copies and templates must both be covered by the trust hash"
+        );
     }
 
     #[test]
@@ -239,7 +293,10 @@ worktree:
 # This repository builds and tests with cargo.
 worktree:
   tasks: [build]
+  templates: [.vscode/settings.json]
   symlinks: [node_modules, target]
+  copies: [.env.local]
+  ports: { web: 3000 }
 tasks:
   test:
     command: cargo
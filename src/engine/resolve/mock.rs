@@ -1,5 +1,5 @@
 use super::{ResolveMany, Resolver};
-use crate::engine::{Identifier, Repo, Scratchpad, Service, TempMode, TempTarget};
+use crate::engine::{Identifier, Repo, Scratchpad, Service, TempMode, TempTarget, Worktree};
 
 // The mock's expectation methods keep the familiar `get_*` names so that tests
 // read naturally (`mock.expect_get_best_repo()...`); the [`Resolver`]
@@ -17,6 +17,8 @@ mockall::mock! {
         pub fn get_repos_for(&self, service: &Service) -> Result<Vec<Repo>, human_errors::Error>;
         pub fn get_best_repo(&self, identifier: &Identifier) -> Result<Repo, human_errors::Error>;
         pub fn get_current_repo(&self) -> Result<Repo, human_errors::Error>;
+
+        pub fn get_current_worktree(&self) -> Result<Worktree, human_errors::Error>;
     }
 }
 
@@ -44,6 +46,12 @@ impl ResolveMany<&Service, Repo> for MockResolver {
     }
 }
 
+impl Resolver<(), Worktree> for MockResolver {
+    fn resolve(&self, _source: ()) -> Result<Worktree, human_errors::Error> {
+        self.get_current_worktree()
+    }
+}
+
 impl Resolver<(), Scratchpad> for MockResolver {
     fn resolve(&self, _source: ()) -> Result<Scratchpad, human_errors::Error> {
         self.get_current_scratchpad()
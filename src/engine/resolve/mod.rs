@@ -10,7 +10,7 @@
 //! working directory, the current time) are implemented on [`TrueResolver`] and
 //! reached through [`super::Core`]'s forwarding implementations, so that tests can
 //! substitute a [`MockResolver`]. Resolutions which are pure functions of the
-//! configuration (applications, branches, worktrees) are implemented on
+//! configuration (applications, branches, a branch's worktree) are implemented on
 //! [`super::Core`] directly.
 
 mod app;
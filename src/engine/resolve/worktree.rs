@@ -1,5 +1,18 @@
-use super::Resolver;
-use crate::engine::{Branch, Core, Repo, Worktree};
+use std::env;
+use std::path::Path;
+
+use super::{Resolver, TrueResolver};
+use crate::engine::{Branch, Core, PortRegistry, Repo, Worktree};
+use human_errors::{OptionExt, ResultExt};
+use tracing_batteries::prelude::*;
+
+/// Resolves the linked worktree containing the current working directory,
+/// including the ports which have been allocated to it.
+impl Resolver<(), Worktree> for Core {
+    fn resolve(&self, source: ()) -> Result<Worktree, human_errors::Error> {
+        self.resolve_with_events(source, "current")
+    }
+}
 
 /// Resolves the worktree a branch maps to within a repository, using the
 /// configured worktree directory.
@@ -9,9 +22,110 @@ impl Resolver<(&Repo, &Branch), Worktree> for Core {
     }
 }
 
+impl Resolver<(), Worktree> for TrueResolver {
+    #[tracing::instrument(err, skip(self, _source))]
+    fn resolve(&self, _source: ()) -> Result<Worktree, human_errors::Error> {
+        let cwd = env::current_dir().map_err(|err| human_errors::wrap_system(
+                err,
+                "Could not determine your current working directory due to an OS-level error.",
+                &["Please report this issue on GitHub so that we can work with you to investigate the cause and resolve it."],
+            ))?;
+
+        self.worktree_from_path(&cwd).wrap_user_err(
+            format!(
+                "Current directory ('{}') is not a valid worktree.",
+                cwd.display()
+            ),
+            &["Make sure that you are currently within a worktree of a repository contained within your development directory."],
+        )
+    }
+}
+
+impl TrueResolver {
+    /// Constructs the [`Worktree`] which contains `path` by following the `.git`
+    /// file at the root of a linked worktree back to its primary checkout, which
+    /// must be a repository within the development directory.
+    pub(super) fn worktree_from_path(&self, path: &Path) -> Result<Worktree, human_errors::Error> {
+        debug!(
+            "Constructing worktree object from path '{}'",
+            path.display()
+        );
+        let root = path
+            .ancestors()
+            .find(|dir| dir.join(".git").exists())
+            .ok_or_user_err(
+                "Current directory is not within a git repository.",
+                &["Make sure that you are currently within a worktree of a repository contained within your development directory."],
+            )?;
+
+        let git_dir = std::fs::read_to_string(root.join(".git"))
+            .ok()
+            .and_then(|dotgit| {
+                dotgit
+                    .trim()
+                    .strip_prefix("gitdir:")
+                    .map(|dir| root.join(dir.trim()))
+            })
+            .ok_or_user_err(
+                "Current directory is the primary checkout of a repository rather than a linked worktree.",
+                &["Make sure that you are currently within a worktree created using 'gt worktree'."],
+            )?;
+
+        let common_dir = std::fs::read_to_string(git_dir.join("commondir"))
+            .wrap_user_err(
+                format!(
+                    "Could not determine the repository which the worktree at '{}' belongs to.",
+                    root.display()
+                ),
+                &["Make sure that the worktree has not been corrupted, running 'git worktree repair' if necessary."],
+            )
+            .map(|dir| git_dir.join(dir.trim()))?;
+
+        let primary = common_dir
+            .canonicalize()
+            .ok()
+            .and_then(|dir| dir.parent().map(|p| p.to_path_buf()))
+            .ok_or_user_err(
+                format!(
+                    "Could not locate the primary checkout for the worktree at '{}'.",
+                    root.display()
+                ),
+                &["Make sure that the worktree has not been corrupted, running 'git worktree repair' if necessary."],
+            )?;
+
+        let head = std::fs::read_to_string(git_dir.join("HEAD")).wrap_user_err(
+            format!(
+                "Could not determine the branch checked out in the worktree at '{}'.",
+                root.display()
+            ),
+            &["Make sure that the worktree has not been corrupted, running 'git worktree repair' if necessary."],
+        )?;
+
+        let branch: Branch = head
+            .trim()
+            .strip_prefix("ref: refs/heads/")
+            .ok_or_user_err(
+                format!(
+                    "The worktree at '{}' does not have a branch checked out.",
+                    root.display()
+                ),
+                &["Check out a branch within the worktree using 'git switch <branch>'."],
+            )?
+            .parse()?;
+
+        let repo = self.repo_from_path(&primary)?;
+        let ports = PortRegistry::for_config(&self.config).get(root);
+
+        Ok(Worktree::at(&repo, &branch, root.to_path_buf()).with_ports(ports))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::{Config, Target};
+    use crate::git;
+    use std::sync::Arc;
 
     #[test]
     fn resolves_a_worktree_from_repo_and_branch() {
@@ -22,4 +136,61 @@ mod tests {
         let worktree: Worktree = core.resolve((&repo, &branch)).unwrap();
         assert_eq!(worktree.branch().as_str(), "feature/test");
     }
+
+    #[tokio::test]
+    async fn resolves_a_worktree_from_its_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = Config::for_dev_directory(temp.path());
+        let resolver = TrueResolver::new(Arc::new(config.clone()));
+
+        let repo_path = temp
+            .path()
+            .join("gh")
+            .join("sierrasoftworks")
+            .join("git-tool");
+        std::fs::create_dir_all(&repo_path).unwrap();
+        git::git_init(&repo_path).await.unwrap();
+        git::git_config_set(&repo_path, "user.name", "Example User")
+            .await
+            .unwrap();
+        git::git_config_set(&repo_path, "user.email", "user@example.com")
+            .await
+            .unwrap();
+        std::fs::write(repo_path.join("README.md"), "testing").unwrap();
+        git::git_add(&repo_path, &vec!["README.md"]).await.unwrap();
+        git::git_commit(&repo_path, "Initial commit", &vec!["README.md"])
+            .await
+            .unwrap();
+
+        let worktree_path = config.get_worktree_directory().join("git-tool-feature");
+        git::git_worktree_add(&repo_path, &worktree_path, "feature/test", true, None)
+            .await
+            .unwrap();
+        PortRegistry::for_config(&config)
+            .allocate(&worktree_path, &[("web".to_string(), 8080)].into())
+            .unwrap();
+
+        std::fs::create_dir_all(worktree_path.join("src")).unwrap();
+        let worktree = resolver
+            .worktree_from_path(&worktree_path.join("src"))
+            .unwrap();
+
+        assert_eq!(worktree.branch().as_str(), "feature/test");
+        assert_eq!(worktree.path(), worktree_path);
+        assert_eq!(
+            worktree.target_repo().to_string(),
+            "gh:sierrasoftworks/git-tool"
+        );
+        assert_eq!(worktree.ports().get("web"), Some(&8081));
+        assert!(
+            worktree
+                .environment()
+                .contains(&("WEB_PORT".to_string(), "8081".to_string()))
+        );
+
+        assert!(
+            resolver.worktree_from_path(&repo_path).is_err(),
+            "the primary checkout is not a linked worktree"
+        );
+    }
 }
@@ -1,7 +1,8 @@
 use super::{Repo, templates};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Service {
     pub name: String,
     pub website: String,
@@ -10,14 +11,70 @@ pub struct Service {
     pub pattern: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api: Option<ServiceAPI>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<ServiceIdentity>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct ServiceAPI {
     pub kind: String,
     pub url: String,
 }
 
+/// The git identity (and commit signing configuration) which should be used for
+/// repositories hosted on a service, written into each repository's local git
+/// config so that commits are attributed correctly regardless of your global
+/// settings.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq, Eq)]
+pub struct ServiceIdentity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(rename = "signingKey", skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+    #[serde(rename = "gpgFormat", skip_serializing_if = "Option::is_none")]
+    pub gpg_format: Option<String>,
+}
+
+impl ServiceIdentity {
+    /// The git config entries which apply this identity to a repository. Commit
+    /// signing is enabled whenever a signing key has been provided.
+    pub fn git_config(&self) -> Vec<(&'static str, &str)> {
+        let mut entries = Vec::new();
+
+        if let Some(name) = &self.name {
+            entries.push(("user.name", name.as_str()));
+        }
+
+        if let Some(email) = &self.email {
+            entries.push(("user.email", email.as_str()));
+        }
+
+        if let Some(signing_key) = &self.signing_key {
+            entries.push(("user.signingkey", signing_key.as_str()));
+            entries.push(("commit.gpgsign", "true"));
+        }
+
+        if let Some(gpg_format) = &self.gpg_format {
+            entries.push(("gpg.format", gpg_format.as_str()));
+        }
+
+        entries
+    }
+}
+
+impl std::fmt::Display for ServiceIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.name, &self.email) {
+            (Some(name), Some(email)) => write!(f, "{name} <{email}>"),
+            (Some(name), None) => write!(f, "{name}"),
+            (None, Some(email)) => write!(f, "<{email}>"),
+            (None, None) => write!(f, "(signing only)"),
+        }
+    }
+}
+
 impl Service {
     pub fn get_website(&self, r: &Repo) -> Result<String, human_errors::Error> {
         templates::render(
@@ -58,6 +115,7 @@ mod tests {
                 kind: "GitHub/v3".into(),
                 url: "https://api.github.com".into(),
             }),
+            identity: None,
         };
 
         assert_eq!(&svc.name, "gh");
@@ -88,6 +146,7 @@ mod tests {
             git_url: "git@ssh.dev.azure.com:v3/{{ .Repo.FullName | urlquery }}".into(),
             pattern: "*/*/*".into(),
             api: None,
+            identity: None,
         };
 
         let repo = Repo::new(
@@ -100,4 +159,35 @@ mod tests {
             "git@ssh.dev.azure.com:v3/sierrasoftworks/example/git%20tool"
         );
     }
+
+    #[test]
+    fn service_identity() {
+        let svc: Service = serde_yaml::from_str(
+            r#"
+name: gh
+website: "https://github.com/{{ .Repo.FullName }}"
+gitUrl: "git@github.com:{{ .Repo.FullName }}.git"
+pattern: "*/*"
+identity:
+  name: Example User
+  email: user@example.com
+  signingKey: ~/.ssh/id_ed25519.pub
+  gpgFormat: ssh
+"#,
+        )
+        .unwrap();
+
+        let identity = svc.identity.expect("the identity should be parsed");
+        assert_eq!(identity.to_string(), "Example User <user@example.com>");
+        assert_eq!(
+            identity.git_config(),
+            vec![
+                ("user.name", "Example User"),
+                ("user.email", "user@example.com"),
+                ("user.signingkey", "~/.ssh/id_ed25519.pub"),
+                ("commit.gpgsign", "true"),
+                ("gpg.format", "ssh"),
+            ]
+        );
+    }
 }
@@ -8,6 +8,12 @@ pub trait Target: Display {
     fn get_path(&self) -> std::path::PathBuf;
     fn exists(&self) -> bool;
     fn template_context(&self, config: &Config) -> Result<Value, human_errors::Error>;
+
+    /// Environment variables which should be provided to any application launched
+    /// within this target, before the application's own environment is applied.
+    fn environment(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
 }
 
 /// Controls what happens to a [`TempTarget`]'s directory once the target is
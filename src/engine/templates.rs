@@ -1,7 +1,8 @@
-use super::{Config, Repo, Service, Target};
+use super::{Config, Repo, Service, Target, Worktree};
 use gotmpl::{MissingKey, Template, TemplateError, Value};
 use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use tracing_batteries::prelude::*;
 
 const URL_QUERY_ENCODE: &AsciiSet = &CONTROLS
@@ -78,12 +79,38 @@ pub fn target_context(target: &(impl Target + ?Sized)) -> Result<Value, human_er
         target: TargetTemplateContext::new(target),
         repo: None,
         service: None,
+        worktree: None,
     })
 }
 
+pub fn worktree_context(
+    config: &Config,
+    worktree: &Worktree,
+) -> Result<Value, human_errors::Error> {
+    let repo = worktree.target_repo();
+    let context = WorktreeTemplateContext {
+        branch: worktree.branch().to_string(),
+        path: path_string(repo),
+        ports: worktree.ports().clone(),
+    };
+
+    match config.get_service(&repo.service) {
+        Ok(service) => repo_template_context_with(repo, Some(service), Some(context)),
+        Err(_) => repo_template_context_with(repo, None, Some(context)),
+    }
+}
+
 fn repo_template_context(
     repo: &Repo,
     service: Option<&Service>,
+) -> Result<Value, human_errors::Error> {
+    repo_template_context_with(repo, service, None)
+}
+
+fn repo_template_context_with(
+    repo: &Repo,
+    service: Option<&Service>,
+    worktree: Option<WorktreeTemplateContext>,
 ) -> Result<Value, human_errors::Error> {
     let service_context = ServiceTemplateContext::new(repo, service);
     let website = service.and_then(|service| service.get_website(repo).ok());
@@ -109,10 +136,17 @@ fn repo_template_context(
             service: service_context,
         }),
         service: Some(service_context),
+        worktree,
     })
 }
 
-fn serialize_context(context: &TemplateContext<'_>) -> Result<Value, human_errors::Error> {
+/// Builds the context used to render a branch naming template from a short
+/// description of the work, like `PROJ-123 Fix the login page`.
+pub fn branch_context(description: &str) -> Result<Value, human_errors::Error> {
+    serialize_context(&BranchTemplateContext::new(description))
+}
+
+fn serialize_context(context: &impl Serialize) -> Result<Value, human_errors::Error> {
     gotmpl::to_value(context).map_err(|error| {
         human_errors::wrap_user(
             error.to_string(),
@@ -132,6 +166,73 @@ struct TemplateContext<'a> {
     target: TargetTemplateContext,
     repo: Option<RepoTemplateContext<'a>>,
     service: Option<ServiceTemplateContext<'a>>,
+    worktree: Option<WorktreeTemplateContext>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct BranchTemplateContext {
+    name: String,
+    user: String,
+    ticket: String,
+    slug: String,
+}
+
+impl BranchTemplateContext {
+    fn new(description: &str) -> Self {
+        let description = description.trim();
+        let (ticket, rest) = match description.split_once(char::is_whitespace) {
+            Some((first, rest)) if is_ticket(first) => (first, rest),
+            _ if is_ticket(description) => (description, ""),
+            _ => ("", description),
+        };
+
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default();
+
+        Self {
+            name: description.to_string(),
+            user: slugify(&user),
+            ticket: ticket.trim_start_matches('#').to_string(),
+            slug: slugify(rest),
+        }
+    }
+}
+
+/// Determines whether a word looks like a ticket reference, such as `PROJ-123`,
+/// `#42` or `42`.
+fn is_ticket(word: &str) -> bool {
+    let word = word.trim_start_matches('#');
+    let number = match word.rsplit_once('-') {
+        Some((project, number))
+            if !project.is_empty() && project.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            number
+        }
+        Some(_) => return false,
+        None => word,
+    };
+
+    !number.is_empty() && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Converts free text into a lowercase, hyphen separated, form which is safe to
+/// use in a branch name.
+fn slugify(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct WorktreeTemplateContext {
+    branch: String,
+    path: String,
+    ports: BTreeMap<String, u16>,
 }
 
 #[derive(Serialize)]
@@ -262,6 +363,76 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn render_basic_worktree() -> Result<(), Box<dyn std::error::Error>> {
+        let cfg = Config::default();
+        let repo = Repo::new(
+            "gh:sierrasoftworks/git-tool",
+            PathBuf::from("/test/github.com/sierrasoftworks/git-tool"),
+        );
+        let worktree = Worktree::at(
+            &repo,
+            &"feat/forgejo".parse()?,
+            PathBuf::from("/test/worktrees/git-tool-feat-forgejo"),
+        )
+        .with_ports([("web".to_string(), 3001)].into_iter().collect());
+
+        let context = worktree_context(&cfg, &worktree)?;
+
+        assert_eq!(
+            render("{{ .Worktree.Branch }}", context.clone()).unwrap(),
+            "feat/forgejo"
+        );
+        assert_eq!(
+            render("{{ .Worktree.Path }}", context.clone()).unwrap(),
+            "/test/worktrees/git-tool-feat-forgejo"
+        );
+        assert_eq!(
+            render("{{ .Worktree.Ports.web }}", context.clone()).unwrap(),
+            "3001"
+        );
+        assert_eq!(
+            render("{{ .Repo.FullName }}", context).unwrap(),
+            "sierrasoftworks/git-tool"
+        );
+
+        let repo_context = repo_context(&cfg, &repo)?;
+        assert_eq!(
+            render(
+                "{{ with .Worktree }}worktree{{ else }}repo{{ end }}",
+                repo_context
+            )
+            .unwrap(),
+            "repo"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_branch_name() -> Result<(), Box<dyn std::error::Error>> {
+        let template = "{{ if .Ticket }}{{ .Ticket }}-{{ end }}{{ .Slug }}";
+
+        assert_eq!(
+            render(template, branch_context("PROJ-123 Fix the login page")?).unwrap(),
+            "PROJ-123-fix-the-login-page"
+        );
+        assert_eq!(
+            render(template, branch_context("#42 Don't crash")?).unwrap(),
+            "42-don-t-crash"
+        );
+        assert_eq!(
+            render(template, branch_context("Improve   docs")?).unwrap(),
+            "improve-docs"
+        );
+        assert_eq!(
+            render("{{ .Name }}", branch_context(" PROJ-1 ")?).unwrap(),
+            "PROJ-1"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn render_basic_scratchpad() -> Result<(), Box<dyn std::error::Error>> {
         let scratch = Scratchpad::new("2020w07", PathBuf::from("/test/scratch/2020w07"));
@@ -0,0 +1,1102 @@
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::{Config, Repo, Service, ServiceAPI, features, migration, templates};
+use crate::online::registry::{self, Entry, EntryVariable};
+
+/// The keys which are understood within each part of a config file.
+const CONFIG_KEYS: &[&str] = &[
+    "$schema",
+    "include",
+    "directory",
+    "scratchpads",
+    "worktrees",
+    "services",
+    "apps",
+    "aliases",
+    "registries",
+    "trusted_repos",
+    "branch_template",
+    "templates",
+    "features",
+    "profile",
+    "profiles",
+];
+const PROFILE_KEYS: &[&str] = &[
+    "directory",
+    "scratchpads",
+    "worktrees",
+    "services",
+    "apps",
+    "features",
+];
+const SERVICE_KEYS: &[&str] = &["name", "website", "gitUrl", "pattern", "api", "identity"];
+const SERVICE_API_KEYS: &[&str] = &["kind", "url"];
+const SERVICE_IDENTITY_KEYS: &[&str] = &["name", "email", "signingKey", "gpgFormat"];
+const APP_KEYS: &[&str] = &["name", "command", "args", "environment"];
+const TEMPLATE_KEYS: &[&str] = &["name", "description", "version", "variables", "configs"];
+const TEMPLATE_VARIABLE_KEYS: &[&str] = &["name", "description", "default", "validation"];
+const TEMPLATE_CONFIG_KEYS: &[&str] = &["platform", "app", "service"];
+const TEMPLATE_APP_KEYS: &[&str] = &["name", "command", "args", "environment", "detect"];
+const TEMPLATE_DETECT_KEYS: &[&str] = &["binaries", "version"];
+const TEMPLATE_SERVICE_KEYS: &[&str] = &["name", "website", "gitUrl", "pattern", "api"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem which was found in a config file, along with where it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: Option<PathBuf>,
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+            if let Some((line, column)) = self.location {
+                write!(f, "{line}:{column}:")?;
+            }
+            write!(f, " ")?;
+        }
+
+        write!(f, "{}: {}", self.severity, self.message)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Validates the config files which make up a [`Config`] (your config file and any
+/// files it includes), as well as the effective configuration they produce.
+pub fn validate_config(config: &Config) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for file in config.get_config_files() {
+        match std::fs::read_to_string(file) {
+            Ok(content) => diagnostics.extend(validate_document(config, Some(file), &content)),
+            Err(err) => diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                file: Some(file.to_path_buf()),
+                location: None,
+                message: format!("Could not read the config file: {err}"),
+            }),
+        }
+    }
+
+    for (name, dir) in [
+        ("directory", config.get_dev_directory().to_path_buf()),
+        ("scratchpads", config.get_scratch_directory()),
+        ("worktrees", config.get_worktree_directory()),
+    ] {
+        if !dir.exists() {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                file: None,
+                location: None,
+                message: format!("The {name} directory '{}' does not exist.", dir.display()),
+            });
+        }
+    }
+
+    diagnostics
+}
+
+/// Validates the contents of a single config file.
+pub fn validate_document(config: &Config, file: Option<&Path>, content: &str) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        config,
+        file,
+        locator: Locator::new(content),
+        diagnostics: Vec::new(),
+    };
+
+    let document: Value = match serde_yaml::from_str(content) {
+        Ok(document) => document,
+        Err(err) => {
+            validator.report_yaml_error(err);
+            return validator.diagnostics;
+        }
+    };
+
+    let version = migration::detect_version(&document);
+    if version < migration::CURRENT_VERSION {
+        validator.report(
+            Severity::Warning,
+            &[],
+            format!(
+                "This config file uses v{version} of the config format, run `gt config migrate` to upgrade it to v{}.",
+                migration::CURRENT_VERSION
+            ),
+        );
+        return validator.diagnostics;
+    }
+
+    if let Err(err) = serde_yaml::from_str::<Config>(content) {
+        validator.report_yaml_error(err);
+    }
+
+    validator.validate_config(&document);
+    validator.diagnostics
+}
+
+/// Validates a registry template, like those in the `registry/` directory. Templates in
+/// an `apps/` or `services/` directory must provide an app or service respectively.
+pub fn validate_template(file: Option<&Path>, content: &str) -> Vec<Diagnostic> {
+    let config = Config::default();
+    let mut validator = Validator {
+        config: &config,
+        file,
+        locator: Locator::new(content),
+        diagnostics: Vec::new(),
+    };
+
+    let document: Value = match serde_yaml::from_str(content) {
+        Ok(document) => document,
+        Err(err) => {
+            validator.report_yaml_error(err);
+            return validator.diagnostics;
+        }
+    };
+
+    if let Err(err) = serde_yaml::from_str::<Entry>(content) {
+        validator.report_yaml_error(err);
+    }
+
+    let kind = file
+        .and_then(|f| f.parent())
+        .and_then(|dir| dir.file_name())
+        .and_then(|name| name.to_str());
+
+    validator.validate_template(&document, kind);
+    validator.diagnostics
+}
+
+struct Validator<'a> {
+    config: &'a Config,
+    file: Option<&'a Path>,
+    locator: Locator,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, severity: Severity, path: &[Segment], message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            file: self.file.map(|f| f.to_path_buf()),
+            location: self.locator.locate(path),
+            message,
+        });
+    }
+
+    fn report_yaml_error(&mut self, err: serde_yaml::Error) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            file: self.file.map(|f| f.to_path_buf()),
+            location: err.location().map(|l| (l.line(), l.column())),
+            message: err.to_string(),
+        });
+    }
+
+    fn validate_config(&mut self, document: &Value) {
+        self.validate_keys(document, &[], CONFIG_KEYS, "config file");
+        self.validate_services(document, &[]);
+        self.validate_apps(document, &[]);
+        self.validate_features(document, &[]);
+
+        if let Some(profiles) = document.get("profiles").and_then(|p| p.as_mapping()) {
+            for (name, profile) in profiles {
+                let Some(name) = name.as_str() else {
+                    continue;
+                };
+
+                let path = [Segment::Key("profiles"), Segment::Key(name)];
+                self.validate_keys(profile, &path, PROFILE_KEYS, &format!("profile '{name}'"));
+                self.validate_services(profile, &path);
+                self.validate_apps(profile, &path);
+                self.validate_features(profile, &path);
+            }
+        }
+    }
+
+    fn validate_keys(&mut self, value: &Value, path: &[Segment], known: &[&str], context: &str) {
+        let Some(mapping) = value.as_mapping() else {
+            return;
+        };
+
+        for key in mapping.keys() {
+            let Some(key) = key.as_str() else {
+                continue;
+            };
+
+            if !known.iter().any(|&k| k == key) {
+                let path = [path, &[Segment::Key(key)]].concat();
+                self.report(
+                    Severity::Error,
+                    &path,
+                    format!(
+                        "Unknown key '{key}' in your {context} (expected one of {}).",
+                        known.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn validate_services(&mut self, parent: &Value, path: &[Segment]) {
+        let Some(services) = parent.get("services").and_then(|s| s.as_sequence()) else {
+            return;
+        };
+
+        let mut names = HashSet::new();
+        for (i, service) in services.iter().enumerate() {
+            let path = [path, &[Segment::Key("services"), Segment::Index(i)]].concat();
+            let name = service
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("<unnamed>");
+            let context = format!("service '{name}'");
+
+            self.validate_keys(service, &path, SERVICE_KEYS, &context);
+            if let Some(api) = service.get("api") {
+                let path = [path.as_slice(), &[Segment::Key("api")]].concat();
+                self.validate_keys(api, &path, SERVICE_API_KEYS, &format!("{context} api"));
+            }
+            if let Some(identity) = service.get("identity") {
+                let path = [path.as_slice(), &[Segment::Key("identity")]].concat();
+                self.validate_keys(
+                    identity,
+                    &path,
+                    SERVICE_IDENTITY_KEYS,
+                    &format!("{context} identity"),
+                );
+            }
+
+            if !names.insert(name.to_string()) {
+                self.report(
+                    Severity::Error,
+                    &[path.as_slice(), &[Segment::Key("name")]].concat(),
+                    format!("The {context} is defined more than once, so only the first definition will be used."),
+                );
+            }
+
+            let pattern = service.get("pattern").and_then(|p| p.as_str());
+            if let Some(pattern) = pattern {
+                if pattern.is_empty() || !pattern.split('/').all(|p| p == "*") {
+                    self.report(
+                        Severity::Error,
+                        &[path.as_slice(), &[Segment::Key("pattern")]].concat(),
+                        format!("The pattern '{pattern}' for the {context} is invalid, it should be made up of one or more '*' segments separated by '/' (e.g. '*/*')."),
+                    );
+                    continue;
+                }
+            }
+
+            let repo = sample_repo(name, pattern.unwrap_or("*/*"));
+            let Ok(context_value) = templates::repo_context_without_service(&repo) else {
+                continue;
+            };
+
+            for key in ["website", "gitUrl"] {
+                if let Some(template) = service.get(key).and_then(|t| t.as_str()) {
+                    if let Err(err) = templates::render(template, context_value.clone()) {
+                        self.report(
+                            Severity::Error,
+                            &[path.as_slice(), &[Segment::Key(key)]].concat(),
+                            format!(
+                                "The {key} template for the {context} could not be rendered: {}",
+                                err.message()
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_apps(&mut self, parent: &Value, path: &[Segment]) {
+        let Some(apps) = parent.get("apps").and_then(|a| a.as_sequence()) else {
+            return;
+        };
+
+        let repo = match self.config.get_default_service() {
+            Some(service) => sample_repo(&service.name, &service.pattern),
+            None => sample_repo("gh", "*/*"),
+        };
+        let context_value = templates::repo_context(self.config, &repo).ok();
+
+        let mut names = HashSet::new();
+        for (i, app) in apps.iter().enumerate() {
+            let path = [path, &[Segment::Key("apps"), Segment::Index(i)]].concat();
+            let name = app
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("<unnamed>");
+            let context = format!("app '{name}'");
+
+            self.validate_keys(app, &path, APP_KEYS, &context);
+
+            if !names.insert(name.to_string()) {
+                self.report(
+                    Severity::Error,
+                    &[path.as_slice(), &[Segment::Key("name")]].concat(),
+                    format!("The {context} is defined more than once, so only the first definition will be used."),
+                );
+            }
+
+            let Some(context_value) = &context_value else {
+                continue;
+            };
+
+            if let Some(command) = app.get("command").and_then(|c| c.as_str()) {
+                if let Err(err) = templates::render(command, context_value.clone()) {
+                    self.report(
+                        Severity::Error,
+                        &[path.as_slice(), &[Segment::Key("command")]].concat(),
+                        format!(
+                            "The command template for the {context} could not be rendered: {}",
+                            err.message()
+                        ),
+                    );
+                }
+            }
+
+            for key in ["args", "environment"] {
+                let Some(items) = app.get(key).and_then(|a| a.as_sequence()) else {
+                    continue;
+                };
+
+                for (j, item) in items.iter().enumerate() {
+                    let Some(template) = item.as_str() else {
+                        continue;
+                    };
+
+                    if let Err(err) = templates::render(template, context_value.clone()) {
+                        self.report(
+                            Severity::Error,
+                            &[path.as_slice(), &[Segment::Key(key), Segment::Index(j)]].concat(),
+                            format!("The {key} template '{template}' for the {context} could not be rendered: {}", err.message()),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_template(&mut self, document: &Value, kind: Option<&str>) {
+        self.validate_keys(document, &[], TEMPLATE_KEYS, "template");
+
+        for key in ["name", "description"] {
+            self.validate_required(document, &[], key, "template");
+        }
+
+        match document.get("version") {
+            Some(Value::String(version)) if semver::Version::parse(version).is_ok() => {}
+            Some(_) => self.report(
+                Severity::Warning,
+                &[Segment::Key("version")],
+                "The template's version is not a valid semantic version (like 1.0.0).".into(),
+            ),
+            None => self.report(
+                Severity::Warning,
+                &[],
+                "The template does not have a version, which is needed for `gt config outdated` to find updates.".into(),
+            ),
+        }
+
+        // Templates are rendered using the default value of each of their variables,
+        // or a placeholder if they don't have one.
+        let mut variables = BTreeMap::new();
+        if let Some(items) = document.get("variables").and_then(|v| v.as_sequence()) {
+            for (i, item) in items.iter().enumerate() {
+                let path = [Segment::Key("variables"), Segment::Index(i)];
+                self.validate_keys(item, &path, TEMPLATE_VARIABLE_KEYS, "template variable");
+
+                let Ok(variable) = serde_yaml::from_value::<EntryVariable>(item.clone()) else {
+                    continue;
+                };
+
+                if variable.name.is_empty() {
+                    self.validate_required(item, &path, "name", "template variable");
+                    continue;
+                }
+
+                if let Some(pattern) = &variable.validation
+                    && let Err(err) = regex::Regex::new(pattern)
+                {
+                    self.report(
+                        Severity::Error,
+                        &[path.as_slice(), &[Segment::Key("validation")]].concat(),
+                        format!(
+                            "The validation pattern for the '{}' variable is not a valid regular expression: {err}",
+                            variable.name
+                        ),
+                    );
+                } else if let Some(default) = &variable.default
+                    && let Err(err) = variable.validate(default)
+                {
+                    self.report(
+                        Severity::Error,
+                        &[path.as_slice(), &[Segment::Key("default")]].concat(),
+                        err.message().to_string(),
+                    );
+                }
+
+                variables.insert(
+                    variable.name.clone(),
+                    variable
+                        .default
+                        .clone()
+                        .unwrap_or_else(|| "example.com".to_string()),
+                );
+            }
+        }
+
+        let configs = document
+            .get("configs")
+            .and_then(|c| c.as_sequence())
+            .filter(|c| !c.is_empty());
+        let Some(configs) = configs else {
+            self.report(
+                Severity::Error,
+                &[Segment::Key("configs")],
+                "The template does not provide any configs.".into(),
+            );
+            return;
+        };
+
+        for (i, config) in configs.iter().enumerate() {
+            let path = [Segment::Key("configs"), Segment::Index(i)];
+            self.validate_keys(config, &path, TEMPLATE_CONFIG_KEYS, "template config");
+
+            let platform = config
+                .get("platform")
+                .and_then(|p| p.as_str())
+                .unwrap_or_default();
+            if !registry::is_known_platform(platform) {
+                self.report(
+                    Severity::Error,
+                    &[path.as_slice(), &[Segment::Key("platform")]].concat(),
+                    format!(
+                        "The platform '{platform}' is not supported, it should be one of: {}.",
+                        registry::PLATFORMS.join(", ")
+                    ),
+                );
+            }
+
+            let app = config.get("app").filter(|a| !a.is_null());
+            let service = config.get("service").filter(|s| !s.is_null());
+            match (kind, app, service) {
+                (Some("apps"), None, _) => self.report(
+                    Severity::Error,
+                    &path,
+                    "Templates in the apps/ directory must provide an app in each of their configs.".into(),
+                ),
+                (Some("services"), _, None) => self.report(
+                    Severity::Error,
+                    &path,
+                    "Templates in the services/ directory must provide a service in each of their configs.".into(),
+                ),
+                (_, None, None) => self.report(
+                    Severity::Error,
+                    &path,
+                    "Each of the template's configs must provide an app or a service.".into(),
+                ),
+                _ => {}
+            }
+
+            if let Some(app) = app {
+                let path = [path.as_slice(), &[Segment::Key("app")]].concat();
+                self.validate_template_app(app, &path, &variables);
+            }
+
+            if let Some(service) = service {
+                let path = [path.as_slice(), &[Segment::Key("service")]].concat();
+                self.validate_template_service(service, &path, &variables);
+            }
+        }
+    }
+
+    fn validate_template_app(
+        &mut self,
+        app: &Value,
+        path: &[Segment],
+        variables: &BTreeMap<String, String>,
+    ) {
+        self.validate_keys(app, path, TEMPLATE_APP_KEYS, "template app");
+        for key in ["name", "command"] {
+            self.validate_required(app, path, key, "template app");
+        }
+
+        if let Some(detect) = app.get("detect") {
+            let path = [path, &[Segment::Key("detect")]].concat();
+            self.validate_keys(
+                detect,
+                &path,
+                TEMPLATE_DETECT_KEYS,
+                "template app detection",
+            );
+        }
+
+        let Ok(context) =
+            templates::repo_context_without_service(&sample_repo("example.com", "*/*"))
+        else {
+            return;
+        };
+
+        if let Some(command) = app.get("command").and_then(|c| c.as_str()) {
+            let path = [path, &[Segment::Key("command")]].concat();
+            self.validate_template_string(command, &path, &context, variables);
+        }
+
+        for key in ["args", "environment"] {
+            let Some(items) = app.get(key).and_then(|a| a.as_sequence()) else {
+                continue;
+            };
+
+            for (i, item) in items.iter().enumerate() {
+                if let Some(template) = item.as_str() {
+                    let path = [path, &[Segment::Key(key), Segment::Index(i)]].concat();
+                    self.validate_template_string(template, &path, &context, variables);
+                }
+            }
+        }
+    }
+
+    fn validate_template_service(
+        &mut self,
+        service: &Value,
+        path: &[Segment],
+        variables: &BTreeMap<String, String>,
+    ) {
+        self.validate_keys(service, path, TEMPLATE_SERVICE_KEYS, "template service");
+        for key in ["name", "website", "gitUrl", "pattern"] {
+            self.validate_required(service, path, key, "template service");
+        }
+
+        if let Some(api) = service.get("api") {
+            let path = [path, &[Segment::Key("api")]].concat();
+            self.validate_keys(api, &path, SERVICE_API_KEYS, "template service api");
+
+            let kind = api.get("kind").and_then(|k| k.as_str()).unwrap_or_default();
+            let url = api.get("url").and_then(|u| u.as_str()).unwrap_or_default();
+            let test_service = Service {
+                name: "example.com".into(),
+                website: String::new(),
+                git_url: String::new(),
+                pattern: "*/*".into(),
+                api: Some(ServiceAPI {
+                    kind: kind.into(),
+                    url: url.into(),
+                }),
+                identity: None,
+            };
+
+            if !crate::online::services()
+                .iter()
+                .any(|s| s.handles(&test_service))
+            {
+                self.report(
+                    Severity::Error,
+                    &[path.as_slice(), &[Segment::Key("kind")]].concat(),
+                    format!("The API kind '{kind}' is not supported by Git-Tool (expected one of GitHub/v3, GitLab/v4, Gitea/v1, BitBucket/2.0)."),
+                );
+            }
+
+            if reqwest::Url::parse(&registry::substitute_variables(url, variables)).is_err() {
+                self.report(
+                    Severity::Error,
+                    &[path.as_slice(), &[Segment::Key("url")]].concat(),
+                    format!("The API URL '{url}' is not a valid URL."),
+                );
+            }
+        }
+
+        let pattern = service
+            .get("pattern")
+            .and_then(|p| p.as_str())
+            .unwrap_or("*/*");
+        if pattern.is_empty() || !pattern.split('/').all(|p| p == "*") {
+            self.report(
+                Severity::Error,
+                &[path, &[Segment::Key("pattern")]].concat(),
+                format!("The pattern '{pattern}' is invalid, it should be made up of one or more '*' segments separated by '/' (e.g. '*/*')."),
+            );
+            return;
+        }
+
+        // Each segment of the sample repository's name is distinct, allowing us to check
+        // that the URL templates include every segment matched by the pattern.
+        let depth = pattern.split('/').count();
+        let segments: Vec<String> = (1..=depth.max(2)).map(|i| format!("segment{i}")).collect();
+        let repo = Repo::new(
+            &format!("example.com:{}", segments.join("/")),
+            PathBuf::from("example.com").join(segments.join("/")),
+        );
+        let Ok(context) = templates::repo_context_without_service(&repo) else {
+            return;
+        };
+
+        for key in ["website", "gitUrl"] {
+            let Some(template) = service.get(key).and_then(|t| t.as_str()) else {
+                continue;
+            };
+
+            let path = [path, &[Segment::Key(key)]].concat();
+            if let Some(rendered) =
+                self.validate_template_string(template, &path, &context, variables)
+                && depth >= 2
+                && !segments.iter().all(|s| rendered.contains(s.as_str()))
+            {
+                self.report(
+                    Severity::Error,
+                    &path,
+                    format!(
+                        "The {key} template doesn't include every part of the repository's name matched by the pattern '{pattern}' (rendered '{rendered}' for '{}').",
+                        repo.get_full_name()
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Reports an error if `key` is missing from `value`, or is empty.
+    fn validate_required(&mut self, value: &Value, path: &[Segment], key: &str, context: &str) {
+        let present = match value.get(key) {
+            Some(Value::String(s)) => !s.trim().is_empty(),
+            Some(Value::Null) | None => false,
+            Some(_) => true,
+        };
+
+        if !present {
+            self.report(
+                Severity::Error,
+                &[path, &[Segment::Key(key)]].concat(),
+                format!("The {context} is missing its {key}."),
+            );
+        }
+    }
+
+    /// Renders a template string from a registry template, after substituting its
+    /// variables, reporting any problems and returning the rendered value.
+    fn validate_template_string(
+        &mut self,
+        template: &str,
+        path: &[Segment],
+        context: &gotmpl::Value,
+        variables: &BTreeMap<String, String>,
+    ) -> Option<String> {
+        let substituted = registry::substitute_variables(template, variables);
+        if let Some((_, rest)) = substituted.split_once(".Variables.") {
+            let name: String = rest
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+                .collect();
+            self.report(
+                Severity::Error,
+                path,
+                format!("The template '{template}' uses the '{name}' variable, which is not declared in the template's variables."),
+            );
+            return None;
+        }
+
+        match templates::render(&substituted, context.clone()) {
+            Ok(rendered) => Some(rendered),
+            Err(err) => {
+                self.report(
+                    Severity::Error,
+                    path,
+                    format!(
+                        "The template '{template}' could not be rendered: {}",
+                        err.message()
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    fn validate_features(&mut self, parent: &Value, path: &[Segment]) {
+        let Some(flags) = parent.get("features").and_then(|f| f.as_mapping()) else {
+            return;
+        };
+
+        for flag in flags.keys() {
+            let Some(flag) = flag.as_str() else {
+                continue;
+            };
+
+            if !features::ALL.iter().any(|&f| f == flag) {
+                self.report(
+                    Severity::Warning,
+                    &[path, &[Segment::Key("features"), Segment::Key(flag)]].concat(),
+                    format!("Unknown feature flag '{flag}', run `gt config feature` to see the available flags."),
+                );
+            }
+        }
+    }
+}
+
+/// Builds a repository which matches a service's pattern, for use when checking
+/// that templates can be rendered.
+fn sample_repo(service: &str, pattern: &str) -> Repo {
+    let depth = pattern.split('/').count().max(2);
+    let mut segments = vec!["example"; depth - 1];
+    segments.push("repo");
+
+    Repo::new(
+        &format!("{service}:{}", segments.join("/")),
+        PathBuf::from(service).join(segments.join("/")),
+    )
+}
+
+/// A line-based index of the keys and sequence items in a YAML document, used to
+/// report the location of problems found in its parsed form. Only block-style YAML
+/// is indexed, so values within flow-style collections report no location.
+struct Locator {
+    entries: Vec<LocatorEntry>,
+}
+
+struct LocatorEntry {
+    line: usize,
+    indent: usize,
+    key: Option<String>,
+    is_item: bool,
+}
+
+impl Locator {
+    fn new(content: &str) -> Self {
+        let mut entries = Vec::new();
+
+        for (i, line) in content.lines().enumerate() {
+            let mut indent = line.len() - line.trim_start().len();
+            let mut rest = line.trim_start();
+            if rest.is_empty() || rest.starts_with('#') || rest.starts_with("---") {
+                continue;
+            }
+
+            while let Some(item) = rest
+                .strip_prefix('-')
+                .filter(|r| r.is_empty() || r.starts_with(' '))
+            {
+                entries.push(LocatorEntry {
+                    line: i + 1,
+                    indent,
+                    key: None,
+                    is_item: true,
+                });
+
+                let trimmed = item.trim_start();
+                indent += 1 + item.len() - trimmed.len();
+                rest = trimmed;
+            }
+
+            if let Some((key, _)) = rest.split_once(':') {
+                let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+                if !key.is_empty() && !key.starts_with('{') && !key.starts_with('[') {
+                    entries.push(LocatorEntry {
+                        line: i + 1,
+                        indent,
+                        key: Some(key.to_string()),
+                        is_item: false,
+                    });
+                }
+            }
+        }
+
+        Self { entries }
+    }
+
+    /// Finds the (1-based) line and column at which the value at `path` is defined.
+    fn locate(&self, path: &[Segment]) -> Option<(usize, usize)> {
+        let mut start = 0;
+        let mut end = self.entries.len();
+        let mut parent: Option<&LocatorEntry> = None;
+        let mut found = None;
+
+        for segment in path {
+            // Sequences may be written at the same indentation as the key which contains them.
+            let is_child = |e: &LocatorEntry| match parent {
+                None => true,
+                Some(p) => e.indent > p.indent || (e.indent == p.indent && e.is_item && !p.is_item),
+            };
+
+            let child_indent = self.entries[start..end]
+                .iter()
+                .find(|&e| is_child(e))?
+                .indent;
+
+            let index = match segment {
+                Segment::Key(key) => (start..end).find(|&i| {
+                    let e = &self.entries[i];
+                    e.indent == child_indent && e.key.as_deref() == Some(*key)
+                }),
+                Segment::Index(n) => (start..end)
+                    .filter(|&i| {
+                        let e = &self.entries[i];
+                        e.indent == child_indent && e.is_item
+                    })
+                    .nth(*n),
+            }?;
+
+            let entry = &self.entries[index];
+            found = Some((entry.line, entry.indent + 1));
+
+            start = index + 1;
+            end = (start..end)
+                .find(|&i| {
+                    let e = &self.entries[i];
+                    e.indent < entry.indent
+                        || (e.indent == entry.indent && (entry.is_item || !e.is_item))
+                })
+                .unwrap_or(end);
+            parent = Some(entry);
+        }
+
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(content: &str) -> Vec<Diagnostic> {
+        let config = Config::from_str(content).unwrap_or_default();
+        validate_document(&config, Some(Path::new("config.yml")), content)
+    }
+
+    #[test]
+    fn valid_config() {
+        let diagnostics = validate(
+            r#"
+directory: /dev
+services:
+  - name: gh
+    website: "https://github.com/{{ .Repo.FullName }}"
+    gitUrl: "git@github.com:{{ .Repo.FullName }}.git"
+    pattern: "*/*"
+apps:
+  - name: shell
+    command: bash
+    args:
+      - "{{ .Target.Name }}"
+features:
+  create_remote: true
+branch_template: "{{ .User }}/{{ .Slug }}"
+"#,
+        );
+
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let diagnostics = validate(
+            r#"
+directory: /dev
+scratchpad: /scratch
+services:
+- name: gh
+  website: "https://github.com/{{ .Repo.FullName }}"
+  gitUrl: "git@github.com:{{ .Repo.FullName }}.git"
+  pattern: "*/*"
+  gitURL: "git@github.com:{{ .Repo.FullName }}.git"
+"#,
+        );
+
+        assert_eq!(diagnostics.len(), 2, "{diagnostics:?}");
+        assert!(diagnostics[0].message.contains("'scratchpad'"));
+        assert_eq!(diagnostics[0].location, Some((3, 1)));
+        assert!(diagnostics[1].message.contains("'gitURL'"));
+        assert_eq!(diagnostics[1].location, Some((9, 3)));
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "config.yml:9:3: error: Unknown key 'gitURL' in your service 'gh' (expected one of name, website, gitUrl, pattern, api, identity)."
+        );
+    }
+
+    #[test]
+    fn invalid_services() {
+        let diagnostics = validate(
+            r#"
+directory: /dev
+services:
+  - name: gh
+    website: "https://github.com/{{ .Repo.FullName }}"
+    gitUrl: "git@github.com:{{ .Repo.FullName }}.git"
+    pattern: "*/repo"
+  - name: gh
+    website: "https://github.com/{{ .Repo.Missing }}"
+    gitUrl: "git@github.com:{{ .Repo.FullName .git"
+    pattern: "*/*"
+"#,
+        );
+
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages.len(), 4, "{messages:?}");
+        assert!(messages[0].starts_with("config.yml:7:5: error: The pattern '*/repo'"));
+        assert!(
+            messages[1]
+                .starts_with("config.yml:8:5: error: The service 'gh' is defined more than once")
+        );
+        assert!(messages[2].starts_with("config.yml:9:5: error: The website template"));
+        assert!(messages[3].starts_with("config.yml:10:5: error: The gitUrl template"));
+    }
+
+    #[test]
+    fn invalid_apps_and_features() {
+        let diagnostics = validate(
+            r#"
+directory: /dev
+apps:
+  - name: shell
+    command: bash
+  - name: shell
+    command: zsh
+    args:
+      - "{{ .Repo.Nope }}"
+features:
+  create_remtoe: true
+"#,
+        );
+
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(
+            messages[0]
+                .starts_with("config.yml:6:5: error: The app 'shell' is defined more than once")
+        );
+        assert!(messages[1].starts_with("config.yml:9:7: error: The args template"));
+        assert!(
+            messages[2]
+                .starts_with("config.yml:11:3: warning: Unknown feature flag 'create_remtoe'")
+        );
+    }
+
+    #[test]
+    fn profiles() {
+        let diagnostics = validate(
+            r#"
+directory: /dev
+profiles:
+  work:
+    directory: /work
+    feature:
+      create_remote: false
+"#,
+        );
+
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages.len(), 1, "{messages:?}");
+        assert!(
+            messages[0]
+                .starts_with("config.yml:6:5: error: Unknown key 'feature' in your profile 'work'")
+        );
+    }
+
+    #[test]
+    fn yaml_errors() {
+        let diagnostics = validate("directory: /dev\nservices: [\n");
+
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0].location.is_some());
+    }
+
+    #[test]
+    fn missing_directories() {
+        let temp = tempfile::tempdir().unwrap();
+        let config = Config::for_dev_directory(&temp.path().join("missing"));
+
+        let diagnostics = validate_config(&config);
+        assert_eq!(diagnostics.len(), 3, "{diagnostics:?}");
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn valid_template() {
+        let diagnostics = validate_template(
+            Some(Path::new("registry/services/gitlab.yaml")),
+            r#"
+name: GitLab
+description: A self-hosted GitLab instance.
+version: 1.0.0
+variables:
+  - name: domain
+    validation: "[a-z0-9.-]+"
+configs:
+  - platform: any
+    service:
+      name: "{{ .Variables.domain }}"
+      website: "https://{{ .Variables.domain }}/{{ .Repo.FullName }}"
+      gitUrl: "git@{{ .Variables.domain }}:{{ .Repo.FullName }}.git"
+      pattern: "*/*"
+      api:
+        kind: GitLab/v4
+        url: "https://{{ .Variables.domain }}/api/v4"
+"#,
+        );
+
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn invalid_template() {
+        let diagnostics = validate_template(
+            Some(Path::new("registry/services/broken.yaml")),
+            r#"
+name: Broken
+description: A broken template.
+configs:
+  - platform: macos
+    service:
+      name: example.com
+      website: "https://example.com/{{ .Repo.Name }}"
+      gitUrl: "git@example.com:{{ .Variables.missing }}.git"
+      pattern: "*/*"
+      api:
+        kind: GitHub/v4
+        url: https://example.com/api
+  - platform: any
+    app:
+      name: shell
+      command: bash
+"#,
+        );
+
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(diagnostics.len(), 6, "{messages:#?}");
+        assert!(messages[0].contains("does not have a version"));
+        assert!(
+            messages[1]
+                .starts_with("registry/services/broken.yaml:5:5: error: The platform 'macos'")
+        );
+        assert!(messages[2].contains("The API kind 'GitHub/v4' is not supported"));
+        assert!(
+            messages[3]
+                .contains("website template doesn't include every part of the repository's name")
+        );
+        assert!(messages[4].contains("uses the 'missing' variable"));
+        assert!(messages[5].contains("must provide a service"));
+    }
+}
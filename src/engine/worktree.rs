@@ -1,5 +1,8 @@
+use super::ports::port_variable;
+use super::templates::worktree_context;
 use super::{Branch, Config, Repo, Target};
 use gotmpl::Value;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// A git worktree for a particular [`Branch`] of a [`Repo`], resolved to its
@@ -7,13 +10,16 @@ use std::path::PathBuf;
 ///
 /// A `Worktree` is a launch [`Target`]: its path is the worktree directory, while
 /// its template context mirrors the originating repository so that application
-/// templates behave exactly as they would for a normal repository.
+/// templates behave exactly as they would for a normal repository, with the
+/// worktree's own details available through `{{ .Worktree }}`.
 #[derive(Debug, Clone)]
 pub struct Worktree {
     /// The launch target: it carries the originating repository's identity
     /// (service + full name) but points at the worktree directory.
     repo: Repo,
     branch: Branch,
+    /// The ports which have been allocated to this worktree, keyed by name.
+    ports: BTreeMap<String, u16>,
 }
 
 impl Worktree {
@@ -29,15 +35,42 @@ impl Worktree {
         Self {
             repo: target,
             branch: branch.clone(),
+            ports: BTreeMap::new(),
         }
     }
 
-    // Currently only exercised by tests, but a natural part of a worktree's API.
-    #[allow(dead_code)]
+    /// Describes an existing worktree of a [`Repo`] which has the [`Branch`] checked
+    /// out at `path`, wherever it happens to be.
+    pub fn at(repo: &Repo, branch: &Branch, path: PathBuf) -> Self {
+        let target = Repo::new(&format!("{}:{}", repo.service, repo.get_full_name()), path);
+
+        Self {
+            repo: target,
+            branch: branch.clone(),
+            ports: BTreeMap::new(),
+        }
+    }
+
+    /// Attaches the ports which have been allocated to this worktree, exposing them
+    /// to launched applications and templates.
+    pub fn with_ports(self, ports: BTreeMap<String, u16>) -> Self {
+        Self { ports, ..self }
+    }
+
+    pub fn ports(&self) -> &BTreeMap<String, u16> {
+        &self.ports
+    }
+
     pub fn branch(&self) -> &Branch {
         &self.branch
     }
 
+    /// The repository identity used as this worktree's launch target, which points
+    /// at the worktree directory rather than the original checkout.
+    pub(crate) fn target_repo(&self) -> &Repo {
+        &self.repo
+    }
+
     pub fn path(&self) -> PathBuf {
         self.repo.get_path()
     }
@@ -70,6 +103,21 @@ impl Worktree {
         )
     }
 
+    /// Determines whether `name` is a directory name which [`Worktree::dir_name`] could
+    /// have produced, ending with the 8 character hash of a repository's identity.
+    pub(crate) fn is_dir_name(name: &str) -> bool {
+        match name.rsplit_once('-') {
+            Some((prefix, hash)) => {
+                !prefix.is_empty()
+                    && hash.len() == 8
+                    && hash
+                        .chars()
+                        .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+            }
+            None => false,
+        }
+    }
+
     /// Produces a stable 8 character hexadecimal hash of the provided string using
     /// the FNV-1a algorithm. FNV-1a is used (rather than [`std::hash::DefaultHasher`])
     /// because it produces identical output across platforms and toolchain
@@ -102,7 +150,14 @@ impl Target for Worktree {
     }
 
     fn template_context(&self, config: &Config) -> Result<Value, human_errors::Error> {
-        self.repo.template_context(config)
+        worktree_context(config, self)
+    }
+
+    fn environment(&self) -> Vec<(String, String)> {
+        self.ports
+            .iter()
+            .map(|(name, port)| (port_variable(name), port.to_string()))
+            .collect()
     }
 }
 
@@ -134,6 +189,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn is_dir_name() {
+        let repo = repo();
+
+        assert!(Worktree::is_dir_name(&Worktree::dir_name(
+            &repo,
+            &"feat/forgejo".parse().unwrap()
+        )));
+        assert!(!Worktree::is_dir_name("notes"));
+        assert!(!Worktree::is_dir_name("my-project"));
+        assert!(!Worktree::is_dir_name("-12345678"));
+        assert!(!Worktree::is_dir_name("project-ABCDEF12"));
+    }
+
     #[test]
     fn dir_name_disambiguates_repositories() {
         let repo_a = Repo::new("gh:org-a/tools", "/dev/a/tools".into());
@@ -155,6 +155,142 @@ fn create_link_native(original: &Path, link: &Path) -> std::io::Result<()> {
     }
 }
 
+/// Copies the file or directory at `original` to `destination`, creating any
+/// missing parent directories. Directories are copied recursively, while symlinks
+/// are recreated as symlinks rather than followed (and are skipped on Windows).
+#[tracing::instrument(err)]
+pub fn copy_path(original: &Path, destination: &Path) -> Result<(), human_errors::Error> {
+    if !original.exists() {
+        return Err(human_errors::user(
+            format!(
+                "Could not copy '{}' because it does not exist.",
+                original.display()
+            ),
+            &["Make sure that the path you are copying exists before trying again."],
+        ));
+    }
+
+    copy_path_native(original, destination).map_err(|e| {
+        human_errors::wrap_system(
+            e,
+            format!(
+                "Could not copy '{}' to '{}' due to an OS-level error.",
+                original.display(),
+                destination.display()
+            ),
+            &["Check that Git-Tool has permission to write to this location."],
+        )
+    })
+}
+
+fn copy_path_native(original: &Path, destination: &Path) -> std::io::Result<()> {
+    let file_type = std::fs::symlink_metadata(original)?.file_type();
+    if file_type.is_symlink() {
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        copy_link_native(original, destination)?;
+    } else if file_type.is_dir() {
+        std::fs::create_dir_all(destination)?;
+        for entry in std::fs::read_dir(original)? {
+            let entry = entry?;
+            copy_path_native(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+    } else {
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(original, destination)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_link_native(original: &Path, destination: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(std::fs::read_link(original)?, destination)
+}
+
+#[cfg(windows)]
+fn copy_link_native(original: &Path, _destination: &Path) -> std::io::Result<()> {
+    // Creating symlinks on Windows requires elevated privileges (or developer mode),
+    // so we skip them rather than copying whatever they point to.
+    tracing::warn!(
+        "Skipping the symlink '{}' while copying.",
+        original.display()
+    );
+    Ok(())
+}
+
+/// Calculates the total size (in bytes) of the files within a directory. Symlinks
+/// are not followed, so directories which are shared with another checkout (like a
+/// symlinked `target` directory) are not counted.
+pub fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+
+    if metadata.is_dir() {
+        std::fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| disk_usage(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or_default()
+    } else if metadata.is_file() {
+        metadata.len()
+    } else {
+        0
+    }
+}
+
+/// Finds the executable which would be run for `command`, searching the directories
+/// on your `PATH` (and, on Windows, trying each of the extensions in `PATHEXT`).
+///
+/// Commands which include a path separator are resolved relative to the current
+/// directory instead of being searched for on your `PATH`.
+pub fn find_executable(command: &str) -> Option<PathBuf> {
+    let command = Path::new(command);
+    if command.components().count() > 1 {
+        return find_executable_with_extensions(command);
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .find_map(|dir| find_executable_with_extensions(&dir.join(command)))
+    })
+}
+
+#[cfg(windows)]
+fn find_executable_with_extensions(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    let extensions = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+    extensions
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| {
+            let mut file = path.as_os_str().to_owned();
+            file.push(ext);
+            PathBuf::from(file)
+        })
+        .find(|path| path.is_file())
+}
+
+#[cfg(unix)]
+fn find_executable_with_extensions(path: &Path) -> Option<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .ok()
+        .filter(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .map(|_| path.to_path_buf())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,4 +406,73 @@ mod tests {
 
         assert!(create_link(&original, &link).is_err());
     }
+
+    #[test]
+    fn test_copy_path() {
+        let temp = tempfile::tempdir().unwrap();
+        let original = temp.path().join("original");
+        std::fs::create_dir_all(original.join("nested")).unwrap();
+        std::fs::write(original.join("nested").join("marker.txt"), "hello").unwrap();
+
+        let destination = temp.path().join("deep").join("copied");
+        copy_path(&original, &destination).expect("the directory should be copied");
+
+        assert_eq!(
+            std::fs::read_to_string(destination.join("nested").join("marker.txt")).unwrap(),
+            "hello"
+        );
+        assert!(copy_path(&temp.path().join("missing"), &destination).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_copy_path_keeps_symlinks() {
+        let temp = tempfile::tempdir().unwrap();
+        let original = temp.path().join("original");
+        std::fs::create_dir_all(original.join("nested")).unwrap();
+        std::fs::write(original.join("nested").join("marker.txt"), "hello").unwrap();
+
+        // A link back up the tree would recurse forever if it were followed.
+        std::os::unix::fs::symlink("..", original.join("nested").join("parent")).unwrap();
+
+        let destination = temp.path().join("copied");
+        copy_path(&original, &destination).expect("the directory should be copied");
+
+        let link = destination.join("nested").join("parent");
+        assert!(
+            std::fs::symlink_metadata(&link)
+                .unwrap()
+                .file_type()
+                .is_symlink(),
+            "the symlink should be recreated as a symlink"
+        );
+        assert_eq!(std::fs::read_link(&link).unwrap(), PathBuf::from(".."));
+    }
+
+    #[test]
+    fn test_disk_usage() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("nested")).unwrap();
+        std::fs::write(temp.path().join("a.txt"), "hello").unwrap();
+        std::fs::write(temp.path().join("nested").join("b.txt"), "world!").unwrap();
+
+        let shared = tempfile::tempdir().unwrap();
+        std::fs::write(shared.path().join("large.bin"), vec![0u8; 1024]).unwrap();
+        create_link(shared.path(), &temp.path().join("shared")).unwrap();
+
+        assert_eq!(disk_usage(temp.path()), 11);
+        assert_eq!(disk_usage(&temp.path().join("missing")), 0);
+    }
+
+    #[test]
+    fn test_find_executable() {
+        assert!(
+            find_executable("git").is_some(),
+            "git should be found on the PATH"
+        );
+        assert!(
+            find_executable("git-tool-definitely-not-a-real-command").is_none(),
+            "commands which don't exist should not be found"
+        );
+    }
 }
@@ -55,6 +55,34 @@ pub async fn git_branches(repo: &path::Path) -> Result<Vec<String>, human_errors
     Ok(unique_refs.iter().map(|s| s.to_string()).collect())
 }
 
+/// Lists the remotes which have a branch called `name`, based on the
+/// remote-tracking branches recorded by the most recent fetch.
+pub async fn git_branch_remotes(
+    repo: &path::Path,
+    name: &str,
+) -> Result<Vec<String>, human_errors::Error> {
+    info!("Running `git for-each-ref refs/remotes/` to find the remotes which have a branch");
+    validate_repo_path_exists(repo)?;
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("for-each-ref")
+            .arg("--format=%(refname:lstrip=2)")
+            .arg("refs/remotes/"),
+    )
+    .await?;
+
+    let refs: HashSet<&str> = output.split_terminator('\n').map(|s| s.trim()).collect();
+
+    // Remote names may themselves contain slashes, so we match against the
+    // configured remotes rather than splitting the ref name.
+    Ok(super::git_remote_list(repo)
+        .await?
+        .into_iter()
+        .filter(|remote| refs.contains(format!("{remote}/{name}").as_str()))
+        .collect())
+}
+
 pub async fn git_branch_delete(repo: &path::Path, name: &str) -> Result<(), human_errors::Error> {
     info!("Running `git branch -D $NAME` to delete branch");
     validate_repo_path_exists(repo)?;
@@ -70,6 +98,27 @@ pub async fn git_branch_delete(repo: &path::Path, name: &str) -> Result<(), huma
     Ok(())
 }
 
+/// Renames a local branch, updating any worktree which has it checked out.
+pub async fn git_branch_rename(
+    repo: &path::Path,
+    old: &str,
+    new: &str,
+) -> Result<(), human_errors::Error> {
+    info!("Running `git branch -m $OLD $NEW` to rename branch");
+    validate_repo_path_exists(repo)?;
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("branch")
+            .arg("-m")
+            .arg(old)
+            .arg(new),
+    )
+    .await?;
+
+    Ok(())
+}
+
 pub async fn git_default_branch(repo: &path::Path) -> Result<String, human_errors::Error> {
     info!("Running `git symbolic-ref refs/remotes/origin/HEAD` to get the default branch");
     validate_repo_path_exists(repo)?;
@@ -110,6 +159,165 @@ pub async fn git_merged_branches(repo: &path::Path) -> Result<Vec<String>, human
     Ok(refs.collect())
 }
 
+/// A local branch along with the upstream branch it tracks (if any).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchUpstream {
+    pub branch: String,
+    pub upstream: Option<String>,
+    /// Whether the upstream branch has been deleted from the remote (as seen by the last `git fetch --prune`).
+    pub gone: bool,
+}
+
+pub async fn git_branch_upstreams(
+    repo: &path::Path,
+) -> Result<Vec<BranchUpstream>, human_errors::Error> {
+    info!("Running `git for-each-ref refs/heads/` to get the upstream of each local branch");
+    validate_repo_path_exists(repo)?;
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("for-each-ref")
+            .arg("--format=%(refname:lstrip=2)%09%(upstream:short)%09%(upstream:track)")
+            .arg("refs/heads/"),
+    )
+    .await?;
+
+    Ok(output
+        .split_terminator('\n')
+        .filter_map(|line| {
+            let mut parts = line.trim_end().splitn(3, '\t');
+            let branch = parts.next().filter(|b| !b.is_empty())?;
+            let upstream = parts.next().filter(|u| !u.is_empty());
+            let gone = parts.next().map(|t| t == "[gone]").unwrap_or_default();
+
+            Some(BranchUpstream {
+                branch: branch.to_string(),
+                upstream: upstream.map(|u| u.to_string()),
+                gone,
+            })
+        })
+        .collect())
+}
+
+/// Determines whether every commit on `branch` has an equivalent commit (with the same
+/// patch ID) in `into`, which is the case once a branch has been rebase merged.
+pub async fn git_branch_is_rebased(
+    repo: &path::Path,
+    branch: &str,
+    into: &str,
+) -> Result<bool, human_errors::Error> {
+    info!("Running `git cherry $INTO $BRANCH` to check whether a branch has been rebase merged");
+    validate_repo_path_exists(repo)?;
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("cherry")
+            .arg(into)
+            .arg(branch),
+    )
+    .await?;
+
+    let mut commits = output.lines().filter(|l| !l.trim().is_empty()).peekable();
+    Ok(commits.peek().is_some() && commits.all(|l| l.starts_with('-')))
+}
+
+/// Determines whether every commit on `branch` is already contained in `into`, in which
+/// case deleting `branch` won't lose any work.
+pub async fn git_branch_is_contained(
+    repo: &path::Path,
+    branch: &str,
+    into: &str,
+) -> Result<bool, human_errors::Error> {
+    info!(
+        "Running `git rev-list --count $INTO..$BRANCH` to check whether a branch is contained in another"
+    );
+    validate_repo_path_exists(repo)?;
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("rev-list")
+            .arg("--count")
+            .arg(format!("{into}..{branch}")),
+    )
+    .await?;
+
+    Ok(output.trim() == "0")
+}
+
+/// Determines whether every commit on `branch` is contained in at least one of the
+/// remote-tracking branches recorded by the most recent fetch.
+pub async fn git_branch_is_on_remote(
+    repo: &path::Path,
+    branch: &str,
+) -> Result<bool, human_errors::Error> {
+    info!(
+        "Running `git for-each-ref --contains $BRANCH refs/remotes/` to check whether a branch has been pushed"
+    );
+    validate_repo_path_exists(repo)?;
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("for-each-ref")
+            .arg("--format=%(refname)")
+            .arg("--contains")
+            .arg(branch)
+            .arg("refs/remotes/"),
+    )
+    .await?;
+
+    Ok(output.lines().any(|l| !l.trim().is_empty()))
+}
+
+/// Determines whether the combined changes on `branch` have been applied to `into` as a
+/// single commit, which is the case once a branch has been squash merged.
+pub async fn git_branch_is_squashed(
+    repo: &path::Path,
+    branch: &str,
+    into: &str,
+) -> Result<bool, human_errors::Error> {
+    info!(
+        "Running `git commit-tree` and `git cherry $INTO $COMMIT` to check whether a branch has been squash merged"
+    );
+    validate_repo_path_exists(repo)?;
+    let merge_base = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("merge-base")
+            .arg(into)
+            .arg(branch),
+    )
+    .await?;
+
+    // We create a (dangling) commit which squashes all of the branch's changes on top
+    // of its merge base, allowing git to compare its patch ID against the commits in `into`.
+    let squashed = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .env("GIT_AUTHOR_NAME", "Git-Tool")
+            .env("GIT_AUTHOR_EMAIL", "git-tool@sierrasoftworks.com")
+            .env("GIT_COMMITTER_NAME", "Git-Tool")
+            .env("GIT_COMMITTER_EMAIL", "git-tool@sierrasoftworks.com")
+            .arg("commit-tree")
+            .arg(format!("{branch}^{{tree}}"))
+            .arg("-p")
+            .arg(merge_base.trim())
+            .arg("-m")
+            .arg(format!("Squashed {branch}")),
+    )
+    .await?;
+
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("cherry")
+            .arg(into)
+            .arg(squashed.trim()),
+    )
+    .await?;
+
+    Ok(output.trim().starts_with('-'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,7 +333,7 @@ mod tests {
         let core = Core::builder().with_config_for_dev_directory(path).build();
 
         sequence![
-            GitInit {},
+            GitInit::default(),
             GitCheckout { branch: "main" },
             WriteFile {
                 path: PathBuf::from("README.md"),
@@ -281,6 +489,136 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_branch_is_squashed_or_rebased() {
+        let temp = tempdir().unwrap();
+        let (core, repo) = setup_test_repo(temp.path()).await;
+
+        sequence![
+            GitCheckout {
+                branch: "feature/squashed"
+            },
+            WriteFile {
+                path: PathBuf::from("a.txt"),
+                content: "First change",
+            },
+            GitAdd {
+                paths: vec!["a.txt"]
+            },
+            GitCommit {
+                message: "Add a.txt",
+                paths: vec!["a.txt"]
+            },
+            WriteFile {
+                path: PathBuf::from("b.txt"),
+                content: "Second change",
+            },
+            GitAdd {
+                paths: vec!["b.txt"]
+            },
+            GitCommit {
+                message: "Add b.txt",
+                paths: vec!["b.txt"]
+            },
+            GitCheckout { branch: "main" }
+        ]
+        .apply_repo(&core, &repo)
+        .await
+        .expect("the feature branch should have been created");
+
+        assert!(
+            !git_branch_is_squashed(&repo.get_path(), "feature/squashed", "main")
+                .await
+                .unwrap(),
+            "the branch should not be squash merged before it has been merged"
+        );
+
+        git_cmd(
+            Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("merge")
+                .arg("--squash")
+                .arg("feature/squashed"),
+        )
+        .await
+        .unwrap();
+        git_cmd(
+            Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("commit")
+                .arg("-m")
+                .arg("Squash merge feature/squashed"),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            git_branch_is_squashed(&repo.get_path(), "feature/squashed", "main")
+                .await
+                .unwrap(),
+            "the branch should be squash merged"
+        );
+        assert!(
+            !git_branch_is_rebased(&repo.get_path(), "feature/squashed", "main")
+                .await
+                .unwrap(),
+            "the branch's individual commits are not present on main"
+        );
+
+        git_cmd(
+            Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("checkout")
+                .arg("-b")
+                .arg("feature/rebased")
+                .arg("main~1"),
+        )
+        .await
+        .unwrap();
+
+        sequence![
+            WriteFile {
+                path: PathBuf::from("c.txt"),
+                content: "Third change",
+            },
+            GitAdd {
+                paths: vec!["c.txt"]
+            },
+            GitCommit {
+                message: "Add c.txt",
+                paths: vec!["c.txt"]
+            },
+            GitCheckout { branch: "main" }
+        ]
+        .apply_repo(&core, &repo)
+        .await
+        .expect("the rebased branch should have been created");
+
+        git_cmd(
+            Command::new("git")
+                .current_dir(repo.get_path())
+                .arg("cherry-pick")
+                .arg("feature/rebased"),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            git_branch_is_rebased(&repo.get_path(), "feature/rebased", "main")
+                .await
+                .unwrap(),
+            "the branch should be rebase merged"
+        );
+
+        let upstreams = git_branch_upstreams(&repo.get_path()).await.unwrap();
+        assert!(
+            upstreams
+                .iter()
+                .any(|b| b.branch == "feature/squashed" && b.upstream.is_none() && !b.gone),
+            "the local branches should be listed without an upstream: {upstreams:?}"
+        );
+    }
+
     #[tokio::test]
     async fn test_delete_branch() {
         let temp = tempdir().unwrap();
@@ -24,3 +24,56 @@ pub async fn git_config_set(
 
     Ok(())
 }
+
+/// Gets the effective value of a git configuration key for a repository (taking
+/// into account its local, global and system configuration), returning `None` if
+/// the key has not been set.
+pub async fn git_config_get(
+    repo: &path::Path,
+    key: &str,
+) -> Result<Option<String>, human_errors::Error> {
+    info!("Running `git config --get` to get a configuration value");
+    validate_repo_path_exists(repo)?;
+    let value = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("config")
+            .arg("--default=")
+            .arg("--get")
+            .arg(key),
+    )
+    .await?;
+
+    match value.trim() {
+        "" => Ok(None),
+        value => Ok(Some(value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::git_init;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_config_get_and_set() {
+        let temp = tempdir().unwrap();
+        git_init(temp.path()).await.unwrap();
+
+        assert_eq!(
+            git_config_get(temp.path(), "gittool.test").await.unwrap(),
+            None,
+            "an unset key should not have a value"
+        );
+
+        git_config_set(temp.path(), "gittool.test", "example")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            git_config_get(temp.path(), "gittool.test").await.unwrap(),
+            Some("example".to_string())
+        );
+    }
+}
@@ -18,3 +18,18 @@ pub async fn git_fetch(repo: &path::Path, origin: &str) -> Result<(), human_erro
 
     Ok(())
 }
+
+/// Fetches the latest branches from all of the repository's remotes.
+pub async fn git_fetch_all(repo: &path::Path) -> Result<(), human_errors::Error> {
+    info!("Running `git fetch --all`");
+    validate_repo_path_exists(repo)?;
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("fetch")
+            .arg("--all"),
+    )
+    .await?;
+
+    Ok(())
+}
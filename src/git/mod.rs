@@ -6,33 +6,41 @@ mod cmd;
 mod commit;
 mod fetch;
 mod init;
+mod pull;
 mod remote;
+mod stash;
 mod switch;
 mod worktree;
 
-#[cfg(test)]
 mod config;
-#[cfg(test)]
 mod refs;
 
 pub use add::git_add;
 #[allow(unused_imports)]
 pub use branch::{
-    git_branch_delete, git_branches, git_current_branch, git_default_branch, git_merged_branches,
+    BranchUpstream, git_branch_delete, git_branch_is_contained, git_branch_is_on_remote,
+    git_branch_is_rebased, git_branch_is_squashed, git_branch_remotes, git_branch_rename,
+    git_branch_upstreams, git_branches, git_current_branch, git_default_branch,
+    git_merged_branches,
 };
 pub use checkout::git_checkout;
 pub use clone::git_clone;
 pub use cmd::git_cmd;
 pub use commit::git_commit;
+pub use config::{git_config_get, git_config_set};
 #[allow(unused_imports)]
-pub use fetch::git_fetch;
+pub use fetch::{git_fetch, git_fetch_all};
 pub use init::git_init;
+pub use pull::git_pull;
 #[allow(unused_imports)]
 pub use remote::{git_remote_add, git_remote_list, git_remote_rename, git_remote_set_url};
-pub use switch::git_switch;
+pub use stash::{Stash, git_stash_list, git_stash_pop, git_stash_push};
+pub use switch::{git_switch, git_switch_track};
 #[allow(unused_imports)]
 pub use worktree::{
-    Worktree, git_worktree_add, git_worktree_is_clean, git_worktree_list, git_worktree_remove,
+    Worktree, git_worktree_add, git_worktree_ahead_behind, git_worktree_is_clean,
+    git_worktree_last_commit, git_worktree_list, git_worktree_move, git_worktree_prune,
+    git_worktree_remove,
 };
 
 // Only exposed to the fuzzing harness (cargo-afl sets `cfg(fuzzing)`), allowing
@@ -40,7 +48,5 @@ pub use worktree::{
 #[cfg(fuzzing)]
 pub use worktree::parse_worktree_list_fuzz;
 
-#[cfg(test)]
-pub use config::git_config_set;
-#[cfg(test)]
+#[allow(unused_imports)]
 pub use refs::{git_rev_parse, git_update_ref};
@@ -0,0 +1,19 @@
+use super::git_cmd;
+use crate::git::cmd::validate_repo_path_exists;
+use std::path;
+use tokio::process::Command;
+use tracing_batteries::prelude::*;
+
+pub async fn git_pull(repo: &path::Path) -> Result<(), human_errors::Error> {
+    info!("Running `git pull --ff-only` to update the repository");
+    validate_repo_path_exists(repo)?;
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("pull")
+            .arg("--ff-only"),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use super::git_cmd;
+use crate::git::cmd::validate_repo_path_exists;
+use std::path;
+use tokio::process::Command;
+use tracing_batteries::prelude::*;
+
+/// An entry in a repository's stash, as reported by `git stash list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stash {
+    /// The reference used to address this stash entry, for example `stash@{0}`.
+    pub reference: String,
+    /// The stash's message, for example `On main: WIP`.
+    pub message: String,
+}
+
+/// Stashes all of the uncommitted changes (including untracked files) in the
+/// repository using the provided message.
+pub async fn git_stash_push(repo: &path::Path, message: &str) -> Result<(), human_errors::Error> {
+    info!("Running `git stash push` to stash uncommitted changes");
+    validate_repo_path_exists(repo)?;
+
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("stash")
+            .arg("push")
+            .arg("--include-untracked")
+            .arg("--message")
+            .arg(message),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the entries in the repository's stash, most recent first.
+pub async fn git_stash_list(repo: &path::Path) -> Result<Vec<Stash>, human_errors::Error> {
+    info!("Running `git stash list` to list stashed changes");
+    validate_repo_path_exists(repo)?;
+
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("stash")
+            .arg("list")
+            .arg("--format=%gd%x00%gs"),
+    )
+    .await?;
+
+    Ok(output
+        .lines()
+        .filter_map(|line| line.split_once('\0'))
+        .map(|(reference, message)| Stash {
+            reference: reference.to_string(),
+            message: message.to_string(),
+        })
+        .collect())
+}
+
+/// Applies the stash entry identified by `reference` to the working tree and
+/// removes it from the stash.
+pub async fn git_stash_pop(repo: &path::Path, reference: &str) -> Result<(), human_errors::Error> {
+    info!("Running `git stash pop` to restore stashed changes");
+    validate_repo_path_exists(repo)?;
+
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("stash")
+            .arg("pop")
+            .arg("--index")
+            .arg(reference),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{git_add, git_checkout, git_commit, git_config_set, git_init};
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_stash_push_list_pop() {
+        let temp = tempdir().unwrap();
+        let repo = temp.path().join("repo");
+
+        git_init(&repo).await.unwrap();
+        git_checkout(&repo, "main").await.unwrap();
+        git_config_set(&repo, "user.name", "Example User")
+            .await
+            .unwrap();
+        git_config_set(&repo, "user.email", "user@example.com")
+            .await
+            .unwrap();
+        std::fs::write(repo.join("test.txt"), "testing").unwrap();
+        git_add(&repo, &vec!["test.txt"]).await.unwrap();
+        git_commit(&repo, "Initial commit", &vec!["test.txt"])
+            .await
+            .unwrap();
+
+        assert!(git_stash_list(&repo).await.unwrap().is_empty());
+
+        std::fs::write(repo.join("test.txt"), "changed").unwrap();
+        std::fs::write(repo.join("untracked.txt"), "new").unwrap();
+        git_stash_push(&repo, "example stash").await.unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(repo.join("test.txt")).unwrap(),
+            "testing"
+        );
+        assert!(!repo.join("untracked.txt").exists());
+
+        let stashes = git_stash_list(&repo).await.unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].reference, "stash@{0}");
+        assert!(stashes[0].message.ends_with("example stash"));
+
+        git_stash_pop(&repo, &stashes[0].reference).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(repo.join("test.txt")).unwrap(),
+            "changed"
+        );
+        assert!(repo.join("untracked.txt").exists());
+        assert!(git_stash_list(&repo).await.unwrap().is_empty());
+    }
+}
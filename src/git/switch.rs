@@ -33,3 +33,26 @@ pub async fn git_switch(
 
     Ok(())
 }
+
+/// Creates the local branch `name` from the remote-tracking branch `upstream`
+/// (like `origin/feature`), configures it to track that branch and switches to it.
+pub async fn git_switch_track(
+    repo: &path::Path,
+    name: &str,
+    upstream: &str,
+) -> Result<(), human_errors::Error> {
+    info!("Running `git switch --create $BRANCH_NAME --track $UPSTREAM` to track a remote branch");
+    validate_repo_path_exists(repo)?;
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("switch")
+            .arg("--create")
+            .arg(name)
+            .arg("--track")
+            .arg(upstream),
+    )
+    .await?;
+
+    Ok(())
+}
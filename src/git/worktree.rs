@@ -62,6 +62,45 @@ pub async fn git_worktree_remove(
     Ok(())
 }
 
+/// Moves a linked worktree to a new location, keeping its registration up to date.
+pub async fn git_worktree_move(
+    repo: &path::Path,
+    worktree: &path::Path,
+    destination: &path::Path,
+) -> Result<(), human_errors::Error> {
+    info!("Running `git worktree move` to move a worktree");
+    validate_repo_path_exists(repo)?;
+
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("worktree")
+            .arg("move")
+            .arg(worktree)
+            .arg(destination),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Removes the administrative files for any worktrees whose directories no longer
+/// exist, so that git stops reporting them.
+pub async fn git_worktree_prune(repo: &path::Path) -> Result<(), human_errors::Error> {
+    info!("Running `git worktree prune` to remove stale worktree registrations");
+    validate_repo_path_exists(repo)?;
+
+    git_cmd(
+        Command::new("git")
+            .current_dir(repo)
+            .arg("worktree")
+            .arg("prune"),
+    )
+    .await?;
+
+    Ok(())
+}
+
 /// Determines whether the worktree at the given path is free of uncommitted
 /// changes (including staged changes and untracked files). Returns `true` when
 /// the worktree is clean and can be safely removed.
@@ -80,6 +119,54 @@ pub async fn git_worktree_is_clean(worktree: &path::Path) -> Result<bool, human_
     Ok(output.trim().is_empty())
 }
 
+/// Counts the commits which the worktree's `HEAD` is ahead of and behind `base` by,
+/// returned as `(ahead, behind)`.
+pub async fn git_worktree_ahead_behind(
+    worktree: &path::Path,
+    base: &str,
+) -> Result<(usize, usize), human_errors::Error> {
+    info!("Running `git rev-list --left-right --count` to compare a worktree with its base");
+    validate_repo_path_exists(worktree)?;
+
+    let output = git_cmd(
+        Command::new("git")
+            .current_dir(worktree)
+            .arg("rev-list")
+            .arg("--left-right")
+            .arg("--count")
+            .arg(format!("{base}...HEAD")),
+    )
+    .await?;
+
+    let mut counts = output
+        .split_whitespace()
+        .map(|count| count.parse::<usize>().unwrap_or_default());
+    let behind = counts.next().unwrap_or_default();
+    let ahead = counts.next().unwrap_or_default();
+
+    Ok((ahead, behind))
+}
+
+/// Gets a human readable description of when the last commit in the worktree was
+/// made (for example `3 days ago`).
+pub async fn git_worktree_last_commit(
+    worktree: &path::Path,
+) -> Result<String, human_errors::Error> {
+    info!("Running `git log -1 --format=%cr` to get the date of the last commit");
+    validate_repo_path_exists(worktree)?;
+
+    Ok(git_cmd(
+        Command::new("git")
+            .current_dir(worktree)
+            .arg("log")
+            .arg("-1")
+            .arg("--format=%cr"),
+    )
+    .await?
+    .trim()
+    .to_string())
+}
+
 pub async fn git_worktree_list(repo: &path::Path) -> Result<Vec<Worktree>, human_errors::Error> {
     info!("Running `git worktree list --porcelain` to list worktrees");
     validate_repo_path_exists(repo)?;
@@ -170,7 +257,9 @@ fn canonicalize_worktree_path(path: path::PathBuf) -> path::PathBuf {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::{git_add, git_checkout, git_commit, git_config_set, git_init};
+    use crate::git::{
+        git_add, git_branch_rename, git_checkout, git_commit, git_config_set, git_init,
+    };
     use tempfile::tempdir;
 
     #[tokio::test]
@@ -211,15 +300,78 @@ mod tests {
         // Every entry should report the commit it has checked out.
         assert!(worktrees.iter().all(|w| w.head.is_some()));
 
-        // The worktree can be removed again, provided it has no pending changes.
-        git_worktree_remove(&repo, &worktree_path).await.unwrap();
+        // The worktree can be moved along with its branch being renamed.
+        let moved_path = temp.path().join("worktrees").join("renamed");
+        git_branch_rename(&repo, "feature", "renamed")
+            .await
+            .unwrap();
+        git_worktree_move(&repo, &worktree_path, &moved_path)
+            .await
+            .unwrap();
         assert!(!worktree_path.exists());
 
+        let worktrees = git_worktree_list(&repo).await.unwrap();
+        assert!(worktrees.iter().any(|w| {
+            w.branch.as_deref() == Some("renamed")
+                && std::fs::canonicalize(&w.path).unwrap()
+                    == std::fs::canonicalize(&moved_path).unwrap()
+        }));
+
+        // The worktree can be removed again, provided it has no pending changes.
+        git_worktree_remove(&repo, &moved_path).await.unwrap();
+        assert!(!moved_path.exists());
+
         let worktrees = git_worktree_list(&repo).await.unwrap();
         assert!(
             !worktrees
                 .iter()
-                .any(|w| w.branch.as_deref() == Some("feature"))
+                .any(|w| w.branch.as_deref() == Some("renamed"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worktree_ahead_behind() {
+        let temp = tempdir().unwrap();
+        let repo = temp.path().join("repo");
+
+        git_init(&repo).await.unwrap();
+        git_checkout(&repo, "main").await.unwrap();
+
+        git_config_set(&repo, "user.name", "Example User")
+            .await
+            .unwrap();
+        git_config_set(&repo, "user.email", "user@example.com")
+            .await
+            .unwrap();
+        std::fs::write(repo.join("test.txt"), "testing").unwrap();
+        git_add(&repo, &vec!["test.txt"]).await.unwrap();
+        git_commit(&repo, "Initial commit", &vec!["test.txt"])
+            .await
+            .unwrap();
+
+        let worktree_path = temp.path().join("worktrees").join("feature");
+        git_worktree_add(&repo, &worktree_path, "feature", true, None)
+            .await
+            .unwrap();
+
+        std::fs::write(worktree_path.join("feature.txt"), "feature").unwrap();
+        git_add(&worktree_path, &vec!["feature.txt"]).await.unwrap();
+        git_commit(&worktree_path, "Add feature", &vec!["feature.txt"])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            git_worktree_ahead_behind(&worktree_path, "main")
+                .await
+                .unwrap(),
+            (1, 0)
+        );
+        assert!(
+            !git_worktree_last_commit(&worktree_path)
+                .await
+                .unwrap()
+                .is_empty(),
+            "the date of the last commit should be reported"
         );
     }
 
@@ -240,7 +240,12 @@ async fn run(
         core_builder.with_default_config()
     };
 
-    let core = core_builder.with_analytics(analytics).build();
+    let core = core_builder
+        .with_environment_overrides()
+        .with_profile(matches.get_one::<String>("profile").map(|p| p.as_str()))?
+        .with_analytics(analytics)
+        .with_dry_run(matches.get_flag("dry-run"))
+        .build();
 
     // If telemetry is enabled in the config file, then turn it on here.
     if !core.config().get_features().has(features::TELEMETRY) {
@@ -2,6 +2,6 @@ pub mod gitignore;
 pub mod registry;
 pub mod service;
 
-pub use registry::GitHubRegistry;
+pub use registry::CompositeRegistry;
 #[allow(unused_imports)]
 pub use service::{OnlineService, services};
@@ -0,0 +1,151 @@
+use super::*;
+use crate::errors::HumanErrorResultExt;
+use human_errors::ResultExt;
+use tracing_batteries::prelude::*;
+
+/// A response which was previously fetched from a registry, along with the
+/// information needed to revalidate it with the server.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CachedResponse {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The UNIX timestamp (in seconds) at which this response was last fetched or revalidated.
+    pub fetched_at: i64,
+    pub body: String,
+}
+
+impl CachedResponse {
+    pub fn new(url: &str, etag: Option<String>, body: String) -> Self {
+        Self {
+            url: url.to_string(),
+            etag,
+            fetched_at: chrono::Utc::now().timestamp(),
+            body,
+        }
+    }
+
+    /// Marks this response as having been revalidated with the server just now.
+    pub fn revalidated(self) -> Self {
+        Self {
+            fetched_at: chrono::Utc::now().timestamp(),
+            ..self
+        }
+    }
+
+    /// Whether this response was fetched within the last `ttl` seconds and can be
+    /// used without revalidating it.
+    pub fn is_fresh(&self, ttl: i64) -> bool {
+        chrono::Utc::now().timestamp() - self.fetched_at < ttl
+    }
+}
+
+/// Stores the responses received from a registry on disk so that they can be
+/// reused (and revalidated) later, or used when the registry is unreachable.
+pub struct RegistryCache {
+    path: PathBuf,
+}
+
+impl RegistryCache {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn file(&self, url: &str) -> PathBuf {
+        self.path.join(format!("{}.json", cache_key(url)))
+    }
+
+    pub async fn get(&self, url: &str) -> Option<CachedResponse> {
+        let content = tokio::fs::read(self.file(url)).await.ok()?;
+        match serde_json::from_slice(&content) {
+            Ok(cached) => Some(cached),
+            Err(err) => {
+                warn!(
+                    "Ignoring the unreadable registry cache entry for {}: {}",
+                    url, err
+                );
+                None
+            }
+        }
+    }
+
+    /// Lists all of the responses which are currently held in the cache.
+    pub async fn list(&self) -> Vec<CachedResponse> {
+        let mut responses = Vec::new();
+
+        let Ok(mut dir) = tokio::fs::read_dir(&self.path).await else {
+            return responses;
+        };
+
+        while let Ok(Some(entry)) = dir.next_entry().await {
+            if let Ok(content) = tokio::fs::read(entry.path()).await
+                && let Ok(cached) = serde_json::from_slice::<CachedResponse>(&content)
+            {
+                responses.push(cached);
+            }
+        }
+
+        responses
+    }
+
+    pub async fn set(&self, response: &CachedResponse) -> Result<(), human_errors::Error> {
+        tokio::fs::create_dir_all(&self.path).await.wrap_user_err(
+            format!(
+                "Could not create the registry cache directory '{}' due to an OS-level error.",
+                self.path.display()
+            ),
+            &["Make sure that Git-Tool has permission to write to your cache directory and then try again."],
+        )?;
+
+        let content = serde_json::to_vec(response).to_human_error()?;
+        tokio::fs::write(self.file(&response.url), content)
+            .await
+            .wrap_user_err(
+                format!(
+                    "Could not write to the registry cache directory '{}' due to an OS-level error.",
+                    self.path.display()
+                ),
+                &["Make sure that Git-Tool has permission to write to your cache directory and then try again."],
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn get_and_set() {
+        let temp = tempdir().unwrap();
+        let cache = RegistryCache::new(temp.path().join("cache"));
+
+        assert!(cache.get("https://example.com/index.yaml").await.is_none());
+        assert!(cache.list().await.is_empty());
+
+        cache
+            .set(&CachedResponse::new(
+                "https://example.com/index.yaml",
+                Some("\"abc\"".into()),
+                "- apps/test".into(),
+            ))
+            .await
+            .unwrap();
+
+        let cached = cache.get("https://example.com/index.yaml").await.unwrap();
+        assert_eq!(cached.etag.as_deref(), Some("\"abc\""));
+        assert_eq!(cached.body, "- apps/test");
+        assert!(cached.is_fresh(60));
+        assert_eq!(cache.list().await.len(), 1);
+    }
+
+    #[test]
+    fn is_fresh() {
+        let mut cached = CachedResponse::new("https://example.com/index.yaml", None, "".into());
+        assert!(cached.is_fresh(60));
+
+        cached.fetched_at -= 120;
+        assert!(!cached.is_fresh(60));
+        assert!(cached.revalidated().is_fresh(60));
+    }
+}
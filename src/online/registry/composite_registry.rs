@@ -0,0 +1,203 @@
+use super::*;
+use std::collections::HashSet;
+use tracing_batteries::prelude::*;
+
+/// Searches a list of registries in priority order, allowing the registries in
+/// your config file to provide (or override) templates from the Git-Tool registry.
+pub struct CompositeRegistry {
+    registries: Vec<Box<dyn Registry>>,
+}
+
+impl CompositeRegistry {
+    pub fn new(registries: Vec<Box<dyn Registry>>) -> Self {
+        Self { registries }
+    }
+
+    /// Creates a registry which searches each of the registries in your config file,
+    /// followed by the built-in Git-Tool registry.
+    pub fn from_config(config: &Config) -> Self {
+        let mut registries: Vec<Box<dyn Registry>> = Vec::new();
+
+        for source in config.get_registries() {
+            registries.push(match source {
+                RegistrySource::Directory { path } => Box::new(FileRegistry::new(path.clone())),
+                RegistrySource::Git { git, branch } => Box::new(GitRegistry::new(
+                    git,
+                    branch.as_deref(),
+                    config
+                        .get_registry_cache_directory()
+                        .join(cache_key(&source.to_string())),
+                )),
+                RegistrySource::Http { url } => Box::new(HttpRegistry::new(url)),
+            });
+        }
+
+        registries.push(Box::new(GitHubRegistry::new(
+            config.get_registry_cache_directory().join("github"),
+        )));
+
+        Self::new(registries)
+    }
+}
+
+#[async_trait::async_trait]
+impl Registry for CompositeRegistry {
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_entries(&self, core: &Core) -> Result<Vec<String>, human_errors::Error> {
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        let mut first_error = None;
+        let mut succeeded = false;
+
+        for registry in self.registries.iter() {
+            match registry.get_entries(core).await {
+                Ok(registry_entries) => {
+                    succeeded = true;
+                    for entry in registry_entries {
+                        if seen.insert(entry.clone()) {
+                            entries.push(entry);
+                        }
+                    }
+                }
+                Err(err) => {
+                    warn!(
+                        "Could not list the entries in one of your registries: {}",
+                        err
+                    );
+                    if first_error.is_none() {
+                        first_error = Some(err);
+                    }
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) if !succeeded => Err(err),
+            _ => Ok(entries),
+        }
+    }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_entry(&self, core: &Core, id: &str) -> Result<Entry, human_errors::Error> {
+        let mut last_error = None;
+
+        for registry in self.registries.iter() {
+            match registry.get_entry(core, id).await {
+                Ok(entry) => return Ok(entry),
+                Err(err) => {
+                    debug!("Could not find {} in a registry: {}", id, err);
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            human_errors::user(
+                format!("Could not find {id} in any of your registries."),
+                &["Add a registry to your config file, or check the `registries` you have configured."],
+            )
+        }))
+    }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn refresh(&self, core: &Core) -> Result<(), human_errors::Error> {
+        let mut first_error = None;
+
+        for registry in self.registries.iter() {
+            if let Err(err) = registry.refresh(core).await {
+                warn!("Could not refresh one of your registries: {}", err);
+                if first_error.is_none() {
+                    first_error = Some(err);
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn write_entry(registry: &std::path::Path, id: &str, name: &str) {
+        let path = registry.join(format!("{id}.yaml"));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            path,
+            format!(
+                "name: {name}\ndescription: A test entry.\nconfigs:\n  - platform: any\n    app:\n      name: test\n      command: test\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn priority_order() {
+        let temp = tempdir().unwrap();
+        let first = temp.path().join("first");
+        let second = temp.path().join("second");
+
+        write_entry(&first, "apps/shared", "First Shared");
+        write_entry(&first, "apps/first", "First");
+        write_entry(&second, "apps/shared", "Second Shared");
+        write_entry(&second, "apps/second", "Second");
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+        let registry = CompositeRegistry::new(vec![
+            Box::new(FileRegistry::new(first)),
+            Box::new(FileRegistry::new(second)),
+        ]);
+
+        let mut entries = registry.get_entries(&core).await.unwrap();
+        entries.sort();
+        assert_eq!(entries, vec!["apps/first", "apps/second", "apps/shared"]);
+
+        assert_eq!(
+            registry.get_entry(&core, "apps/shared").await.unwrap().name,
+            "First Shared"
+        );
+        assert_eq!(
+            registry.get_entry(&core, "apps/second").await.unwrap().name,
+            "Second"
+        );
+        registry
+            .get_entry(&core, "apps/missing")
+            .await
+            .expect_err("entries which are not in any registry should return an error");
+    }
+
+    #[tokio::test]
+    async fn unavailable_registry() {
+        let temp = tempdir().unwrap();
+        let available = temp.path().join("available");
+        write_entry(&available, "apps/test", "Test");
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+        let registry = CompositeRegistry::new(vec![
+            Box::new(FileRegistry::new(temp.path().join("missing"))),
+            Box::new(FileRegistry::new(available)),
+        ]);
+
+        assert_eq!(
+            registry.get_entries(&core).await.unwrap(),
+            vec!["apps/test"]
+        );
+
+        let registry = CompositeRegistry::new(vec![Box::new(FileRegistry::new(
+            temp.path().join("missing"),
+        ))]);
+        registry
+            .get_entries(&core)
+            .await
+            .expect_err("an error should be returned if no registries are available");
+    }
+}
@@ -9,8 +9,7 @@ pub struct FileRegistry {
 }
 
 impl FileRegistry {
-    #[allow(dead_code)]
-    fn new(path: PathBuf) -> Self {
+    pub fn new(path: PathBuf) -> Self {
         Self { path }
     }
 }
@@ -150,152 +149,27 @@ mod registry_compliance {
         name: &str,
     ) -> Result<bool, human_errors::Error> {
         let core = Core::builder().with_default_config().build();
-        let entry = registry.get_entry(&core, name).await?;
+        registry.get_entry(&core, name).await?;
         let mut valid = true;
 
-        let is_app = name.starts_with("apps/");
-        let is_service = name.starts_with("services/");
-
         if !name.is_ascii() {
             println!("- {name} has a non-ascii ID");
             valid = false;
         }
 
-        if entry.name.is_empty() {
-            println!("- {name} has an empty name field");
-            valid = false;
-        }
+        let path = registry.path.join(format!("{name}.yaml"));
+        let content = read_to_string(&path).wrap_user_err(
+            format!("Could not read the registry entry '{}'.", path.display()),
+            &["Check that the file exists and that Git-Tool has read access to it."],
+        )?;
 
-        if entry.description.is_empty() {
-            println!("- {name} has an empty description field");
+        // The registry is held to a higher standard than other templates, so warnings
+        // are treated as errors here.
+        for diagnostic in validate_template(Some(&path), &content) {
+            println!("- {diagnostic}");
             valid = false;
         }
 
-        let test_repo = Repo::new(
-            "example.com:test/repo",
-            PathBuf::from("/dev/example.com/test/repo"),
-        );
-
-        for config in entry.configs {
-            if config.platform.is_empty() {
-                println!("- {name} has a config which is missing the platform field",);
-                valid = false;
-            }
-
-            if is_app && config.app.is_none() {
-                println!(
-                    "- {name} is in the apps/ namespace but has a configuration which is missing an app setting"
-                );
-                valid = false;
-            }
-
-            if is_service && config.service.is_none() {
-                println!(
-                    "- {name} is in the services/ namespace but has a configuration which is missing a service setting"
-                );
-                valid = false;
-            }
-
-            if let Some(app) = config.app {
-                if app.name.is_empty() {
-                    println!(
-                        "- {}#{} has an app entry which is missing its name",
-                        name, &config.platform
-                    );
-                    valid = false;
-                }
-
-                if app.command.is_empty() {
-                    println!(
-                        "- {}#{} has an app entry which is missing its command",
-                        name, &config.platform
-                    );
-                    valid = false;
-                }
-            }
-
-            if let Some(svc) = config.service {
-                if svc.name.is_empty() {
-                    println!(
-                        "- {}#{} has a service entry which is missing its domain",
-                        name, &config.platform
-                    );
-                    valid = false;
-                }
-
-                if svc.pattern.is_empty() {
-                    println!(
-                        "- {}#{} has a service entry which is missing its pattern",
-                        name, &config.platform
-                    );
-                    valid = false;
-                } else if !valid_service_pattern(&svc.pattern) {
-                    println!(
-                        "- {}#{} has a service entry with an invalid pattern, it should match the regex: /^\\*(\\/\\*)*$/",
-                        name, &config.platform
-                    );
-                    valid = false;
-                }
-
-                if svc.website.is_empty() {
-                    println!(
-                        "- {}#{} has a service entry which is missing its website template",
-                        name, &config.platform
-                    );
-                    valid = false;
-                }
-
-                if svc.git_url.is_empty() {
-                    println!(
-                        "- {}#{} has a service entry which is missing its Git URL template",
-                        name, &config.platform
-                    );
-                    valid = false;
-                }
-
-                if valid {
-                    let test_service: Service = svc.into();
-
-                    if let Err(err) = test_service.get_website(&test_repo) {
-                        println!(
-                            "- {}#{} could not render the website URL for a repository: {}",
-                            name,
-                            &config.platform,
-                            err.message()
-                        );
-                        valid = false;
-                    }
-
-                    if let Err(err) = test_service.get_git_url(&test_repo) {
-                        println!(
-                            "- {}#{} could not render the Git URL for a repository: {}",
-                            name,
-                            &config.platform,
-                            err.message()
-                        );
-                        valid = false;
-                    }
-                }
-            }
-        }
-
         Ok(valid)
     }
-
-    fn valid_service_pattern(pattern: &str) -> bool {
-        let mut expecting_slash = false;
-        for c in pattern.chars() {
-            if expecting_slash && c != '/' {
-                return false;
-            }
-
-            if !expecting_slash && c != '*' {
-                return false;
-            }
-
-            expecting_slash = !expecting_slash;
-        }
-
-        true
-    }
 }
@@ -0,0 +1,204 @@
+use super::*;
+use crate::git;
+use human_errors::ResultExt;
+use std::path::PathBuf;
+use tokio::process::Command;
+use tokio::sync::OnceCell;
+use tracing_batteries::prelude::*;
+
+/// A registry which is stored in a Git repository, either in its root or in a
+/// `registry/` directory (matching the layout of the Git-Tool repository). The
+/// repository is cloned into `path` the first time it is used and updated the first
+/// time each `GitRegistry` is used after that. Concurrent lookups wait for that sync
+/// to complete rather than racing one another in the cache directory.
+pub struct GitRegistry {
+    url: String,
+    branch: Option<String>,
+    path: PathBuf,
+    registry_dir: OnceCell<PathBuf>,
+}
+
+impl GitRegistry {
+    pub fn new(url: &str, branch: Option<&str>, path: PathBuf) -> Self {
+        Self {
+            url: url.to_string(),
+            branch: branch.map(|b| b.to_string()),
+            path,
+            registry_dir: OnceCell::new(),
+        }
+    }
+
+    async fn checkout(&self) -> Result<FileRegistry, human_errors::Error> {
+        let registry_dir = self.registry_dir.get_or_try_init(|| self.sync()).await?;
+        Ok(FileRegistry::new(registry_dir.clone()))
+    }
+
+    /// Clones (or updates) the registry's repository and returns the directory which
+    /// holds its entries.
+    async fn sync(&self) -> Result<PathBuf, human_errors::Error> {
+        if !self.path.join(".git").exists() {
+            if let Some(parent) = self.path.parent() {
+                tokio::fs::create_dir_all(parent).await.wrap_user_err(
+                    format!(
+                        "Could not create the registry cache directory '{}' due to an OS-level error.",
+                        parent.display()
+                    ),
+                    &["Make sure that Git-Tool has permission to write to your cache directory and then try again."],
+                )?;
+            }
+
+            info!(
+                "Cloning the registry {} into {}",
+                self.url,
+                self.path.display()
+            );
+            let mut cmd = Command::new("git");
+            cmd.arg("clone").arg("--depth=1");
+            if let Some(branch) = &self.branch {
+                cmd.arg("--branch").arg(branch);
+            }
+            git::git_cmd(cmd.arg(&self.url).arg(&self.path)).await?;
+        } else if let Err(err) = git::git_pull(&self.path).await {
+            warn!(
+                "Could not update the registry {}, using the copy which was previously downloaded instead: {}",
+                self.url, err
+            );
+        }
+
+        let registry_dir = self.path.join("registry");
+        if registry_dir.is_dir() {
+            Ok(registry_dir)
+        } else {
+            Ok(self.path.clone())
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Registry for GitRegistry {
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_entries(&self, core: &Core) -> Result<Vec<String>, human_errors::Error> {
+        self.checkout().await?.get_entries(core).await
+    }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_entry(&self, core: &Core, id: &str) -> Result<Entry, human_errors::Error> {
+        self.checkout().await?.get_entry(core, id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    async fn create_registry_repo(path: &std::path::Path) {
+        git::git_init(path).await.unwrap();
+        git::git_config_set(path, "user.name", "Test User")
+            .await
+            .unwrap();
+        git::git_config_set(path, "user.email", "test@example.com")
+            .await
+            .unwrap();
+
+        tokio::fs::create_dir_all(path.join("registry").join("apps"))
+            .await
+            .unwrap();
+        tokio::fs::write(
+            path.join("registry").join("apps").join("internal-ide.yaml"),
+            r#"
+name: Internal IDE
+description: The IDE used within our company.
+configs:
+  - platform: any
+    app:
+      name: ide
+      command: ide
+"#,
+        )
+        .await
+        .unwrap();
+
+        git::git_add(path, &vec!["."]).await.unwrap();
+        git::git_commit(path, "Add the internal IDE", &vec![])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn get_entries() {
+        let temp = tempdir().unwrap();
+        let source = temp.path().join("source");
+        create_registry_repo(&source).await;
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+        let registry = GitRegistry::new(
+            source.to_str().unwrap(),
+            None,
+            temp.path().join("cache").join("registry"),
+        );
+
+        let entries = registry.get_entries(&core).await.unwrap();
+        assert_eq!(entries, vec!["apps/internal-ide"]);
+
+        let entry = registry
+            .get_entry(&core, "apps/internal-ide")
+            .await
+            .unwrap();
+        assert_eq!(entry.name, "Internal IDE");
+    }
+
+    #[tokio::test]
+    async fn syncs_once_per_instance() {
+        let temp = tempdir().unwrap();
+        let source = temp.path().join("source");
+        create_registry_repo(&source).await;
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+        let registry = GitRegistry::new(
+            source.to_str().unwrap(),
+            None,
+            temp.path().join("cache").join("registry"),
+        );
+
+        let (entries, entry) = futures::join!(
+            registry.get_entries(&core),
+            registry.get_entry(&core, "apps/internal-ide")
+        );
+        assert_eq!(entries.unwrap(), vec!["apps/internal-ide"]);
+        assert_eq!(entry.unwrap().name, "Internal IDE");
+
+        tokio::fs::write(
+            source.join("registry").join("apps").join("other-ide.yaml"),
+            "name: Other IDE\ndescription: Another IDE.\nconfigs: []\n",
+        )
+        .await
+        .unwrap();
+        git::git_add(&source, &vec!["."]).await.unwrap();
+        git::git_commit(&source, "Add the other IDE", &vec![])
+            .await
+            .unwrap();
+
+        // The registry has already been synced, so it doesn't pull again...
+        assert_eq!(
+            registry.get_entries(&core).await.unwrap(),
+            vec!["apps/internal-ide"]
+        );
+
+        // ...while a new instance picks up the latest changes.
+        let mut entries = GitRegistry::new(
+            source.to_str().unwrap(),
+            None,
+            temp.path().join("cache").join("registry"),
+        )
+        .get_entries(&core)
+        .await
+        .unwrap();
+        entries.sort();
+        assert_eq!(entries, vec!["apps/internal-ide", "apps/other-ide"]);
+    }
+}
@@ -4,10 +4,33 @@ use human_errors::ResultExt;
 use serde::Deserialize;
 use tracing_batteries::prelude::*;
 
-pub struct GitHubRegistry;
+/// How long (in seconds) responses from the Git-Tool registry are used before
+/// they are revalidated with GitHub.
+const CACHE_TTL: i64 = 60 * 60;
+
+const TREE_URL: &str =
+    "https://api.github.com/repos/SierraSoftworks/git-tool/git/trees/main?recursive=true";
+
+/// The built-in Git-Tool registry, which is hosted on GitHub. Responses are cached
+/// on disk so that the registry remains usable when GitHub is unreachable, or when
+/// we have been rate limited.
+pub struct GitHubRegistry {
+    cache: RegistryCache,
+}
 
 impl GitHubRegistry {
-    async fn get(&self, core: &Core, url: &str) -> Result<reqwest::Response, human_errors::Error> {
+    pub fn new(cache_path: PathBuf) -> Self {
+        Self {
+            cache: RegistryCache::new(cache_path),
+        }
+    }
+
+    async fn get(
+        &self,
+        core: &Core,
+        url: &str,
+        etag: Option<&str>,
+    ) -> Result<reqwest::Response, human_errors::Error> {
         let uri: reqwest::Url = url.parse().wrap_system_err(
             format!("Unable to parse GitHub API URL '{url}'."),
             &["Please report this error to us by opening a ticket in GitHub."],
@@ -16,7 +39,6 @@ impl GitHubRegistry {
         // NOTE: This allows us to consume the GITHUB_TOKEN environment variable in the test
         // environment to bypass rate limiting restrictions.
         // TODO: We should probably support using the users github.com token here to avoid rate limiting
-        #[allow(unused_mut)]
         let mut req = reqwest::Request::new(reqwest::Method::GET, uri);
 
         req.headers_mut().append(
@@ -30,6 +52,16 @@ impl GitHubRegistry {
             )?,
         );
 
+        if let Some(etag) = etag {
+            req.headers_mut().append(
+                reqwest::header::IF_NONE_MATCH,
+                etag.parse().wrap_system_err(
+                    format!("Unable to parse the cached ETag {etag} for {url}."),
+                    &["Run `git-tool config registry refresh` to replace your cached copy of the registry."],
+                )?,
+            );
+        }
+
         #[cfg(test)]
         {
             if let Ok(token) = std::env::var("GITHUB_TOKEN") {
@@ -45,38 +77,78 @@ impl GitHubRegistry {
 
         core.http_client().request(req).await
     }
-}
 
-#[async_trait::async_trait]
-impl Registry for GitHubRegistry {
-    #[tracing::instrument(err, skip(self, core))]
-    async fn get_entries(&self, core: &Core) -> Result<Vec<String>, human_errors::Error> {
-        let resp = self.get(core, "https://api.github.com/repos/SierraSoftworks/git-tool/git/trees/main?recursive=true").await?;
-
-        match resp.status() {
-            reqwest::StatusCode::OK => {
-                let tree: GitHubTree = resp.json().await.to_human_error()?;
-
-                let mut entries: Vec<String> = Vec::new();
-
-                let prefix = "registry/";
-                let suffix = ".yaml";
-
-                for node in tree.tree {
-                    if node.node_type == "blob"
-                        && node.path.starts_with(prefix)
-                        && node.path.ends_with(suffix)
-                    {
-                        let len = node.path.len();
-                        let name: String = node.path[prefix.len()..(len - suffix.len())].into();
-                        debug!("Found entry '{}'", &name);
-                        entries.push(name);
+    /// Fetches `url`, using the cached response if it is fresh (or `refresh` is not set),
+    /// revalidating it with its ETag otherwise, and falling back to it if GitHub cannot
+    /// be reached.
+    async fn fetch(
+        &self,
+        core: &Core,
+        url: &str,
+        what: &str,
+        refresh: bool,
+    ) -> Result<String, human_errors::Error> {
+        let cached = self.cache.get(url).await;
+        if let Some(cached) = cached.as_ref()
+            && !refresh
+            && cached.is_fresh(CACHE_TTL)
+        {
+            debug!("Using the cached copy of {}", url);
+            return Ok(cached.body.clone());
+        }
+
+        let resp = match self
+            .get(core, url, cached.as_ref().and_then(|c| c.etag.as_deref()))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(err) => {
+                return match cached {
+                    Some(cached) => {
+                        warn!(
+                            "Could not reach the Git-Tool registry, using the cached copy of {} instead: {}",
+                            what, err
+                        );
+                        Ok(cached.body)
                     }
-                }
+                    None => Err(err),
+                };
+            }
+        };
+
+        match (resp.status(), cached) {
+            (reqwest::StatusCode::NOT_MODIFIED, Some(cached)) => {
+                debug!("The cached copy of {} is still up to date", url);
+                let cached = cached.revalidated();
+                self.save(&cached).await;
+                Ok(cached.body)
+            }
+            (reqwest::StatusCode::OK, _) => {
+                let etag = resp
+                    .headers()
+                    .get(reqwest::header::ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(|etag| etag.to_string());
+                let body = resp.text().await.to_human_error()?;
 
-                Ok(entries)
+                self.save(&CachedResponse::new(url, etag, body.clone()))
+                    .await;
+                Ok(body)
             }
-            reqwest::StatusCode::TOO_MANY_REQUESTS | reqwest::StatusCode::FORBIDDEN => {
+            (reqwest::StatusCode::NOT_FOUND, _) => Err(human_errors::user(
+                format!("Could not find {what} in the Git-Tool registry."),
+                &[
+                    "Please make sure that you've selected a configuration entry which exists in the registry. You can check this with `git-tool config list`.",
+                ],
+            )),
+            (status, Some(cached)) => {
+                warn!(
+                    "Received an HTTP {} response from GitHub, using the cached copy of {} instead.",
+                    status, what
+                );
+                Ok(cached.body)
+            }
+            (reqwest::StatusCode::TOO_MANY_REQUESTS | reqwest::StatusCode::FORBIDDEN, None) => {
                 let inner_error = errors::reqwest::ResponseError::with_body(resp).await;
                 Err(human_errors::wrap_user(
                     inner_error,
@@ -84,12 +156,12 @@ impl Registry for GitHubRegistry {
                     &["Please wait until GitHub removes this rate limit before trying again."],
                 ))
             }
-            status => {
+            (status, None) => {
                 let inner_error = errors::reqwest::ResponseError::with_body(resp).await;
                 Err(human_errors::wrap_system(
                     inner_error,
                     format!(
-                        "Received an HTTP {status} response from GitHub when attempting to list items in the Git-Tool registry."
+                        "Received an HTTP {status} response from GitHub when attempting to fetch {what} from the Git-Tool registry."
                     ),
                     &[
                         "Please read the error message below and decide if there is something you can do to fix the problem, or report it to us on GitHub.",
@@ -99,54 +171,90 @@ impl Registry for GitHubRegistry {
         }
     }
 
+    async fn save(&self, response: &CachedResponse) {
+        if let Err(err) = self.cache.set(response).await {
+            warn!(
+                "Could not cache the response from {}: {}",
+                response.url, err
+            );
+        }
+    }
+
+    async fn get_tree(
+        &self,
+        core: &Core,
+        refresh: bool,
+    ) -> Result<Vec<String>, human_errors::Error> {
+        let body = self
+            .fetch(core, TREE_URL, "the list of entries", refresh)
+            .await?;
+        let tree: GitHubTree = serde_json::from_str(&body).to_human_error()?;
+
+        let mut entries: Vec<String> = Vec::new();
+
+        let prefix = "registry/";
+        let suffix = ".yaml";
+
+        for node in tree.tree {
+            if node.node_type == "blob"
+                && node.path.starts_with(prefix)
+                && node.path.ends_with(suffix)
+            {
+                let len = node.path.len();
+                let name: String = node.path[prefix.len()..(len - suffix.len())].into();
+                debug!("Found entry '{}'", &name);
+                entries.push(name);
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+#[async_trait::async_trait]
+impl Registry for GitHubRegistry {
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_entries(&self, core: &Core) -> Result<Vec<String>, human_errors::Error> {
+        self.get_tree(core, false).await
+    }
+
     #[tracing::instrument(err, skip(self, core))]
     async fn get_entry(&self, core: &Core, id: &str) -> Result<Entry, human_errors::Error> {
-        let resp = self
-            .get(
+        let body = self
+            .fetch(
                 core,
                 &format!(
-            "https://raw.githubusercontent.com/SierraSoftworks/git-tool/main/registry/{id}.yaml"
-        ),
+                    "https://raw.githubusercontent.com/SierraSoftworks/git-tool/main/registry/{id}.yaml"
+                ),
+                id,
+                false,
             )
             .await?;
 
-        match resp.status() {
-            reqwest::StatusCode::OK => {
-                let body = resp.bytes().await.to_human_error()?;
-                let entity = serde_yaml::from_slice(&body).wrap_system_err(
-                    format!("Could not parse /registry/{id}.yaml from the Git-Tool registry."),
-                    &["Please report this issue to us by creating a new GitHub issue."],
-                )?;
-                debug!("{}", entity);
-                Ok(entity)
-            }
-            reqwest::StatusCode::NOT_FOUND => Err(human_errors::user(
-                format!("Could not find {id} in the Git-Tool registry."),
-                &[
-                    "Please make sure that you've selected a configuration entry which exists in the registry. You can check this with `git-tool config list`.",
-                ],
-            )),
-            reqwest::StatusCode::TOO_MANY_REQUESTS | reqwest::StatusCode::FORBIDDEN => {
-                let inner_error = errors::reqwest::ResponseError::with_body(resp).await;
-                Err(human_errors::wrap_user(
-                    inner_error,
-                    "GitHub has rate limited requests from your IP address.",
-                    &["Please wait until GitHub removes this rate limit before trying again."],
-                ))
-            }
-            status => {
-                let inner_error = errors::reqwest::ResponseError::with_body(resp).await;
-                Err(human_errors::wrap_system(
-                    inner_error,
-                    format!(
-                        "Received an HTTP {status} response from GitHub when attempting to fetch /registry/{id}.yaml."
-                    ),
-                    &[
-                        "Please read the error message below and decide if there is something you can do to fix the problem, or report it to us on GitHub.",
-                    ],
-                ))
+        let entity = serde_yaml::from_str(&body).wrap_system_err(
+            format!("Could not parse /registry/{id}.yaml from the Git-Tool registry."),
+            &["Please report this issue to us by creating a new GitHub issue."],
+        )?;
+        debug!("{}", entity);
+        Ok(entity)
+    }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn refresh(&self, core: &Core) -> Result<(), human_errors::Error> {
+        self.get_tree(core, true).await?;
+
+        for cached in self.cache.list().await {
+            if cached.url != TREE_URL
+                && let Err(err) = self.fetch(core, &cached.url, &cached.url, true).await
+            {
+                warn!(
+                    "Could not refresh the cached copy of {}: {}",
+                    cached.url, err
+                );
             }
         }
+
+        Ok(())
     }
 }
 
@@ -171,8 +279,9 @@ mod tests {
     #[tokio::test]
     #[cfg_attr(feature = "pure-tests", ignore)]
     async fn get_entries() {
+        let temp = tempfile::tempdir().unwrap();
         let core = Core::builder().with_default_config().build();
-        let registry = GitHubRegistry;
+        let registry = GitHubRegistry::new(temp.path().to_path_buf());
 
         let entries = registry.get_entries(&core).await.unwrap();
         assert_ne!(entries.len(), 0);
@@ -182,10 +291,102 @@ mod tests {
     #[tokio::test]
     #[cfg_attr(feature = "pure-tests", ignore)]
     async fn get_entry() {
+        let temp = tempfile::tempdir().unwrap();
         let core = Core::builder().with_default_config().build();
-        let registry = GitHubRegistry;
+        let registry = GitHubRegistry::new(temp.path().to_path_buf());
 
         let entry = registry.get_entry(&core, "apps/bash").await.unwrap();
         assert_eq!(entry.name, "Bash");
     }
+
+    const ENTRY_URL: &str =
+        "https://raw.githubusercontent.com/SierraSoftworks/git-tool/main/registry/apps/test.yaml";
+
+    fn core(routes: Vec<MockHttpRoute>) -> Core {
+        Core::builder()
+            .with_default_config()
+            .with_mock_http_client(routes)
+            .build()
+    }
+
+    fn tree() -> &'static str {
+        r#"{ "tree": [{ "type": "blob", "path": "registry/apps/test.yaml" }], "truncated": false }"#
+    }
+
+    #[tokio::test]
+    async fn cached_entries() {
+        let temp = tempfile::tempdir().unwrap();
+        let registry = GitHubRegistry::new(temp.path().to_path_buf());
+
+        let core = core(vec![
+            MockHttpRoute::new("GET", TREE_URL, 200, tree()).with_header("ETag", "\"tree-v1\""),
+            MockHttpRoute::new(
+                "GET",
+                ENTRY_URL,
+                200,
+                "name: Test\ndescription: A test entry.\nconfigs: []\n",
+            ),
+        ]);
+        assert_eq!(
+            registry.get_entries(&core).await.unwrap(),
+            vec!["apps/test"]
+        );
+        assert_eq!(
+            registry.get_entry(&core, "apps/test").await.unwrap().name,
+            "Test"
+        );
+
+        // Fresh responses are served from the cache without contacting GitHub
+        let core = self::core(vec![]);
+        assert_eq!(
+            registry.get_entries(&core).await.unwrap(),
+            vec!["apps/test"]
+        );
+        assert_eq!(
+            registry.get_entry(&core, "apps/test").await.unwrap().name,
+            "Test"
+        );
+
+        // Refreshing revalidates the cached responses using their ETags
+        let core = self::core(vec![
+            MockHttpRoute::new("GET", TREE_URL, 304, "")
+                .with_request_header("If-None-Match", "\"tree-v1\""),
+            MockHttpRoute::new(
+                "GET",
+                ENTRY_URL,
+                200,
+                "name: Updated Test\ndescription: A test entry.\nconfigs: []\n",
+            ),
+        ]);
+        registry.refresh(&core).await.unwrap();
+        assert_eq!(
+            registry.get_entry(&core, "apps/test").await.unwrap().name,
+            "Updated Test"
+        );
+    }
+
+    #[tokio::test]
+    async fn offline_fallback() {
+        let temp = tempfile::tempdir().unwrap();
+        let registry = GitHubRegistry::new(temp.path().to_path_buf());
+
+        let core = core(vec![MockHttpRoute::new("GET", TREE_URL, 503, "")]);
+        registry
+            .get_entries(&core)
+            .await
+            .expect_err("an error should be returned when there is no cached copy");
+
+        let core = self::core(vec![MockHttpRoute::new("GET", TREE_URL, 200, tree())]);
+        registry.get_entries(&core).await.unwrap();
+
+        let core = self::core(vec![MockHttpRoute::new("GET", TREE_URL, 503, "")]);
+        registry
+            .refresh(&core)
+            .await
+            .expect("the cached copy should be used when GitHub is unavailable");
+        assert_eq!(
+            registry.get_entries(&core).await.unwrap(),
+            vec!["apps/test"]
+        );
+    }
 }
@@ -0,0 +1,160 @@
+use super::*;
+use crate::errors::{self, HumanErrorResultExt};
+use human_errors::ResultExt;
+use tracing_batteries::prelude::*;
+
+/// A registry which is served over HTTP, providing an `index.yaml` file which
+/// lists the IDs of its entries (e.g. `apps/bash`) and a `{id}.yaml` file for each
+/// of those entries.
+pub struct HttpRegistry {
+    url: String,
+}
+
+impl HttpRegistry {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn get(&self, core: &Core, path: &str) -> Result<Vec<u8>, human_errors::Error> {
+        let url = format!("{}/{}", self.url, path);
+        let uri: reqwest::Url = url.parse().wrap_user_err(
+            format!("The registry URL '{url}' is not a valid URL."),
+            &["Check the `url` of the registries listed in your config file."],
+        )?;
+
+        let mut req = reqwest::Request::new(reqwest::Method::GET, uri);
+        req.headers_mut().append(
+            "User-Agent",
+            version!("Git-Tool/").parse().wrap_system_err(
+                format!(
+                    "Unable to parse Git-Tool user agent header {}.",
+                    version!("Git-Tool/")
+                ),
+                &["Please report this error to us by opening a ticket in GitHub."],
+            )?,
+        );
+
+        let resp = core.http_client().request(req).await?;
+        match resp.status() {
+            reqwest::StatusCode::OK => Ok(resp.bytes().await.to_human_error()?.to_vec()),
+            reqwest::StatusCode::NOT_FOUND => Err(human_errors::user(
+                format!("Could not find {path} in the registry at {}.", self.url),
+                &[
+                    "Please make sure that you've selected a configuration entry which exists in the registry. You can check this with `git-tool config list`.",
+                ],
+            )),
+            status => {
+                let inner_error = errors::reqwest::ResponseError::with_body(resp).await;
+                Err(human_errors::wrap_user(
+                    inner_error,
+                    format!(
+                        "Received an HTTP {status} response from the registry at {} when attempting to fetch {path}.",
+                        self.url
+                    ),
+                    &[
+                        "Check that the registry is available and that the `url` in your config file is correct.",
+                    ],
+                ))
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Registry for HttpRegistry {
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_entries(&self, core: &Core) -> Result<Vec<String>, human_errors::Error> {
+        let body = self.get(core, "index.yaml").await?;
+        let entries: Vec<String> = serde_yaml::from_slice(&body).wrap_user_err(
+            format!(
+                "Could not parse the index.yaml file from the registry at {}.",
+                self.url
+            ),
+            &["Make sure that the registry's index.yaml file contains a list of the entries it provides, such as `apps/bash`."],
+        )?;
+
+        debug!("Found {} entries in {}", entries.len(), self.url);
+        Ok(entries)
+    }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_entry(&self, core: &Core, id: &str) -> Result<Entry, human_errors::Error> {
+        let body = self.get(core, &format!("{id}.yaml")).await?;
+        serde_yaml::from_slice(&body).wrap_user_err(
+            format!(
+                "Could not parse {id}.yaml from the registry at {}.",
+                self.url
+            ),
+            &["Check that the registry entry is valid YAML and matches the registry entry schema."],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn core() -> Core {
+        Core::builder()
+            .with_default_config()
+            .with_mock_http_client(vec![
+                MockHttpRoute::new(
+                    "GET",
+                    "https://templates.example.com/git-tool/index.yaml",
+                    200,
+                    "- apps/internal-ide\n- services/gitlab-internal\n",
+                ),
+                MockHttpRoute::new(
+                    "GET",
+                    "https://templates.example.com/git-tool/apps/internal-ide.yaml",
+                    200,
+                    r#"
+name: Internal IDE
+description: The IDE used within our company.
+configs:
+  - platform: any
+    app:
+      name: ide
+      command: ide
+"#,
+                ),
+                MockHttpRoute::new(
+                    "GET",
+                    "https://templates.example.com/git-tool/apps/missing.yaml",
+                    404,
+                    "",
+                ),
+            ])
+            .build()
+    }
+
+    #[tokio::test]
+    async fn get_entries() {
+        let registry = HttpRegistry::new("https://templates.example.com/git-tool/");
+
+        let entries = registry.get_entries(&core()).await.unwrap();
+        assert_eq!(
+            entries,
+            vec!["apps/internal-ide", "services/gitlab-internal"]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_entry() {
+        let registry = HttpRegistry::new("https://templates.example.com/git-tool");
+        let core = core();
+
+        let entry = registry
+            .get_entry(&core, "apps/internal-ide")
+            .await
+            .unwrap();
+        assert_eq!(entry.name, "Internal IDE");
+
+        registry
+            .get_entry(&core, "apps/missing")
+            .await
+            .expect_err("missing entries should return an error");
+    }
+}
@@ -1,35 +1,179 @@
 use crate::engine::*;
+use human_errors::ResultExt;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env::consts::OS;
+use std::io::Write;
 
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+mod cache;
+mod composite_registry;
 mod file_registry;
+mod git_registry;
 mod github_registry;
+mod http_registry;
 
-#[allow(unused_imports)]
+use cache::{CachedResponse, RegistryCache};
+pub use composite_registry::CompositeRegistry;
 pub use file_registry::FileRegistry;
+pub use git_registry::GitRegistry;
 pub use github_registry::GitHubRegistry;
+pub use http_registry::HttpRegistry;
 
 #[async_trait::async_trait]
 pub trait Registry: Send + Sync {
     async fn get_entries(&self, core: &Core) -> Result<Vec<String>, human_errors::Error>;
     async fn get_entry(&self, core: &Core, id: &str) -> Result<Entry, human_errors::Error>;
+
+    /// Updates any copies of this registry which have been cached locally.
+    async fn refresh(&self, core: &Core) -> Result<(), human_errors::Error> {
+        self.get_entries(core).await.map(|_| ())
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
 pub struct Entry {
     pub name: String,
     pub description: String,
     #[serde(default)]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<EntryVariable>,
+    #[serde(default)]
     pub configs: Vec<EntryConfig>,
 }
 
+impl Entry {
+    /// Determines the value of each of this template's variables, using the values
+    /// which have been `provided` and prompting the user for the rest.
+    pub fn get_variables(
+        &self,
+        core: &Core,
+        provided: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, String>, human_errors::Error> {
+        if let Some(name) = provided
+            .keys()
+            .find(|name| !self.variables.iter().any(|v| &v.name == *name))
+        {
+            return Err(human_errors::user(
+                format!(
+                    "The {} template does not have a variable called '{name}'.",
+                    self.name
+                ),
+                &[
+                    "Check the variables which this template declares and remove the `--set` options for any which it doesn't use.",
+                ],
+            ));
+        }
+
+        let mut prompter = core.prompter();
+        let mut values = BTreeMap::new();
+        for variable in self.variables.iter() {
+            let value = match provided.get(&variable.name) {
+                Some(value) => value.clone(),
+                None => prompter
+                    .prompt(&variable.prompt_message(), |value| {
+                        if value.is_empty() {
+                            return true;
+                        }
+
+                        match variable.validate(value) {
+                            Ok(()) => true,
+                            Err(err) => {
+                                writeln!(core.output(), " [!] {}", err.message())
+                                    .unwrap_or_default();
+                                false
+                            }
+                        }
+                    })?
+                    .filter(|value| !value.is_empty())
+                    .or_else(|| variable.default.clone())
+                    .ok_or_else(|| {
+                        human_errors::user(
+                            format!(
+                                "You did not provide a value for the '{}' variable.",
+                                variable.name
+                            ),
+                            &["Enter a value when prompted, or provide one using `--set name=value`."],
+                        )
+                    })?,
+            };
+
+            variable.validate(&value)?;
+            values.insert(variable.name.clone(), value);
+        }
+
+        Ok(values)
+    }
+}
+
+/// A value which is provided when a template is installed, replacing each occurrence
+/// of `{{ .Variables.<name> }}` within the template.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct EntryVariable {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// A regular expression which the entire value must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validation: Option<String>,
+}
+
+impl EntryVariable {
+    pub fn validate(&self, value: &str) -> Result<(), human_errors::Error> {
+        let Some(pattern) = &self.validation else {
+            return Ok(());
+        };
+
+        let regex = regex::Regex::new(&format!("^(?:{pattern})$")).wrap_user_err(
+            format!(
+                "The validation pattern for the '{}' variable is not a valid regular expression.",
+                self.name
+            ),
+            &["Please report this issue to the maintainer of the registry which provides this template."],
+        )?;
+
+        if regex.is_match(value) {
+            Ok(())
+        } else {
+            Err(human_errors::user(
+                format!(
+                    "The value '{value}' is not valid for the '{}' variable, it must match /{pattern}/.",
+                    self.name
+                ),
+                &["Provide a value which matches the pattern required by this variable."],
+            ))
+        }
+    }
+
+    fn prompt_message(&self) -> String {
+        let mut message = if self.description.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({})", self.description, self.name)
+        };
+
+        if let Some(default) = &self.default {
+            message.push_str(&format!(" [{default}]"));
+        }
+
+        message.push_str(": ");
+        message
+    }
+}
+
 impl std::fmt::Display for Entry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", &self.name)
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
 pub struct EntryConfig {
     pub platform: String,
     #[serde(default)]
@@ -50,9 +194,69 @@ impl EntryConfig {
             service: self.service.map(|s| s.with_name(name)),
         }
     }
+
+    /// Replaces each occurrence of `{{ .Variables.<name> }}` in this template with the
+    /// value of the corresponding variable.
+    pub fn with_variables(self, variables: &BTreeMap<String, String>) -> EntryConfig {
+        if variables.is_empty() {
+            return self;
+        }
+
+        let sub = |value: String| substitute_variables(&value, variables);
+
+        EntryConfig {
+            platform: self.platform,
+            app: self.app.map(|a| EntryApp {
+                name: sub(a.name),
+                command: sub(a.command),
+                args: a.args.into_iter().map(sub).collect(),
+                environment: a.environment.into_iter().map(sub).collect(),
+                detect: a.detect,
+            }),
+            service: self.service.map(|s| EntryService {
+                name: sub(s.name),
+                website: sub(s.website),
+                git_url: sub(s.git_url),
+                pattern: sub(s.pattern),
+                api: s.api.map(|api| ServiceAPI {
+                    url: sub(api.url),
+                    ..api
+                }),
+            }),
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// Replaces `{{ .Variables.<name> }}` placeholders in `value`, leaving any other
+/// template expressions (like `{{ .Repo.FullName }}`) to be rendered later.
+pub fn substitute_variables(value: &str, variables: &BTreeMap<String, String>) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start..].find("}}") else {
+            break;
+        };
+
+        let end = start + length + 2;
+        result.push_str(&rest[..start]);
+        match rest[start + 2..start + length]
+            .trim()
+            .strip_prefix(".Variables.")
+            .and_then(|name| variables.get(name))
+        {
+            Some(value) => result.push_str(value),
+            None => result.push_str(&rest[start..end]),
+        }
+
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
 pub struct EntryApp {
     pub name: String,
     pub command: String,
@@ -60,6 +264,8 @@ pub struct EntryApp {
     pub args: Vec<String>,
     #[serde(default)]
     pub environment: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detect: Option<EntryAppDetect>,
 }
 
 impl EntryApp {
@@ -69,8 +275,55 @@ impl EntryApp {
             command: self.command.clone(),
             args: self.args,
             environment: self.environment,
+            detect: self.detect,
+        }
+    }
+
+    /// Determines whether this app is installed, using its `detect` block if it has
+    /// one and otherwise checking whether its command is on your `PATH`.
+    pub fn is_installed(&self) -> bool {
+        match &self.detect {
+            Some(detect) if !detect.binaries.is_empty() => detect
+                .binaries
+                .iter()
+                .any(|binary| crate::fs::find_executable(binary).is_some()),
+            _ => crate::fs::find_executable(&self.command).is_some(),
         }
     }
+
+    /// Runs the `detect.version` command for this app, returning the first line of its
+    /// output if it succeeds.
+    pub async fn get_version(&self) -> Option<String> {
+        let command = self.detect.as_ref()?.version.as_ref()?;
+        let args = shell_words::split(command).ok()?;
+        let (program, args) = args.split_first()?;
+
+        let output = tokio::process::Command::new(crate::fs::find_executable(program)?)
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .output()
+            .await
+            .ok()
+            .filter(|output| output.status.success())?;
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(|line| line.to_string())
+    }
+}
+
+/// Describes how to determine whether an app is installed on your machine.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct EntryAppDetect {
+    /// The names of the binaries which indicate that the app is installed, if any of
+    /// them are on your `PATH`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub binaries: Vec<String>,
+    /// A command which prints the installed version of the app, like `code --version`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 #[allow(clippy::from_over_into)]
@@ -85,7 +338,7 @@ impl Into<App> for EntryApp {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
 pub struct EntryService {
     pub name: String,
     pub website: String,
@@ -117,10 +370,29 @@ impl Into<Service> for EntryService {
             git_url: self.git_url,
             pattern: self.pattern,
             api: self.api,
+            identity: None,
         }
     }
 }
 
+/// A short, filesystem safe, identifier for a registry which is used to name its
+/// directory within the cache.
+fn cache_key(source: &str) -> String {
+    Sha256::digest(source.as_bytes())
+        .iter()
+        .take(8)
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// The platforms which a template's configs may target, using the names produced
+/// by [`translate_os_name`].
+pub const PLATFORMS: &[&str] = &["any", "windows", "linux", "darwin"];
+
+pub fn is_known_platform(platform: &str) -> bool {
+    PLATFORMS.contains(&platform)
+}
+
 fn translate_os_name(name: &str) -> &str {
     match name {
         "macos" => "darwin",
@@ -169,4 +441,123 @@ mod tests {
             OS == "macos"
         );
     }
+
+    #[test]
+    fn substitute_variables() {
+        let variables: BTreeMap<String, String> =
+            [("domain".to_string(), "gitlab.example.com".to_string())].into();
+
+        assert_eq!(
+            super::substitute_variables(
+                "git@{{ .Variables.domain }}:{{ .Repo.FullName }}.git",
+                &variables
+            ),
+            "git@gitlab.example.com:{{ .Repo.FullName }}.git"
+        );
+        assert_eq!(
+            super::substitute_variables(
+                "https://{{.Variables.domain}}/{{ .Variables.missing }}",
+                &variables
+            ),
+            "https://gitlab.example.com/{{ .Variables.missing }}"
+        );
+        assert_eq!(
+            super::substitute_variables("{{ unterminated", &variables),
+            "{{ unterminated"
+        );
+    }
+
+    #[test]
+    fn validate_variable() {
+        let variable = EntryVariable {
+            name: "domain".to_string(),
+            validation: Some(r"[a-z0-9.-]+".to_string()),
+            ..Default::default()
+        };
+
+        assert!(variable.validate("gitlab.example.com").is_ok());
+        assert!(variable.validate("https://gitlab.example.com").is_err());
+        assert!(EntryVariable::default().validate("anything").is_ok());
+    }
+
+    #[test]
+    fn get_variables() {
+        let entry = Entry {
+            name: "GitLab".to_string(),
+            variables: vec![
+                EntryVariable {
+                    name: "domain".to_string(),
+                    description: "The domain of your GitLab instance".to_string(),
+                    validation: Some(r"[a-z0-9.-]+".to_string()),
+                    ..Default::default()
+                },
+                EntryVariable {
+                    name: "user".to_string(),
+                    default: Some("git".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let console = crate::console::mock_with_input("not a domain\ngitlab.example.com\n\n");
+        let core = Core::builder()
+            .with_default_config()
+            .with_console(console.clone())
+            .build();
+
+        let values = entry.get_variables(&core, &BTreeMap::new()).unwrap();
+        assert_eq!(values.get("domain").unwrap(), "gitlab.example.com");
+        assert_eq!(values.get("user").unwrap(), "git");
+
+        let provided: BTreeMap<String, String> =
+            [("domain".to_string(), "https://example.com".to_string())].into();
+        entry
+            .get_variables(&core, &provided)
+            .expect_err("invalid values should be rejected");
+
+        let provided: BTreeMap<String, String> =
+            [("unknown".to_string(), "value".to_string())].into();
+        entry
+            .get_variables(&core, &provided)
+            .expect_err("unknown variables should be rejected");
+    }
+
+    #[tokio::test]
+    async fn detect_installed_apps() {
+        let app = EntryApp {
+            name: "git".to_string(),
+            command: "git".to_string(),
+            ..Default::default()
+        };
+        assert!(app.is_installed());
+        assert_eq!(app.get_version().await, None);
+
+        let app = EntryApp {
+            name: "missing".to_string(),
+            command: "git-tool-definitely-not-a-real-command".to_string(),
+            ..Default::default()
+        };
+        assert!(!app.is_installed());
+
+        let app = EntryApp {
+            name: "code".to_string(),
+            command: "cmd.exe".to_string(),
+            detect: Some(EntryAppDetect {
+                binaries: vec![
+                    "git-tool-definitely-not-a-real-command".to_string(),
+                    "git".to_string(),
+                ],
+                version: Some("git --version".to_string()),
+            }),
+            ..Default::default()
+        };
+        assert!(app.is_installed());
+        assert!(
+            app.get_version()
+                .await
+                .is_some_and(|version| version.starts_with("git version")),
+            "the version of the app should be detected"
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use super::*;
 use base64::prelude::{BASE64_STANDARD, Engine};
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
 use reqwest::{Method, StatusCode};
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
@@ -227,6 +228,48 @@ value and Bearer authentication when you provide a standalone access token."#
             Err(e) => Err(e.into()),
         }
     }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_merged_pull_request_heads(
+        &self,
+        core: &Core,
+        service: &Service,
+        repo: &Repo,
+        branch: &str,
+    ) -> Result<Vec<String>, human_errors::Error> {
+        let uri = format!(
+            "{}/repositories/{}/{}/pullrequests?state=MERGED&q={}",
+            self.api_url(service),
+            repo.namespace,
+            repo.name,
+            utf8_percent_encode(
+                &format!("source.branch.name=\"{}\"", branch.replace('"', "\\\"")),
+                NON_ALPHANUMERIC
+            )
+        );
+
+        let resp: Result<PullRequestsResponse, BitBucketErrorResponse> = self
+            .make_request(
+                core,
+                service,
+                Method::GET,
+                &uri,
+                Vec::new(),
+                vec![StatusCode::OK],
+            )
+            .await?;
+
+        match resp {
+            // BitBucket reports abbreviated commit hashes, which git is happy to resolve.
+            Ok(pulls) => Ok(pulls
+                .values
+                .into_iter()
+                .map(|pr| pr.source.commit.hash)
+                .collect()),
+            Err(e) if e.http_status_code == StatusCode::NOT_FOUND => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 impl BitBucketService {
@@ -296,6 +339,27 @@ struct RepoResponse {
     pub full_name: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct PullRequestsResponse {
+    #[serde(default)]
+    pub values: Vec<PullRequestResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestResponse {
+    pub source: PullRequestSource,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestSource {
+    pub commit: PullRequestCommit,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestCommit {
+    pub hash: String,
+}
+
 #[derive(Debug, Default, Deserialize)]
 struct BitBucketErrorResponse {
     #[serde(skip)]
@@ -384,6 +448,7 @@ mod tests {
                 kind: "BitBucket/2.0".into(),
                 url: "https://api.bitbucket.org/2.0".into(),
             }),
+            identity: None,
         }
     }
 
@@ -491,6 +556,28 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_merged_pull_request_heads() {
+        let core = core(vec![MockHttpRoute::new(
+            "GET",
+            "https://api.bitbucket.org/2.0/repositories/myworkspace/user-repo/pullrequests?state=MERGED&q=source%2Ebranch%2Ename%3D%22feature%2Fmerged%22",
+            200,
+            r#"{ "values": [{ "id": 1, "source": { "commit": { "hash": "abc123def456" } } }] }"#,
+        )]);
+
+        let repo = Repo::new(
+            "bitbucket:myworkspace/user-repo",
+            std::path::PathBuf::from("/"),
+        );
+        assert_eq!(
+            BitBucketService::default()
+                .get_merged_pull_request_heads(&core, &service(), &repo, "feature/merged")
+                .await
+                .expect("No error should have been generated"),
+            vec!["abc123def456".to_string()]
+        );
+    }
+
     #[tokio::test]
     async fn test_move_same_workspace() {
         let core = core(vec![MockHttpRoute::new(
@@ -229,6 +229,44 @@ On gitea.com the token page is available at https://gitea.com/user/settings/appl
             Err(e) => Err(e.into()),
         }
     }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_merged_pull_request_heads(
+        &self,
+        core: &Core,
+        service: &Service,
+        repo: &Repo,
+        branch: &str,
+    ) -> Result<Vec<String>, human_errors::Error> {
+        // Gitea can't filter pull requests by their head branch, so we look through
+        // the most recently updated closed pull requests instead.
+        let uri = format!(
+            "{}/repos/{}/pulls?state=closed&sort=recentupdate&limit=50",
+            self.api_url(service),
+            repo.get_full_name()
+        );
+
+        let resp: Result<Vec<PullRequestResponse>, GiteaErrorResponse> = self
+            .make_request(
+                core,
+                service,
+                Method::GET,
+                &uri,
+                Vec::new(),
+                vec![StatusCode::OK],
+            )
+            .await?;
+
+        match resp {
+            Ok(pulls) => Ok(pulls
+                .into_iter()
+                .filter(|pr| pr.merged && pr.head.name == branch)
+                .map(|pr| pr.head.sha)
+                .collect()),
+            Err(e) if e.http_status_code == StatusCode::NOT_FOUND => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 impl GiteaService {
@@ -344,6 +382,20 @@ struct RepoResponse {
     pub id: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct PullRequestResponse {
+    #[serde(default)]
+    pub merged: bool,
+    pub head: PullRequestBranch,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestBranch {
+    #[serde(rename = "ref")]
+    pub name: String,
+    pub sha: String,
+}
+
 #[derive(Debug, Default, Deserialize)]
 #[allow(dead_code)]
 struct GiteaErrorResponse {
@@ -419,6 +471,7 @@ mod tests {
                 kind: "Gitea/v1".into(),
                 url: "https://gitea.com/api/v1".into(),
             }),
+            identity: None,
         }
     }
 
@@ -542,6 +595,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_merged_pull_request_heads() {
+        let core = core(vec![MockHttpRoute::new(
+            "GET",
+            "https://gitea.com/api/v1/repos/test/user-repo/pulls?state=closed&sort=recentupdate&limit=50",
+            200,
+            r#"[{ "merged": true, "head": { "ref": "feature/merged", "sha": "abc123" } }, { "merged": false, "head": { "ref": "feature/closed", "sha": "def456" } }]"#,
+        )]);
+
+        let repo = Repo::new("gitea:test/user-repo", std::path::PathBuf::from("/"));
+        let gitea = GiteaService::default();
+        assert_eq!(
+            gitea
+                .get_merged_pull_request_heads(&core, &service(), &repo, "feature/merged")
+                .await
+                .expect("No error should have been generated"),
+            vec!["abc123".to_string()]
+        );
+        assert!(
+            gitea
+                .get_merged_pull_request_heads(&core, &service(), &repo, "feature/closed")
+                .await
+                .expect("No error should have been generated")
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn test_move_same_namespace() {
         let core = core(vec![MockHttpRoute::new(
@@ -4,6 +4,7 @@ use crate::errors::HumanErrorResultExt;
 
 use super::*;
 use human_errors::ResultExt;
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
 use reqwest::{Method, Request, StatusCode, Url};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
@@ -244,6 +245,43 @@ Configure it with the following:
             .await?;
         Ok(())
     }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_merged_pull_request_heads(
+        &self,
+        core: &Core,
+        service: &Service,
+        repo: &Repo,
+        branch: &str,
+    ) -> Result<Vec<String>, human_errors::Error> {
+        let uri = format!(
+            "{}/repos/{}/pulls?state=closed&head={}",
+            service.api.as_ref().unwrap().url.as_str(),
+            repo.get_full_name(),
+            utf8_percent_encode(&format!("{}:{}", repo.namespace, branch), NON_ALPHANUMERIC)
+        );
+
+        let pulls: Result<Vec<PullRequest>, GitHubErrorResponse> = self
+            .make_request(
+                core,
+                service,
+                Method::GET,
+                &uri,
+                Vec::new(),
+                vec![StatusCode::OK],
+            )
+            .await?;
+
+        match pulls {
+            Ok(pulls) => Ok(pulls
+                .into_iter()
+                .filter(|pr| pr.merged_at.is_some())
+                .map(|pr| pr.head.sha)
+                .collect()),
+            Err(e) if e.http_status_code == StatusCode::NOT_FOUND => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 impl GitHubService {
@@ -393,6 +431,17 @@ struct NewRepoResponse {
     pub id: u64,
 }
 
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    pub merged_at: Option<String>,
+    pub head: PullRequestHead,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestHead {
+    pub sha: String,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct GitHubErrorResponse {
@@ -496,6 +545,7 @@ mod tests {
                         kind: "github".into(),
                         url: "https://api.github.com".into(),
                     }),
+                    identity: None,
                 },
                 &repo,
             )
@@ -528,6 +578,7 @@ mod tests {
                         kind: "github".into(),
                         url: "https://api.github.com".into(),
                     }),
+                    identity: None,
                 },
                 &repo,
             )
@@ -561,6 +612,7 @@ mod tests {
                         kind: "github".into(),
                         url: "https://api.github.com".into(),
                     }),
+                    identity: None,
                 },
                 &src_repo,
                 &dest_repo,
@@ -608,6 +660,50 @@ mod tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn test_get_merged_pull_request_heads() {
+        let core = Core::builder()
+            .with_default_config()
+            .with_mock_keychain(|mock| {
+                mock.expect_get_token()
+                    .with(eq("gh"))
+                    .returning(|_| Ok("test_token".into()));
+            })
+            .with_mock_http_client(vec![
+                mocks::pull_request_merged("test/user-repo", "feature/merged", true),
+                mocks::pull_request_merged("test/user-repo", "feature/closed", false),
+            ])
+            .build();
+
+        let repo = Repo::new("gh:test/user-repo", std::path::PathBuf::from("/"));
+        let service = Service {
+            name: "gh".into(),
+            website: "https://github.com/{{ .Repo.FullName }}".into(),
+            git_url: "git@github.com/{{ .Repo.FullName }}.git".into(),
+            pattern: "*/*".into(),
+            api: Some(ServiceAPI {
+                kind: "github".into(),
+                url: "https://api.github.com".into(),
+            }),
+            identity: None,
+        };
+
+        assert_eq!(
+            GitHubService::default()
+                .get_merged_pull_request_heads(&core, &service, &repo, "feature/merged")
+                .await
+                .expect("No error should have been generated"),
+            vec!["abc123".to_string()]
+        );
+        assert!(
+            GitHubService::default()
+                .get_merged_pull_request_heads(&core, &service, &repo, "feature/closed")
+                .await
+                .expect("No error should have been generated")
+                .is_empty()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -758,6 +854,27 @@ pub mod mocks {
         ]
     }
 
+    pub fn pull_request_merged(repo: &str, branch: &str, merged: bool) -> super::MockHttpRoute {
+        let namespace = repo.split('/').next().unwrap();
+        super::MockHttpRoute::new(
+            "GET",
+            format!(
+                "https://api.github.com/repos/{repo}/pulls?state=closed&head={}",
+                super::utf8_percent_encode(
+                    &format!("{namespace}:{branch}"),
+                    super::NON_ALPHANUMERIC
+                )
+            )
+            .as_str(),
+            200,
+            if merged {
+                r#"[{ "merged_at": "2024-01-01T00:00:00Z", "head": { "sha": "abc123" } }]"#
+            } else {
+                r#"[{ "merged_at": null, "head": { "sha": "def456" } }]"#
+            },
+        )
+    }
+
     pub fn repo_fork(repo: &str) -> Vec<super::MockHttpRoute> {
         vec![
             super::MockHttpRoute::new(
@@ -221,6 +221,39 @@ For a self-managed GitLab instance, replace the domain above with your instance'
             Err(e) => Err(e.into()),
         }
     }
+
+    #[tracing::instrument(err, skip(self, core))]
+    async fn get_merged_pull_request_heads(
+        &self,
+        core: &Core,
+        service: &Service,
+        repo: &Repo,
+        branch: &str,
+    ) -> Result<Vec<String>, human_errors::Error> {
+        let uri = format!(
+            "{}/projects/{}/merge_requests?state=merged&source_branch={}",
+            self.api_url(service),
+            encode_path(&repo.get_full_name()),
+            encode_path(branch)
+        );
+
+        let resp: Result<Vec<MergeRequestResponse>, GitLabErrorResponse> = self
+            .make_request(
+                core,
+                service,
+                Method::GET,
+                &uri,
+                Vec::new(),
+                vec![StatusCode::OK],
+            )
+            .await?;
+
+        match resp {
+            Ok(merge_requests) => Ok(merge_requests.into_iter().map(|mr| mr.sha).collect()),
+            Err(e) if e.http_status_code == StatusCode::NOT_FOUND => Ok(Vec::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 impl GitLabService {
@@ -367,6 +400,13 @@ struct ProjectResponse {
     pub id: u64,
 }
 
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct MergeRequestResponse {
+    pub iid: u64,
+    pub sha: String,
+}
+
 #[derive(Debug, Default, Deserialize)]
 struct GitLabErrorResponse {
     #[serde(skip)]
@@ -478,6 +518,7 @@ mod tests {
                 kind: "GitLab/v4".into(),
                 url: "https://gitlab.com/api/v4".into(),
             }),
+            identity: None,
         }
     }
 
@@ -607,6 +648,40 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_merged_pull_request_heads() {
+        let core = core(vec![
+            MockHttpRoute::new(
+                "GET",
+                "https://gitlab.com/api/v4/projects/test%2Fuser-repo/merge_requests?state=merged&source_branch=feature%2Fmerged",
+                200,
+                r#"[{ "iid": 1, "sha": "abc123" }]"#,
+            ),
+            MockHttpRoute::new(
+                "GET",
+                "https://gitlab.com/api/v4/projects/test%2Fuser-repo/merge_requests?state=merged&source_branch=feature%2Fopen",
+                200,
+                r#"[]"#,
+            ),
+        ]);
+
+        let repo = Repo::new("gl:test/user-repo", std::path::PathBuf::from("/"));
+        assert_eq!(
+            GitLabService::default()
+                .get_merged_pull_request_heads(&core, &service(), &repo, "feature/merged")
+                .await
+                .expect("No error should have been generated"),
+            vec!["abc123".to_string()]
+        );
+        assert!(
+            GitLabService::default()
+                .get_merged_pull_request_heads(&core, &service(), &repo, "feature/open")
+                .await
+                .expect("No error should have been generated")
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn test_move_same_namespace() {
         let core = core(vec![MockHttpRoute::new(
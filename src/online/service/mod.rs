@@ -46,6 +46,17 @@ pub trait OnlineService: Send + Sync {
         destination: &Repo,
         default_branch_only: bool,
     ) -> Result<(), human_errors::Error>;
+
+    /// Gets the head commits of the pull requests from `branch` which have been merged into
+    /// the repository, which lets us spot branches that were squash or rebase merged on the
+    /// service (and check that they haven't gained new commits since).
+    async fn get_merged_pull_request_heads(
+        &self,
+        core: &Core,
+        service: &Service,
+        repo: &Repo,
+        branch: &str,
+    ) -> Result<Vec<String>, human_errors::Error>;
 }
 
 #[allow(dead_code)]
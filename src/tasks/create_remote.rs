@@ -17,6 +17,27 @@ impl Task for CreateRemote {
         "create-remote"
     }
 
+    #[cfg(feature = "auth")]
+    fn describe_repo(&self, core: &Core, repo: &engine::Repo) -> Vec<String> {
+        if !self.enabled
+            || !core
+                .config()
+                .get_features()
+                .has(engine::features::CREATE_REMOTE)
+        {
+            return Vec::new();
+        }
+
+        match core.config().get_service(&repo.service) {
+            Ok(service) if crate::online::services().iter().any(|s| s.handles(service)) => {
+                vec![format!(
+                    "Create the remote repository {repo} if it does not already exist"
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     #[cfg(feature = "auth")]
     #[tracing::instrument(name = "task:create_remote(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
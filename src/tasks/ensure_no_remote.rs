@@ -17,6 +17,27 @@ impl Task for EnsureNoRemote {
         "ensure-no-remote"
     }
 
+    #[cfg(feature = "auth")]
+    fn describe_repo(&self, core: &Core, repo: &engine::Repo) -> Vec<String> {
+        if !self.enabled
+            || !core
+                .config()
+                .get_features()
+                .has(engine::features::CHECK_EXISTS)
+        {
+            return Vec::new();
+        }
+
+        match core.config().get_service(&repo.service) {
+            Ok(service) if crate::online::services().iter().any(|s| s.handles(service)) => {
+                vec![format!(
+                    "Check that the remote repository {repo} does not already exist"
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     #[cfg(feature = "auth")]
     #[tracing::instrument(name = "task:ensure_no_remote(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
@@ -54,6 +75,15 @@ impl Task for EnsureNoRemote {
 
         Ok(())
     }
+
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        _repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        // This task only inspects the remote service, so there is nothing to undo.
+        Ok(Rollback::NotRequired)
+    }
 }
 
 #[cfg(test)]
@@ -13,6 +13,33 @@ impl Task for ForkRemote {
         "fork-remote"
     }
 
+    #[cfg(feature = "auth")]
+    fn describe_repo(&self, core: &Core, repo: &Repo) -> Vec<String> {
+        if !core
+            .config()
+            .get_features()
+            .has(engine::features::MOVE_REMOTE)
+        {
+            return Vec::new();
+        }
+
+        match core.config().get_service(&repo.service) {
+            Ok(service) if crate::online::services().iter().any(|s| s.handles(service)) => {
+                vec![format!(
+                    "Fork the remote repository {} to {}{}",
+                    self.from_repo,
+                    repo,
+                    if self.default_branch_only {
+                        " (default branch only)"
+                    } else {
+                        ""
+                    }
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     #[cfg(feature = "auth")]
     #[tracing::instrument(name = "task:fork_repository(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &Repo) -> Result<(), engine::Error> {
@@ -13,6 +13,14 @@ impl Task for GitAdd<'_> {
         "git-add"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Stage {} in '{}'",
+            self.paths.join(", "),
+            repo.get_path().display()
+        )]
+    }
+
     #[tracing::instrument(name = "task:git_add(repo)", err, skip(self, _core))]
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         git::git_add(&repo.get_path(), &self.paths).await
@@ -36,7 +44,7 @@ mod tests {
             .build();
 
         sequence![
-            GitInit {},
+            GitInit::default(),
             WriteFile {
                 path: PathBuf::from("README.md"),
                 content: "This is a test"
@@ -12,6 +12,10 @@ impl Task for GitBranchDelete {
         "git-branch-delete"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        vec![format!("Delete the branch '{}' from {}", self.branch, repo)]
+    }
+
     #[tracing::instrument(name = "task:git_branch_delete(repo)", err, skip(self, _core))]
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         git::git_branch_delete(&repo.get_path(), &self.branch).await
@@ -36,7 +40,7 @@ mod tests {
             .with_config_for_dev_directory(temp.path())
             .build();
 
-        sequence![GitInit {}, GitCheckout { branch: "main" }]
+        sequence![GitInit::default(), GitCheckout { branch: "main" }]
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -0,0 +1,99 @@
+use super::*;
+use crate::{engine::Target, git};
+use tracing_batteries::prelude::*;
+
+pub struct GitBranchRename {
+    pub from: String,
+    pub to: String,
+}
+
+#[async_trait::async_trait]
+impl Task for GitBranchRename {
+    fn name(&self) -> &'static str {
+        "git-branch-rename"
+    }
+
+    fn describe_repo(&self, _core: &Core, _repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Rename the branch '{}' to '{}'",
+            self.from, self.to
+        )]
+    }
+
+    #[tracing::instrument(name = "task:git_branch_rename(repo)", err, skip(self, _core))]
+    async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        git::git_branch_rename(&repo.get_path(), &self.from, &self.to).await
+    }
+
+    #[tracing::instrument(name = "task:git_branch_rename:rollback(repo)", err, skip(self, _core))]
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        git::git_branch_rename(&repo.get_path(), &self.to, &self.from).await?;
+        Ok(Rollback::Reverted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_rename_and_rollback() {
+        let temp = tempdir().unwrap();
+        let repo = engine::Repo::new(
+            "gh:sierrasoftworks/test-git-branch-rename",
+            temp.path().join("repo"),
+        );
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_null_console()
+            .build();
+
+        sequence![
+            GitInit::default(),
+            GitCheckout { branch: "main" },
+            WriteFile {
+                path: "README.md".into(),
+                content: "main",
+            },
+            GitAdd {
+                paths: vec!["README.md"],
+            },
+            GitCommit {
+                message: "Add README.md",
+                paths: vec!["README.md"],
+            },
+            GitCheckout {
+                branch: "feature/old"
+            }
+        ]
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        let task = GitBranchRename {
+            from: "feature/old".into(),
+            to: "feature/new".into(),
+        };
+
+        task.apply_repo(&core, &repo).await.unwrap();
+        assert_eq!(
+            git::git_current_branch(&repo.get_path()).await.unwrap(),
+            "feature/new"
+        );
+
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::Reverted
+        );
+        assert_eq!(
+            git::git_current_branch(&repo.get_path()).await.unwrap(),
+            "feature/old"
+        );
+    }
+}
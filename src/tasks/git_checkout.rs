@@ -12,6 +12,13 @@ impl Task for GitCheckout<'_> {
         "git-checkout"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Check out the branch '{}' in {}, creating or resetting it as needed",
+            self.branch, repo
+        )]
+    }
+
     #[tracing::instrument(name = "task:git_checkout(repo)", err, skip(self, _core))]
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         git::git_checkout(&repo.get_path(), self.branch).await
@@ -36,7 +43,7 @@ mod tests {
             .with_config_for_dev_directory(temp.path())
             .build();
 
-        sequence![GitInit {}, GitCheckout { branch: "test" }]
+        sequence![GitInit::default(), GitCheckout { branch: "test" }]
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -1,10 +1,16 @@
 use super::*;
 use crate::{engine::Target, git};
+use human_errors::ResultExt;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing_batteries::prelude::*;
 
 #[derive(Default)]
 pub struct GitClone {
     pub url: Option<String>,
+
+    /// Whether this task cloned the repository (as opposed to finding it already
+    /// present), which determines whether it is safe to remove on rollback.
+    cloned: AtomicBool,
 }
 
 #[async_trait::async_trait]
@@ -13,6 +19,29 @@ impl Task for GitClone {
         "git-clone"
     }
 
+    fn describe_repo(&self, core: &Core, repo: &engine::Repo) -> Vec<String> {
+        if repo.exists() {
+            return Vec::new();
+        }
+
+        let url = self
+            .url
+            .clone()
+            .or_else(|| {
+                core.config()
+                    .get_service(&repo.service)
+                    .and_then(|service| service.get_git_url(repo))
+                    .ok()
+            })
+            .unwrap_or_else(|| "<unknown remote>".to_string());
+
+        vec![format!(
+            "Clone '{}' into '{}'",
+            url,
+            repo.get_path().display()
+        )]
+    }
+
     #[tracing::instrument(name = "task:git_clone(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         if repo.exists() {
@@ -25,6 +54,7 @@ impl Task for GitClone {
         let url = self.url.as_deref().unwrap_or(default_url.as_str());
 
         git::git_clone(&repo.get_path(), url).await?;
+        self.cloned.store(true, Ordering::SeqCst);
 
         #[cfg(test)]
         {
@@ -32,14 +62,38 @@ impl Task for GitClone {
             git::git_config_set(&repo.get_path(), "user.email", "user@example.com").await?;
         }
 
+        GitIdentity {}.apply_repo(core, repo).await?;
+
         Ok(())
     }
+
+    #[tracing::instrument(name = "task:git_clone:rollback(repo)", err, skip(self, _core))]
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        if !self.cloned.swap(false, Ordering::SeqCst) {
+            return Ok(Rollback::NotRequired);
+        }
+
+        std::fs::remove_dir_all(repo.get_path()).wrap_user_err(
+            format!(
+                "Could not remove the cloned repository at '{}' due to an OS-level error.",
+                repo.get_path().display()
+            ),
+            &["Remove the repository directory manually before trying again."],
+        )?;
+
+        Ok(Rollback::Reverted)
+    }
 }
 
 impl GitClone {
     pub fn with_url<S: ToString>(url: S) -> Self {
         Self {
             url: Some(url.to_string()),
+            ..Default::default()
         }
     }
 }
@@ -81,6 +135,50 @@ mod tests {
         assert!(repo.valid());
     }
 
+    #[tokio::test]
+    #[cfg_attr(feature = "pure-tests", ignore)]
+    async fn test_rollback() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new("gh:git-fixtures/basic", temp.path().join("repo"));
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+
+        let task = GitClone::default();
+        task.apply_repo(&core, &repo).await.unwrap();
+        assert!(repo.valid());
+
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::Reverted
+        );
+        assert!(!repo.exists(), "the cloned repository should be removed");
+    }
+
+    #[tokio::test]
+    async fn test_rollback_existing() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new("gh:git-fixtures/basic", temp.path().join("repo"));
+        std::fs::create_dir_all(repo.get_path()).unwrap();
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+
+        let task = GitClone::default();
+        task.apply_repo(&core, &repo).await.unwrap();
+
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::NotRequired
+        );
+        assert!(
+            repo.exists(),
+            "a repository which was already present should be kept"
+        );
+    }
+
     #[tokio::test]
     async fn test_scratch() {
         let temp = tempdir().unwrap();
@@ -14,6 +14,15 @@ impl Task for GitCommit<'_> {
         "git-commit"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Commit {} in '{}' with the message '{}'",
+            self.paths.join(", "),
+            repo.get_path().display(),
+            self.message
+        )]
+    }
+
     #[tracing::instrument(name = "task:git_commit(repo)", err, skip(self, _core))]
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         git::git_commit(&repo.get_path(), self.message, &self.paths).await
@@ -38,7 +47,7 @@ mod tests {
             .build();
 
         sequence![
-            GitInit {},
+            GitInit::default(),
             WriteFile {
                 path: PathBuf::from("README.md"),
                 content: "This is a test"
@@ -0,0 +1,133 @@
+use super::*;
+use crate::{engine::Target, git};
+use tracing_batteries::prelude::*;
+
+/// Writes the git identity configured for a repository's service (if any) into
+/// the repository's local git config.
+pub struct GitIdentity {}
+
+#[async_trait::async_trait]
+impl Task for GitIdentity {
+    fn name(&self) -> &'static str {
+        "git-identity"
+    }
+
+    fn describe_repo(&self, core: &Core, repo: &engine::Repo) -> Vec<String> {
+        match core
+            .config()
+            .get_service(&repo.service)
+            .ok()
+            .and_then(|service| service.identity.as_ref())
+        {
+            Some(identity) => vec![format!(
+                "Configure {} to use the git identity {}",
+                repo, identity
+            )],
+            None => Vec::new(),
+        }
+    }
+
+    #[tracing::instrument(name = "task:git_identity(repo)", err, skip(self, core))]
+    async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        // Repositories which don't belong to a configured service have no identity
+        // to apply, which is not an error for the tasks that compose this one.
+        let identity = core
+            .config()
+            .get_service(&repo.service)
+            .ok()
+            .and_then(|service| service.identity.as_ref());
+
+        if let Some(identity) = identity {
+            for (key, value) in identity.git_config() {
+                git::git_config_set(&repo.get_path(), key, value).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        _repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        // The identity lives within the repository's own git config, so it is
+        // removed along with the repository by the task which created it.
+        Ok(Rollback::NotRequired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::*;
+    use tempfile::tempdir;
+
+    fn config_with_identity(dev_dir: &std::path::Path) -> Config {
+        Config::from_str(&format!(
+            r#"
+directory: {}
+services:
+  - name: gh
+    website: "https://github.com/{{{{ .Repo.FullName }}}}"
+    gitUrl: "git@github.com:{{{{ .Repo.FullName }}}}.git"
+    pattern: "*/*"
+    identity:
+      name: Service User
+      email: service@example.com
+"#,
+            dev_dir.display()
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_repo() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new(
+            "gh:sierrasoftworks/test-git-identity",
+            temp.path().join("repo"),
+        );
+
+        let core = Core::builder()
+            .with_config(config_with_identity(temp.path()))
+            .build();
+
+        git::git_init(&repo.get_path()).await.unwrap();
+        GitIdentity {}.apply_repo(&core, &repo).await.unwrap();
+
+        assert_eq!(
+            git::git_config_get(&repo.get_path(), "user.name")
+                .await
+                .unwrap(),
+            Some("Service User".to_string())
+        );
+        assert_eq!(
+            git::git_config_get(&repo.get_path(), "user.email")
+                .await
+                .unwrap(),
+            Some("service@example.com".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repo_without_identity() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new(
+            "gh:sierrasoftworks/test-git-identity",
+            temp.path().join("repo"),
+        );
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+
+        assert!(
+            GitIdentity {}.describe_repo(&core, &repo).is_empty(),
+            "there should be nothing to do without an identity"
+        );
+
+        git::git_init(&repo.get_path()).await.unwrap();
+        GitIdentity {}.apply_repo(&core, &repo).await.unwrap();
+    }
+}
@@ -1,8 +1,19 @@
 use super::*;
 use crate::{engine::Target, git};
+use human_errors::ResultExt;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tracing_batteries::prelude::*;
 
-pub struct GitInit {}
+#[derive(Default)]
+pub struct GitInit {
+    /// Whether this task initialized the repository (as opposed to finding it already
+    /// present), which determines whether it is safe to remove on rollback.
+    initialized: AtomicBool,
+
+    /// Whether this task created the repository's directory, in which case it is
+    /// removed along with the repository on rollback.
+    created_directory: AtomicBool,
+}
 
 #[async_trait::async_trait]
 impl Task for GitInit {
@@ -10,9 +21,23 @@ impl Task for GitInit {
         "git-init"
     }
 
-    #[tracing::instrument(name = "task:git_init(repo)", err, skip(self, _core))]
-    async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+    fn describe_repo(&self, core: &Core, repo: &engine::Repo) -> Vec<String> {
+        let mut steps = vec![format!(
+            "Initialize a new git repository in '{}'",
+            repo.get_path().display()
+        )];
+        steps.extend(GitIdentity {}.describe_repo(core, repo));
+        steps
+    }
+
+    #[tracing::instrument(name = "task:git_init(repo)", err, skip(self, core))]
+    async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        let existed = repo.exists();
+        let valid = repo.valid();
+
         git::git_init(&repo.get_path()).await?;
+        self.initialized.store(!valid, Ordering::SeqCst);
+        self.created_directory.store(!existed, Ordering::SeqCst);
 
         #[cfg(test)]
         {
@@ -20,8 +45,42 @@ impl Task for GitInit {
             git::git_config_set(&repo.get_path(), "user.email", "user@example.com").await?;
         }
 
+        GitIdentity {}.apply_repo(core, repo).await?;
+
         Ok(())
     }
+
+    #[tracing::instrument(name = "task:git_init:rollback(repo)", err, skip(self, _core))]
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        if !self.initialized.swap(false, Ordering::SeqCst) {
+            return Ok(Rollback::NotRequired);
+        }
+
+        let git_dir = repo.get_path().join(".git");
+        std::fs::remove_dir_all(&git_dir).wrap_user_err(
+            format!(
+                "Could not remove the partially initialized repository at '{}' due to an OS-level error.",
+                git_dir.display()
+            ),
+            &["Remove the '.git' directory manually before trying again."],
+        )?;
+
+        if self.created_directory.swap(false, Ordering::SeqCst) {
+            std::fs::remove_dir(repo.get_path()).wrap_user_err(
+                format!(
+                    "Could not remove the repository directory '{}' due to an OS-level error.",
+                    repo.get_path().display()
+                ),
+                &["Remove the repository directory manually before trying again."],
+            )?;
+        }
+
+        Ok(Rollback::Reverted)
+    }
 }
 
 #[cfg(test)]
@@ -38,12 +97,81 @@ mod tests {
         let core = Core::builder()
             .with_config_for_dev_directory(temp.path())
             .build();
-        let task = GitInit {};
+        let task = GitInit::default();
 
         task.apply_repo(&core, &repo).await.unwrap();
         assert!(repo.valid());
     }
 
+    #[tokio::test]
+    async fn test_rollback() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new("gh:sierrasoftworks/test-git-init", temp.path().join("repo"));
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+        let task = GitInit::default();
+
+        task.apply_repo(&core, &repo).await.unwrap();
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::Reverted
+        );
+        assert!(!repo.valid());
+        assert!(
+            !repo.get_path().exists(),
+            "the empty repository directory should be removed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_existing_repo() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new("gh:sierrasoftworks/test-git-init", temp.path().join("repo"));
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+
+        GitInit::default().apply_repo(&core, &repo).await.unwrap();
+
+        let task = GitInit::default();
+        task.apply_repo(&core, &repo).await.unwrap();
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::NotRequired
+        );
+        assert!(
+            repo.valid(),
+            "a repository which was not created by the task should be kept"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_existing_directory() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new("gh:sierrasoftworks/test-git-init", temp.path().join("repo"));
+        std::fs::create_dir_all(repo.get_path()).unwrap();
+        std::fs::write(repo.get_path().join("README.md"), "This is a test file").unwrap();
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+        let task = GitInit::default();
+
+        task.apply_repo(&core, &repo).await.unwrap();
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::Reverted
+        );
+        assert!(!repo.valid());
+        assert!(
+            repo.get_path().join("README.md").exists(),
+            "a directory which was not created by the task should be kept"
+        );
+    }
+
     #[tokio::test]
     async fn test_scratch() {
         let temp = tempdir().unwrap();
@@ -52,7 +180,7 @@ mod tests {
         let core = Core::builder()
             .with_config_for_dev_directory(temp.path())
             .build();
-        let task = GitInit {};
+        let task = GitInit::default();
 
         task.apply_scratchpad(&core, &scratch).await.unwrap();
         assert!(!scratch.exists());
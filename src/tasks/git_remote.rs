@@ -18,6 +18,16 @@ impl Task for GitRemote<'_> {
         "git-remote"
     }
 
+    fn describe_repo(&self, core: &Core, repo: &engine::Repo) -> Vec<String> {
+        let url = core
+            .config()
+            .get_service(&repo.service)
+            .and_then(|service| service.get_git_url(repo))
+            .unwrap_or_else(|_| "<unknown remote>".to_string());
+
+        vec![format!("Point the '{}' remote at '{}'", self.name, url)]
+    }
+
     #[tracing::instrument(name = "task:git_remote(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         let service = core.config().get_service(&repo.service)?;
@@ -36,6 +46,33 @@ impl Task for GitRemote<'_> {
     }
 }
 
+/// Points a remote at the URL for `target`, running against `target` rather
+/// than the repository the task is applied to. This allows the remote to be
+/// updated in the same sequence as the step which moves a repository to `target`,
+/// so that both are rolled back together.
+pub struct GitUpdateRemote<'a> {
+    pub name: &'a str,
+    pub target: engine::Repo,
+}
+
+#[async_trait::async_trait]
+impl Task for GitUpdateRemote<'_> {
+    fn name(&self) -> &'static str {
+        "git-remote"
+    }
+
+    fn describe_repo(&self, core: &Core, _repo: &engine::Repo) -> Vec<String> {
+        GitRemote { name: self.name }.describe_repo(core, &self.target)
+    }
+
+    #[tracing::instrument(name = "task:git_update_remote(repo)", err, skip(self, core))]
+    async fn apply_repo(&self, core: &Core, _repo: &engine::Repo) -> Result<(), engine::Error> {
+        GitRemote { name: self.name }
+            .apply_repo(core, &self.target)
+            .await
+    }
+}
+
 pub struct GitAddRemote {
     pub name: String,
     pub url: String,
@@ -47,6 +84,13 @@ impl Task for GitAddRemote {
         "git-add-remote"
     }
 
+    fn describe_repo(&self, _core: &Core, _repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Point the '{}' remote at '{}'",
+            self.name, self.url
+        )]
+    }
+
     #[tracing::instrument(name = "task:git_remote(repo)", err, skip(self, _core))]
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         if git::git_remote_list(&repo.get_path())
@@ -79,7 +123,7 @@ mod tests {
             .with_config_for_dev_directory(temp.path())
             .build();
 
-        sequence![GitInit {}, GitRemote { name: "origin" }]
+        sequence![GitInit::default(), GitRemote { name: "origin" }]
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -102,7 +146,7 @@ mod tests {
             .build();
 
         sequence![
-            GitInit {},
+            GitInit::default(),
             GitRemote { name: "origin" },
             GitAddRemote {
                 name: "upstream".to_string(),
@@ -119,6 +163,45 @@ mod tests {
         assert!(remotes.iter().any(|r| r == "upstream"));
     }
 
+    #[tokio::test]
+    async fn test_update_remote() {
+        let temp = tempdir().unwrap();
+        let repo = Repo::new(
+            "gh:sierrasoftworks/test-git-remote",
+            temp.path().join("repo"),
+        );
+        let target = Repo::new("gh:sierrasoftworks/renamed", temp.path().join("repo"));
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .build();
+
+        sequence![
+            GitInit::default(),
+            GitUpdateRemote {
+                name: "origin",
+                target: target.clone()
+            }
+        ]
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        let url = core
+            .config()
+            .get_service("gh")
+            .unwrap()
+            .get_git_url(&target)
+            .unwrap();
+        let output = tokio::process::Command::new("git")
+            .args(["remote", "get-url", "origin"])
+            .current_dir(repo.get_path())
+            .output()
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), url);
+    }
+
     #[tokio::test]
     async fn test_scratch() {
         let temp = tempdir().unwrap();
@@ -1,10 +1,24 @@
 use super::*;
+use crate::errors::HumanErrorResultExt;
 use crate::{engine::Target, git};
+use std::io::Write;
 use tracing_batteries::prelude::*;
 
 pub struct GitSwitch {
     pub branch: String,
     pub create_if_missing: bool,
+    /// Stash any uncommitted changes on the current branch before switching, and
+    /// restore the target branch's own auto-stash (if it has one) afterwards.
+    pub autostash: bool,
+    /// Fetch the latest branches from the repository's remotes when the branch
+    /// isn't known locally, so that recently pushed branches can be tracked.
+    pub fetch: bool,
+}
+
+/// Builds the message used to identify the changes which were automatically
+/// stashed when switching away from `branch`.
+pub fn autostash_message(branch: &str) -> String {
+    format!("git-tool autostash: {branch}")
 }
 
 #[async_trait::async_trait]
@@ -13,20 +27,173 @@ impl Task for GitSwitch {
         "git-switch"
     }
 
-    #[tracing::instrument(name = "task:git_switch(repo)", err, skip(self, _core))]
-    async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
-        let mut create = self.create_if_missing;
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        let mut description = vec![if self.create_if_missing {
+            format!(
+                "Switch {} to the branch '{}', creating it if it does not exist",
+                repo, self.branch
+            )
+        } else {
+            format!("Switch {} to the branch '{}'", repo, self.branch)
+        }];
+
+        if self.fetch {
+            description.push(format!(
+                "Fetch the latest branches from {}'s remotes if '{}' is not known locally",
+                repo, self.branch
+            ));
+        }
+
+        if self.autostash {
+            description.push(format!(
+                "Stash any uncommitted changes and restore the auto-stash for '{}'",
+                self.branch
+            ));
+        }
+
+        description
+    }
+
+    #[tracing::instrument(name = "task:git_switch(repo)", err, skip(self, core))]
+    async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        if !self.autostash {
+            return self.switch(core, repo).await;
+        }
+
+        let current = git::git_current_branch(&repo.get_path())
+            .await
+            .unwrap_or_else(|_| "HEAD".into());
+        if current == self.branch {
+            return Ok(());
+        }
+
+        let stashed = !git::git_worktree_is_clean(&repo.get_path()).await?;
+        if stashed {
+            git::git_stash_push(&repo.get_path(), &autostash_message(&current)).await?;
+        }
+
+        if let Err(err) = self.switch(core, repo).await {
+            // Put the changes back where we found them so that a failed switch
+            // doesn't leave the user wondering where their work went.
+            if stashed {
+                self.restore_autostash(repo, &current).await?;
+            }
+
+            return Err(err);
+        }
+
+        self.restore_autostash(repo, &self.branch).await
+    }
+}
+
+impl GitSwitch {
+    /// Switches to the branch, setting up a tracking branch when it only exists on
+    /// one of the repository's remotes and creating it (if permitted) when it
+    /// doesn't exist anywhere.
+    async fn switch(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        let path = repo.get_path();
+
+        if git::git_rev_parse(&path, &format!("refs/heads/{}", self.branch))
+            .await
+            .is_ok()
+        {
+            return git::git_switch(&path, &self.branch, false).await;
+        }
+
+        let mut remotes = git::git_branch_remotes(&path, &self.branch).await?;
+        if remotes.is_empty() && self.fetch {
+            git::git_fetch_all(&path).await?;
+            remotes = git::git_branch_remotes(&path, &self.branch).await?;
+        }
+
+        match self.choose_remote(core, repo, &remotes)? {
+            Some(remote) => {
+                git::git_switch_track(&path, &self.branch, &format!("{remote}/{}", self.branch))
+                    .await
+            }
+            None => git::git_switch(&path, &self.branch, self.create_if_missing).await,
+        }
+    }
 
-        if create
-            && git::git_branches(&repo.get_path())
-                .await?
-                .iter()
-                .any(|v| v == &self.branch || v == &format!("origin/{}", &self.branch))
+    /// Picks the remote whose branch should be tracked, preferring `origin` and then
+    /// `upstream`, and asking the user when several other remotes have the branch.
+    fn choose_remote<'a>(
+        &self,
+        core: &Core,
+        repo: &engine::Repo,
+        remotes: &'a [String],
+    ) -> Result<Option<&'a str>, engine::Error> {
+        if let Some(remote) = ["origin", "upstream"]
+            .iter()
+            .find_map(|preferred| remotes.iter().find(|remote| remote == preferred))
         {
-            create = false;
+            return Ok(Some(remote.as_str()));
+        }
+
+        match remotes {
+            [] => Ok(None),
+            [remote] => Ok(Some(remote.as_str())),
+            _ => {
+                {
+                    let mut output = core.output();
+                    writeln!(
+                        output,
+                        "The branch '{}' exists on more than one of {}'s remotes:",
+                        self.branch, repo
+                    )
+                    .to_human_error()?;
+                    for (index, remote) in remotes.iter().enumerate() {
+                        writeln!(output, "  {}) {remote}", index + 1).to_human_error()?;
+                    }
+                }
+
+                let answer = core.prompter().prompt(
+                    &format!(
+                        "Which remote should '{}' track? [1-{}]: ",
+                        self.branch,
+                        remotes.len()
+                    ),
+                    |answer| {
+                        answer
+                            .parse::<usize>()
+                            .is_ok_and(|index| index >= 1 && index <= remotes.len())
+                    },
+                )?;
+
+                match answer.and_then(|answer| answer.parse::<usize>().ok()) {
+                    Some(index) => Ok(Some(remotes[index - 1].as_str())),
+                    None => Err(human_errors::user(
+                        format!(
+                            "The branch '{}' exists on more than one remote and no remote was chosen.",
+                            self.branch
+                        ),
+                        &["Choose the remote whose branch you would like to track when prompted."],
+                    )),
+                }
+            }
         }
+    }
+
+    /// Restores the most recent auto-stash which was created when switching away
+    /// from `branch`, if there is one.
+    async fn restore_autostash(
+        &self,
+        repo: &engine::Repo,
+        branch: &str,
+    ) -> Result<(), engine::Error> {
+        let message = autostash_message(branch);
+        let stash = git::git_stash_list(&repo.get_path())
+            .await?
+            .into_iter()
+            .find(|stash| stash.message.ends_with(&format!(": {message}")));
 
-        git::git_switch(&repo.get_path(), &self.branch, create).await
+        match stash {
+            Some(stash) => {
+                info!("Restoring the auto-stash for '{}'", branch);
+                git::git_stash_pop(&repo.get_path(), &stash.reference).await
+            }
+            None => Ok(()),
+        }
     }
 }
 
@@ -49,11 +216,13 @@ mod tests {
             .build();
 
         sequence![
-            GitInit {},
+            GitInit::default(),
             GitCheckout { branch: "main" },
             GitSwitch {
                 branch: "test".into(),
                 create_if_missing: true,
+                autostash: false,
+                fetch: false,
             }
         ]
         .apply_repo(&core, &repo)
@@ -81,11 +250,13 @@ mod tests {
             .build();
 
         sequence![
-            GitInit {},
+            GitInit::default(),
             GitCheckout { branch: "main" },
             GitSwitch {
                 branch: "test".into(),
                 create_if_missing: false,
+                autostash: false,
+                fetch: false,
             }
         ]
         .apply_repo(&core, &repo)
@@ -99,6 +270,222 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_repo_autostash() {
+        let temp = tempdir().unwrap();
+        let repo = engine::Repo::new(
+            "gh:sierrasoftworks/test-git-switch",
+            temp.path().join("repo"),
+        );
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_null_console()
+            .build();
+
+        sequence![
+            GitInit::default(),
+            GitCheckout { branch: "main" },
+            WriteFile {
+                path: "README.md".into(),
+                content: "main",
+            },
+            GitAdd {
+                paths: vec!["README.md"],
+            },
+            GitCommit {
+                message: "Add README.md",
+                paths: vec!["README.md"],
+            }
+        ]
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        let readme = repo.get_path().join("README.md");
+        std::fs::write(&readme, "changes on main").unwrap();
+
+        GitSwitch {
+            branch: "feature".into(),
+            create_if_missing: true,
+            autostash: true,
+            fetch: false,
+        }
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&readme).unwrap(),
+            "main",
+            "the changes on main should have been stashed"
+        );
+
+        std::fs::write(&readme, "changes on feature").unwrap();
+
+        GitSwitch {
+            branch: "main".into(),
+            create_if_missing: false,
+            autostash: true,
+            fetch: false,
+        }
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            git::git_current_branch(&repo.get_path()).await.unwrap(),
+            "main"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&readme).unwrap(),
+            "changes on main",
+            "the auto-stash for main should have been restored"
+        );
+
+        let stashes = git::git_stash_list(&repo.get_path()).await.unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].message.ends_with(&autostash_message("feature")));
+    }
+
+    #[tokio::test]
+    async fn test_repo_tracks_upstream_branch() {
+        let temp = tempdir().unwrap();
+        let upstream = engine::Repo::new(
+            "gh:sierrasoftworks/test-git-switch-upstream",
+            temp.path().join("upstream"),
+        );
+        let repo = engine::Repo::new(
+            "gh:sierrasoftworks/test-git-switch",
+            temp.path().join("repo"),
+        );
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_null_console()
+            .build();
+
+        let setup = sequence![
+            GitInit::default(),
+            GitCheckout { branch: "main" },
+            WriteFile {
+                path: "README.md".into(),
+                content: "main",
+            },
+            GitAdd {
+                paths: vec!["README.md"],
+            },
+            GitCommit {
+                message: "Add README.md",
+                paths: vec!["README.md"],
+            }
+        ];
+        setup.apply_repo(&core, &upstream).await.unwrap();
+        setup.apply_repo(&core, &repo).await.unwrap();
+
+        git::git_switch(&upstream.get_path(), "feature/upstream", true)
+            .await
+            .unwrap();
+        git::git_remote_add(
+            &repo.get_path(),
+            "upstream",
+            upstream.get_path().to_str().unwrap(),
+        )
+        .await
+        .unwrap();
+
+        GitSwitch {
+            branch: "feature/upstream".into(),
+            create_if_missing: false,
+            autostash: false,
+            fetch: true,
+        }
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            git::git_current_branch(&repo.get_path()).await.unwrap(),
+            "feature/upstream"
+        );
+
+        let upstreams = git::git_branch_upstreams(&repo.get_path()).await.unwrap();
+        assert!(
+            upstreams.iter().any(|b| b.branch == "feature/upstream"
+                && b.upstream.as_deref() == Some("upstream/feature/upstream")),
+            "the new branch should track the upstream remote's branch"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_repo_asks_which_remote_to_track() {
+        let temp = tempdir().unwrap();
+        let fork = engine::Repo::new(
+            "gh:sierrasoftworks/test-git-switch-fork",
+            temp.path().join("fork"),
+        );
+        let repo = engine::Repo::new(
+            "gh:sierrasoftworks/test-git-switch",
+            temp.path().join("repo"),
+        );
+
+        let console = crate::console::mock_with_input("2\n");
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_console(console.clone())
+            .build();
+
+        let setup = sequence![
+            GitInit::default(),
+            GitCheckout { branch: "main" },
+            WriteFile {
+                path: "README.md".into(),
+                content: "main",
+            },
+            GitAdd {
+                paths: vec!["README.md"],
+            },
+            GitCommit {
+                message: "Add README.md",
+                paths: vec!["README.md"],
+            }
+        ];
+        setup.apply_repo(&core, &fork).await.unwrap();
+        setup.apply_repo(&core, &repo).await.unwrap();
+
+        git::git_switch(&fork.get_path(), "feature/shared", true)
+            .await
+            .unwrap();
+        for remote in ["alice", "bob"] {
+            git::git_remote_add(&repo.get_path(), remote, fork.get_path().to_str().unwrap())
+                .await
+                .unwrap();
+        }
+
+        GitSwitch {
+            branch: "feature/shared".into(),
+            create_if_missing: false,
+            autostash: false,
+            fetch: true,
+        }
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        let output = console.to_string();
+        assert!(
+            output.contains("1) alice") && output.contains("2) bob"),
+            "the user should be offered each remote, got: {output}"
+        );
+
+        let upstreams = git::git_branch_upstreams(&repo.get_path()).await.unwrap();
+        assert!(
+            upstreams.iter().any(|b| b.branch == "feature/shared"
+                && b.upstream.as_deref() == Some("bob/feature/shared")),
+            "the new branch should track the remote the user chose"
+        );
+    }
+
     #[tokio::test]
     async fn test_scratch() {
         let temp = tempdir().unwrap();
@@ -112,6 +499,8 @@ mod tests {
         let task = GitSwitch {
             branch: "test".into(),
             create_if_missing: true,
+            autostash: false,
+            fetch: false,
         };
 
         task.apply_scratchpad(&core, &scratch).await.unwrap();
@@ -17,6 +17,26 @@ impl Task for GitWorktree {
         "git-worktree"
     }
 
+    fn describe_repo(&self, _core: &Core, _repo: &engine::Repo) -> Vec<String> {
+        if self.path.exists() {
+            return Vec::new();
+        }
+
+        match (&self.base, self.create_if_missing) {
+            (Some(base), true) => vec![format!(
+                "Add a worktree for the branch '{}' (based on '{}' if it does not exist) at '{}'",
+                self.branch,
+                base,
+                self.path.display()
+            )],
+            _ => vec![format!(
+                "Add a worktree for the branch '{}' at '{}'",
+                self.branch,
+                self.path.display()
+            )],
+        }
+    }
+
     #[tracing::instrument(name = "task:git_worktree(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         if self.path.exists() {
@@ -95,7 +115,7 @@ mod tests {
             .with_null_console()
             .build();
 
-        sequence![GitInit {}, GitCheckout { branch: "main" }]
+        sequence![GitInit::default(), GitCheckout { branch: "main" }]
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -132,7 +152,7 @@ mod tests {
             .with_null_console()
             .build();
 
-        sequence![GitInit {}, GitCheckout { branch: "main" }]
+        sequence![GitInit::default(), GitCheckout { branch: "main" }]
             .apply_repo(&core, &repo)
             .await
             .unwrap();
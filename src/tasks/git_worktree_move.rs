@@ -0,0 +1,140 @@
+use super::*;
+use crate::{
+    engine::{PortRegistry, Target},
+    git,
+};
+use human_errors::ResultExt;
+use std::path::PathBuf;
+use tracing_batteries::prelude::*;
+
+pub struct GitWorktreeMove {
+    pub path: PathBuf,
+    pub destination: PathBuf,
+}
+
+#[async_trait::async_trait]
+impl Task for GitWorktreeMove {
+    fn name(&self) -> &'static str {
+        "git-worktree-move"
+    }
+
+    fn describe_repo(&self, _core: &Core, _repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Move the worktree at '{}' to '{}'",
+            self.path.display(),
+            self.destination.display()
+        )]
+    }
+
+    #[tracing::instrument(name = "task:git_worktree_move(repo)", err, skip(self, core))]
+    async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        if let Some(parent) = self.destination.parent() {
+            std::fs::create_dir_all(parent).wrap_user_err(
+                format!(
+                    "Could not create the worktree directory '{}' due to an OS-level error.",
+                    parent.display()
+                ),
+                &["Make sure that Git-Tool has permission to write to your worktree directory and then try again."],
+            )?;
+        }
+
+        git::git_worktree_move(&repo.get_path(), &self.path, &self.destination).await?;
+
+        // The ports allocated to the worktree move along with it. The worktree itself
+        // has already been moved by this point, so failing to update the port registry
+        // shouldn't fail (and roll back) the move.
+        if let Err(err) =
+            PortRegistry::for_config(core.config()).rename(&self.path, &self.destination)
+        {
+            warn!(
+                "Could not move the ports allocated to the worktree at '{}': {}",
+                self.path.display(),
+                err
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "task:git_worktree_move:rollback(repo)", err, skip(self, core))]
+    async fn rollback_repo(
+        &self,
+        core: &Core,
+        repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        git::git_worktree_move(&repo.get_path(), &self.destination, &self.path).await?;
+        if let Err(err) =
+            PortRegistry::for_config(core.config()).rename(&self.destination, &self.path)
+        {
+            warn!(
+                "Could not move the ports allocated to the worktree at '{}' back: {}",
+                self.destination.display(),
+                err
+            );
+        }
+
+        Ok(Rollback::Reverted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_move_with_unreadable_ports() {
+        let temp = tempdir().unwrap();
+        let repo = engine::Repo::new(
+            "gh:sierrasoftworks/test-git-worktree-move",
+            temp.path().join("repo"),
+        );
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_null_console()
+            .build();
+
+        sequence![
+            GitInit::default(),
+            GitCheckout { branch: "main" },
+            WriteFile {
+                path: "README.md".into(),
+                content: "main",
+            },
+            GitAdd {
+                paths: vec!["README.md"],
+            },
+            GitCommit {
+                message: "Add README.md",
+                paths: vec!["README.md"],
+            }
+        ]
+        .apply_repo(&core, &repo)
+        .await
+        .unwrap();
+
+        let worktree_directory = core.config().get_worktree_directory();
+        let path = worktree_directory.join("old");
+        git::git_worktree_add(&repo.get_path(), &path, "feature/test", true, None)
+            .await
+            .unwrap();
+        std::fs::write(worktree_directory.join(".ports.json"), "not json").unwrap();
+
+        let task = GitWorktreeMove {
+            path: path.clone(),
+            destination: worktree_directory.join("new"),
+        };
+
+        task.apply_repo(&core, &repo).await.unwrap();
+        assert!(!path.exists());
+        assert!(worktree_directory.join("new").exists());
+
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::Reverted
+        );
+        assert!(path.exists());
+        assert!(!worktree_directory.join("new").exists());
+    }
+}
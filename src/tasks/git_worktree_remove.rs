@@ -1,5 +1,8 @@
 use super::*;
-use crate::{engine::Target, git};
+use crate::{
+    engine::{PortRegistry, Target},
+    git,
+};
 use std::path::PathBuf;
 use tracing_batteries::prelude::*;
 
@@ -13,9 +16,14 @@ impl Task for GitWorktreeRemove {
         "git-worktree-remove"
     }
 
-    #[tracing::instrument(name = "task:git_worktree_remove(repo)", err, skip(self, _core))]
-    async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
-        git::git_worktree_remove(&repo.get_path(), &self.path).await
+    fn describe_repo(&self, _core: &Core, _repo: &engine::Repo) -> Vec<String> {
+        vec![format!("Remove the worktree at '{}'", self.path.display())]
+    }
+
+    #[tracing::instrument(name = "task:git_worktree_remove(repo)", err, skip(self, core))]
+    async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        git::git_worktree_remove(&repo.get_path(), &self.path).await?;
+        PortRegistry::for_config(core.config()).release(&self.path)
     }
 }
 
@@ -46,7 +54,7 @@ mod tests {
             .with_null_console()
             .build();
 
-        sequence![GitInit {}, GitCheckout { branch: "main" }]
+        sequence![GitInit::default(), GitCheckout { branch: "main" }]
             .apply_repo(&core, &repo)
             .await
             .unwrap();
@@ -23,13 +23,16 @@ mod ensure_no_remote;
 mod fork_remote;
 mod git_add;
 mod git_branch_delete;
+mod git_branch_rename;
 mod git_checkout;
 mod git_clone;
 mod git_commit;
+mod git_identity;
 mod git_init;
 mod git_remote;
 mod git_switch;
 mod git_worktree;
+mod git_worktree_move;
 mod git_worktree_remove;
 mod move_directory;
 mod move_remote;
@@ -42,14 +45,17 @@ pub use fork_remote::ForkRemote;
 #[allow(unused_imports)]
 pub use git_add::GitAdd;
 pub use git_branch_delete::GitBranchDelete;
+pub use git_branch_rename::GitBranchRename;
 pub use git_checkout::GitCheckout;
 pub use git_clone::GitClone;
 #[allow(unused_imports)]
 pub use git_commit::GitCommit;
+pub use git_identity::GitIdentity;
 pub use git_init::GitInit;
-pub use git_remote::{GitAddRemote, GitRemote};
+pub use git_remote::{GitAddRemote, GitRemote, GitUpdateRemote};
 pub use git_switch::GitSwitch;
 pub use git_worktree::GitWorktree;
+pub use git_worktree_move::GitWorktreeMove;
 pub use git_worktree_remove::GitWorktreeRemove;
 pub use move_directory::MoveDirectory;
 pub use move_remote::MoveRemote;
@@ -58,6 +64,17 @@ pub use sequence::Sequence;
 #[allow(unused_imports)]
 pub use write_file::WriteFile;
 
+/// The outcome of rolling back a task which had previously been applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rollback {
+    /// The changes made by the task were undone.
+    Reverted,
+    /// The task did not make any changes which need to be undone.
+    NotRequired,
+    /// The task has no compensating action, so its changes remain in place.
+    Unsupported,
+}
+
 #[async_trait]
 pub trait Task {
     /// A short, hard-coded name identifying this kind of task in telemetry events.
@@ -67,6 +84,19 @@ pub trait Task {
     /// the task is applied to — never derive it from the task's arguments.
     fn name(&self) -> &'static str;
 
+    /// Describes the changes this task would make to a repository, one per line,
+    /// without touching the disk or the network. This is used to render the plan
+    /// for `--dry-run` executions; tasks which would do nothing return no lines.
+    fn describe_repo(&self, _core: &Core, _repo: &engine::Repo) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Describes the changes this task would make to a scratchpad (see
+    /// [`Task::describe_repo`]).
+    fn describe_scratchpad(&self, _core: &Core, _scratch: &engine::Scratchpad) -> Vec<String> {
+        Vec::new()
+    }
+
     async fn apply_repo(&self, _core: &Core, _repo: &engine::Repo) -> Result<(), engine::Error> {
         Ok(())
     }
@@ -77,12 +107,34 @@ pub trait Task {
     ) -> Result<(), engine::Error> {
         Ok(())
     }
+
+    /// Undoes the changes made by a successful [`Task::apply_repo`] call. This is
+    /// invoked by [`Sequence`] (in reverse order) when a later task fails, so that
+    /// multi-step operations don't leave a repository half-way through a change.
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        _repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        Ok(Rollback::Unsupported)
+    }
+
+    /// Undoes the changes made by a successful [`Task::apply_scratchpad`] call
+    /// (see [`Task::rollback_repo`]).
+    async fn rollback_scratchpad(
+        &self,
+        _core: &Core,
+        _scratch: &engine::Scratchpad,
+    ) -> Result<Rollback, engine::Error> {
+        Ok(Rollback::Unsupported)
+    }
 }
 
 #[cfg(test)]
 pub struct TestTask {
     ran_repo: Mutex<Option<engine::Repo>>,
     ran_scratchpad: Mutex<Option<engine::Scratchpad>>,
+    rolled_back: Mutex<bool>,
     error: bool,
 }
 
@@ -92,6 +144,7 @@ impl Default for TestTask {
         Self {
             ran_repo: Mutex::new(None),
             ran_scratchpad: Mutex::new(None),
+            rolled_back: Mutex::new(false),
             error: false,
         }
     }
@@ -104,6 +157,14 @@ impl Task for TestTask {
         "test"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        vec![format!("Run the test task against {repo}")]
+    }
+
+    fn describe_scratchpad(&self, _core: &Core, scratch: &engine::Scratchpad) -> Vec<String> {
+        vec![format!("Run the test task against {scratch}")]
+    }
+
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         let mut r = self.ran_repo.lock().await;
 
@@ -135,4 +196,22 @@ impl Task for TestTask {
             false => Ok(()),
         }
     }
+
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        _repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        *self.rolled_back.lock().await = true;
+        Ok(Rollback::Reverted)
+    }
+
+    async fn rollback_scratchpad(
+        &self,
+        _core: &Core,
+        _scratch: &engine::Scratchpad,
+    ) -> Result<Rollback, engine::Error> {
+        *self.rolled_back.lock().await = true;
+        Ok(Rollback::Reverted)
+    }
 }
@@ -1,5 +1,5 @@
 use crate::engine::{Core, Target};
-use crate::tasks::Task;
+use crate::tasks::{Rollback, Task};
 use human_errors::ResultExt;
 use std::{fs, path};
 use tracing_batteries::prelude::tracing;
@@ -14,6 +14,26 @@ impl Task for MoveDirectory {
         "move-directory"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &crate::engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Move '{}' to '{}'",
+            repo.get_path().display(),
+            self.new_path.display()
+        )]
+    }
+
+    fn describe_scratchpad(
+        &self,
+        _core: &Core,
+        scratch: &crate::engine::Scratchpad,
+    ) -> Vec<String> {
+        vec![format!(
+            "Move '{}' to '{}'",
+            scratch.get_path().display(),
+            self.new_path.display()
+        )]
+    }
+
     #[tracing::instrument(name = "task:move_directory(repo)", err, skip(self, _core))]
     async fn apply_repo(
         &self,
@@ -59,6 +79,27 @@ impl Task for MoveDirectory {
         Ok(())
     }
 
+    #[tracing::instrument(name = "task:move_directory:rollback(repo)", err, skip(self, _core))]
+    async fn rollback_repo(
+        &self,
+        _core: &Core,
+        repo: &crate::engine::Repo,
+    ) -> Result<Rollback, crate::engine::Error> {
+        fs::rename(self.new_path.clone(), repo.path.clone()).map_err(|err| {
+            human_errors::wrap_user(
+                err,
+                format!(
+                    "Could not move the repository directory '{}' back to '{}' due to an OS-level error.",
+                    self.new_path.display(),
+                    repo.path.display()
+                ),
+                &["Move the directory back to its original location manually."],
+            )
+        })?;
+
+        Ok(Rollback::Reverted)
+    }
+
     #[tracing::instrument(name = "task:git_rename(scratchpad)", err, skip(self, _core))]
     async fn apply_scratchpad(
         &self,
@@ -89,6 +130,31 @@ impl Task for MoveDirectory {
 
         Ok(())
     }
+
+    #[tracing::instrument(
+        name = "task:move_directory:rollback(scratchpad)",
+        err,
+        skip(self, _core)
+    )]
+    async fn rollback_scratchpad(
+        &self,
+        _core: &Core,
+        scratch: &crate::engine::Scratchpad,
+    ) -> Result<Rollback, crate::engine::Error> {
+        fs::rename(self.new_path.clone(), scratch.get_path()).map_err(|err| {
+            human_errors::wrap_user(
+                err,
+                format!(
+                    "Could not move the scratchpad directory '{}' back to '{}' due to an OS-level error.",
+                    self.new_path.display(),
+                    scratch.get_path().display()
+                ),
+                &["Move the directory back to its original location manually."],
+            )
+        })?;
+
+        Ok(Rollback::Reverted)
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +193,39 @@ mod tests {
         assert!(moved.exists());
     }
 
+    #[tokio::test]
+    async fn rollback_repository() {
+        let temp = tempdir().unwrap();
+
+        let original = temp.path().join("original");
+        let moved = temp.path().join("moved");
+        let repo = crate::engine::Repo::new("gh:sierrasoftworks/git-tool", original.clone());
+
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_null_console()
+            .build();
+
+        fs::create_dir(&original).unwrap();
+
+        let task = MoveDirectory {
+            new_path: moved.clone(),
+        };
+
+        task.apply_repo(&core, &repo).await.unwrap();
+        assert!(moved.exists());
+
+        assert_eq!(
+            task.rollback_repo(&core, &repo).await.unwrap(),
+            Rollback::Reverted
+        );
+        assert!(
+            original.exists(),
+            "the directory should have been moved back"
+        );
+        assert!(!moved.exists());
+    }
+
     #[tokio::test]
     async fn move_directory_no_repository_to_rename() {
         let temp = tempdir().unwrap();
@@ -13,6 +13,29 @@ impl Task for MoveRemote {
         "move-remote"
     }
 
+    #[cfg(feature = "auth")]
+    fn describe_repo(&self, core: &Core, repo: &Repo) -> Vec<String> {
+        if !self.enabled
+            || !core
+                .config()
+                .get_features()
+                .has(engine::features::MOVE_REMOTE)
+            || repo.service != self.target.service
+        {
+            return Vec::new();
+        }
+
+        match core.config().get_service(&repo.service) {
+            Ok(service) if crate::online::services().iter().any(|s| s.handles(service)) => {
+                vec![format!(
+                    "Rename the remote repository {} to {}",
+                    repo, self.target
+                )]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     #[cfg(feature = "auth")]
     #[tracing::instrument(name = "task:move_remote(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &Repo) -> Result<(), engine::Error> {
@@ -49,6 +72,34 @@ impl Task for MoveRemote {
 
         Ok(())
     }
+
+    #[cfg(feature = "auth")]
+    #[tracing::instrument(name = "task:move_remote:rollback(repo)", err, skip(self, core))]
+    async fn rollback_repo(&self, core: &Core, repo: &Repo) -> Result<Rollback, engine::Error> {
+        if !self.enabled
+            || !core
+                .config()
+                .get_features()
+                .has(engine::features::MOVE_REMOTE)
+            || repo.service != self.target.service
+        {
+            return Ok(Rollback::NotRequired);
+        }
+
+        let service = core.config().get_service(&repo.service)?;
+
+        if let Some(online_service) = crate::online::services()
+            .iter()
+            .find(|s| s.handles(service))
+        {
+            online_service
+                .move_repo(core, service, &self.target, repo)
+                .await?;
+            return Ok(Rollback::Reverted);
+        }
+
+        Ok(Rollback::NotRequired)
+    }
 }
 
 #[cfg(test)]
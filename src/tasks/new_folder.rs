@@ -10,6 +10,20 @@ impl Task for NewFolder {
         "new-folder"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Create the directory '{}'",
+            repo.get_path().display()
+        )]
+    }
+
+    fn describe_scratchpad(&self, _core: &Core, scratch: &engine::Scratchpad) -> Vec<String> {
+        vec![format!(
+            "Create the directory '{}'",
+            scratch.get_path().display()
+        )]
+    }
+
     #[tracing::instrument(name = "task:new_folder(repo)", err, skip(self, _core))]
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         let path = repo.get_path();
@@ -1,4 +1,6 @@
 use super::*;
+use crate::errors::HumanErrorResultExt;
+use std::io::Write;
 use std::sync::Arc;
 use tracing_batteries::prelude::*;
 
@@ -10,6 +12,17 @@ impl Sequence {
     pub fn new(tasks: Vec<Arc<dyn Task + Send + Sync>>) -> Self {
         Self { tasks }
     }
+
+    /// Prints the plan for a `--dry-run` execution of this sequence in place of
+    /// applying it.
+    fn print_plan(&self, core: &Core, plan: Vec<String>) -> Result<(), engine::Error> {
+        let mut output = core.output();
+        for step in plan {
+            writeln!(output, "[dry-run] {step}").to_human_error()?;
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -18,12 +31,46 @@ impl Task for Sequence {
         "sequence"
     }
 
+    fn describe_repo(&self, core: &Core, repo: &engine::Repo) -> Vec<String> {
+        self.tasks
+            .iter()
+            .flat_map(|task| task.describe_repo(core, repo))
+            .collect()
+    }
+
+    fn describe_scratchpad(&self, core: &Core, scratch: &engine::Scratchpad) -> Vec<String> {
+        self.tasks
+            .iter()
+            .flat_map(|task| task.describe_scratchpad(core, scratch))
+            .collect()
+    }
+
     #[tracing::instrument(name = "task:sequence(repo)", err, skip(self, core))]
     async fn apply_repo(&self, core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
+        if core.dry_run() {
+            return self.print_plan(core, self.describe_repo(core, repo));
+        }
+
+        let mut applied: Vec<(&(dyn Task + Send + Sync), Vec<String>)> = Vec::new();
         for task in self.tasks.iter() {
+            // The description is captured before the task is applied, since many
+            // tasks describe nothing once their changes are already in place.
+            let description = task.describe_repo(core, repo);
             let result = task.apply_repo(core, repo).await;
             record_task_event(core, task.as_ref(), "repo", &result);
-            result?;
+
+            if let Err(err) = result {
+                let mut outcomes = Vec::new();
+                for (task, description) in applied.into_iter().rev() {
+                    let outcome = task.rollback_repo(core, repo).await;
+                    outcomes.push(RollbackOutcome::new(task, description, outcome));
+                }
+
+                report_rollback(core, outcomes)?;
+                return Err(err);
+            }
+
+            applied.push((task.as_ref(), description));
         }
 
         Ok(())
@@ -35,14 +82,141 @@ impl Task for Sequence {
         core: &Core,
         scratch: &engine::Scratchpad,
     ) -> Result<(), engine::Error> {
+        if core.dry_run() {
+            return self.print_plan(core, self.describe_scratchpad(core, scratch));
+        }
+
+        let mut applied: Vec<(&(dyn Task + Send + Sync), Vec<String>)> = Vec::new();
         for task in self.tasks.iter() {
+            let description = task.describe_scratchpad(core, scratch);
             let result = task.apply_scratchpad(core, scratch).await;
             record_task_event(core, task.as_ref(), "scratchpad", &result);
-            result?;
+
+            if let Err(err) = result {
+                let mut outcomes = Vec::new();
+                for (task, description) in applied.into_iter().rev() {
+                    let outcome = task.rollback_scratchpad(core, scratch).await;
+                    outcomes.push(RollbackOutcome::new(task, description, outcome));
+                }
+
+                report_rollback(core, outcomes)?;
+                return Err(err);
+            }
+
+            applied.push((task.as_ref(), description));
         }
 
         Ok(())
     }
+
+    /// Undoes every task in the sequence in reverse order, allowing a nested
+    /// sequence to be rolled back as a single step of its parent.
+    #[tracing::instrument(name = "task:sequence:rollback(repo)", err, skip(self, core))]
+    async fn rollback_repo(
+        &self,
+        core: &Core,
+        repo: &engine::Repo,
+    ) -> Result<Rollback, engine::Error> {
+        let mut outcomes = Vec::new();
+        for task in self.tasks.iter().rev() {
+            outcomes.push(task.rollback_repo(core, repo).await);
+        }
+
+        combine_rollbacks(outcomes)
+    }
+
+    #[tracing::instrument(name = "task:sequence:rollback(scratchpad)", err, skip(self, core))]
+    async fn rollback_scratchpad(
+        &self,
+        core: &Core,
+        scratch: &engine::Scratchpad,
+    ) -> Result<Rollback, engine::Error> {
+        let mut outcomes = Vec::new();
+        for task in self.tasks.iter().rev() {
+            outcomes.push(task.rollback_scratchpad(core, scratch).await);
+        }
+
+        combine_rollbacks(outcomes)
+    }
+}
+
+/// Summarizes the rollback of each task in a sequence as a single outcome. The
+/// first error wins, and any task which couldn't be reverted means the sequence
+/// as a whole couldn't be either.
+fn combine_rollbacks(
+    outcomes: Vec<Result<Rollback, engine::Error>>,
+) -> Result<Rollback, engine::Error> {
+    let mut combined = Rollback::NotRequired;
+    for outcome in outcomes {
+        match outcome? {
+            Rollback::Unsupported => combined = Rollback::Unsupported,
+            Rollback::Reverted if matches!(combined, Rollback::NotRequired) => {
+                combined = Rollback::Reverted
+            }
+            _ => {}
+        }
+    }
+
+    Ok(combined)
+}
+
+/// The result of rolling back a single task, along with the description of the
+/// changes it had made so that they can be reported to the user.
+struct RollbackOutcome {
+    steps: Vec<String>,
+    outcome: Result<Rollback, engine::Error>,
+}
+
+impl RollbackOutcome {
+    fn new(
+        task: &(dyn Task + Send + Sync),
+        description: Vec<String>,
+        outcome: Result<Rollback, engine::Error>,
+    ) -> Self {
+        // Tasks which didn't expect to change anything have no description, so we
+        // fall back to their name if they turn out to have done something anyway.
+        let steps = if description.is_empty() && !matches!(outcome, Ok(Rollback::Unsupported)) {
+            vec![format!("The '{}' step", task.name())]
+        } else {
+            description
+        };
+
+        Self { steps, outcome }
+    }
+}
+
+/// Reports which of the steps applied before a failure were (and weren't)
+/// reverted, so that the user knows what state they have been left in.
+fn report_rollback(core: &Core, outcomes: Vec<RollbackOutcome>) -> Result<(), engine::Error> {
+    let outcomes: Vec<RollbackOutcome> = outcomes
+        .into_iter()
+        .filter(|o| !o.steps.is_empty() && !matches!(o.outcome, Ok(Rollback::NotRequired)))
+        .collect();
+
+    if outcomes.is_empty() {
+        return Ok(());
+    }
+
+    let mut output = core.output();
+    writeln!(
+        output,
+        "Git-Tool encountered an error and attempted to undo the changes it had already made:"
+    )
+    .to_human_error()?;
+
+    for outcome in outcomes {
+        let status = match &outcome.outcome {
+            Ok(Rollback::Reverted) | Ok(Rollback::NotRequired) => "reverted".to_string(),
+            Ok(Rollback::Unsupported) => "not reverted".to_string(),
+            Err(err) => format!("not reverted: {}", err.message()),
+        };
+
+        for step in outcome.steps {
+            writeln!(output, " - {step} ({status})").to_human_error()?;
+        }
+    }
+
+    Ok(())
 }
 
 /// Records a telemetry event for a task which has just been applied. Only the
@@ -133,6 +307,213 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_dry_run_repo() {
+        let task = Arc::new(TestTask::default());
+        let seq = Sequence::new(vec![task.clone(), Arc::new(GitInit::default())]);
+
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::new("gh:sierrasoftworks/git-tool", temp.path().join("repo"));
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_console(console.clone())
+            .with_dry_run(true)
+            .build();
+
+        seq.apply_repo(&core, &repo).await.unwrap();
+
+        assert!(
+            task.ran_repo.lock().await.is_none(),
+            "the task should not have been applied"
+        );
+        assert!(
+            !repo.get_path().exists(),
+            "the repository should not have been initialized"
+        );
+
+        let output = console.to_string();
+        assert!(
+            output.contains("[dry-run] Run the test task against gh:sierrasoftworks/git-tool"),
+            "the plan should describe the test task: {output}"
+        );
+        assert!(
+            output.contains(&format!(
+                "[dry-run] Initialize a new git repository in '{}'",
+                repo.get_path().display()
+            )),
+            "the plan should describe the git init task: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_scratchpad() {
+        let task = Arc::new(TestTask::default());
+        let seq = Sequence::new(vec![task.clone()]);
+
+        let scratch = get_scratch();
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(Config::from_str("directory: /dev").unwrap())
+            .with_console(console.clone())
+            .with_dry_run(true)
+            .build();
+
+        seq.apply_scratchpad(&core, &scratch).await.unwrap();
+
+        assert!(
+            task.ran_scratchpad.lock().await.is_none(),
+            "the task should not have been applied"
+        );
+        assert!(
+            console
+                .to_string()
+                .contains("[dry-run] Run the test task against scratch:2020w07"),
+            "the plan should describe the test task"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_repo() {
+        let task1 = Arc::new(TestTask::default());
+        let task2 = Arc::new(TestTask {
+            error: true,
+            ..Default::default()
+        });
+        let task3 = Arc::new(TestTask::default());
+        let seq = Sequence::new(vec![task1.clone(), task2.clone(), task3.clone()]);
+
+        let repo = get_repo();
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(Config::from_str("directory: /dev").unwrap())
+            .with_console(console.clone())
+            .build();
+
+        seq.apply_repo(&core, &repo)
+            .await
+            .expect_err("the sequence should fail");
+
+        assert!(
+            *task1.rolled_back.lock().await,
+            "the task applied before the failure should be rolled back"
+        );
+        assert!(
+            !*task2.rolled_back.lock().await,
+            "the failing task should not be rolled back"
+        );
+        assert!(
+            task3.ran_repo.lock().await.is_none(),
+            "tasks after the failure should not be applied"
+        );
+
+        let output = console.to_string();
+        assert!(
+            output.contains(" - Run the test task against gh:sierrasoftworks/git-tool (reverted)"),
+            "the rollback should be reported: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_nested_sequence() {
+        let task1 = Arc::new(TestTask::default());
+        let task2 = Arc::new(TestTask::default());
+        let seq = Sequence::new(vec![
+            Arc::new(Sequence::new(vec![task1.clone(), task2.clone()])),
+            Arc::new(TestTask {
+                error: true,
+                ..Default::default()
+            }),
+        ]);
+
+        let repo = get_repo();
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config(Config::from_str("directory: /dev").unwrap())
+            .with_console(console.clone())
+            .build();
+
+        seq.apply_repo(&core, &repo)
+            .await
+            .expect_err("the sequence should fail");
+
+        assert!(
+            *task1.rolled_back.lock().await && *task2.rolled_back.lock().await,
+            "the tasks in the nested sequence should be rolled back"
+        );
+
+        let output = console.to_string();
+        assert!(
+            output.contains(" - Run the test task against gh:sierrasoftworks/git-tool (reverted)"),
+            "the rollback should be reported: {output}"
+        );
+        assert!(
+            !output.contains("not reverted"),
+            "the nested sequence should not be reported as unsupported: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_reports_unsupported() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = Repo::new("gh:sierrasoftworks/git-tool", temp.path().join("repo"));
+        std::fs::create_dir_all(repo.get_path()).unwrap();
+
+        let seq = Sequence::new(vec![
+            Arc::new(WriteFile {
+                path: "README.md".into(),
+                content: "Hello World",
+            }),
+            Arc::new(TestTask {
+                error: true,
+                ..Default::default()
+            }),
+        ]);
+
+        let console = crate::console::mock();
+        let core = Core::builder()
+            .with_config_for_dev_directory(temp.path())
+            .with_console(console.clone())
+            .build();
+
+        seq.apply_repo(&core, &repo)
+            .await
+            .expect_err("the sequence should fail");
+
+        let output = console.to_string();
+        assert!(
+            output.contains(&format!(
+                " - Write 11 bytes to '{}' (not reverted)",
+                repo.get_path().join("README.md").display()
+            )),
+            "the unsupported rollback should be reported: {output}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rollback_scratchpad() {
+        let task1 = Arc::new(TestTask::default());
+        let task2 = Arc::new(TestTask {
+            error: true,
+            ..Default::default()
+        });
+        let seq = Sequence::new(vec![task1.clone(), task2.clone()]);
+
+        let scratch = get_scratch();
+        let core = Core::builder()
+            .with_config(Config::from_str("directory: /dev").unwrap())
+            .with_null_console()
+            .build();
+
+        seq.apply_scratchpad(&core, &scratch)
+            .await
+            .expect_err("the sequence should fail");
+
+        assert!(*task1.rolled_back.lock().await);
+        assert!(!*task2.rolled_back.lock().await);
+    }
+
     fn get_repo() -> Repo {
         Repo::new(
             "gh:sierrasoftworks/git-tool",
@@ -15,6 +15,22 @@ impl Task for WriteFile<'_> {
         "write-file"
     }
 
+    fn describe_repo(&self, _core: &Core, repo: &engine::Repo) -> Vec<String> {
+        vec![format!(
+            "Write {} bytes to '{}'",
+            self.content.len(),
+            repo.get_path().join(&self.path).display()
+        )]
+    }
+
+    fn describe_scratchpad(&self, _core: &Core, scratch: &engine::Scratchpad) -> Vec<String> {
+        vec![format!(
+            "Write {} bytes to '{}'",
+            self.content.len(),
+            scratch.get_path().join(&self.path).display()
+        )]
+    }
+
     #[tracing::instrument(name = "task:write_file(repo)", err, skip(self, _core))]
     async fn apply_repo(&self, _core: &Core, repo: &engine::Repo) -> Result<(), engine::Error> {
         let path = repo.get_path().join(&self.path);